@@ -1,3 +1,6 @@
+// Underscore-prefixed parameters must really be unused
+#![deny(unused_variables, clippy::used_underscore_binding)]
+
 use anyhow::{Context, Result};
 use clap::Parser;
 use crossterm::{
@@ -9,22 +12,27 @@ use crossterm::{
         self, disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen,
     },
 };
-use directories::ProjectDirs;
+use directories::{BaseDirs, ProjectDirs};
+use rand::rngs::SmallRng;
+use rand::SeedableRng;
 use ratatui::{
     backend::CrosstermBackend,
+    buffer::Buffer,
     layout::{Alignment, Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
+    symbols,
     text::{Line, Span},
-    widgets::{Block, Borders, List, ListItem, Paragraph},
+    widgets::{Block, Borders, Cell, Clear, List, ListItem, Paragraph, Row, Table, Wrap},
     Frame, Terminal,
 };
 use serde::{Deserialize, Serialize};
-use std::collections::{HashMap, VecDeque};
-use std::fs;
-use std::io;
-use std::path::PathBuf;
-use std::process::Command;
-use std::time::Instant;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, Stdio};
+use std::sync::{mpsc, Mutex, OnceLock};
+use std::time::{Duration, Instant};
 
 // ============================================================================
 // CONFIGURATION
@@ -33,6 +41,7 @@ use std::time::Instant;
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Config {
     /// Window title shown in the border
+    /// Supports {hostname}, {user}, {time} (HH:MM) and {date} (YYYY-MM-DD)
     pub title: String,
     /// Alignment of the title: "left", "center", "right"
     pub title_alignment: String,
@@ -64,12 +73,26 @@ pub struct Config {
     /// Layout mode: "vertical", "horizontal", "grid", "compact"
     pub layout_mode: String,
 
-    /// Window manager type: "auto", "hyprland", "sway", "i3", "bspwm", "awesome"
+    /// Window manager type: "auto", "hyprland", "sway", "i3", "bspwm", "awesome",
+    /// "kwin", "openbox", "mutter", "fluxbox", "fvwm", "niri", "river", "labwc"
     pub wm_type: String,
 
+    /// Processes sent SIGTERM before the logout command runs, kanshi and
+    /// wlr-randr by default, so helpers started by the session don't outlive it
+    #[serde(default = "default_pre_logout_kill")]
+    pub pre_logout_kill: Vec<String>,
+
     /// Grace period configuration for critical actions
     pub grace_period: GracePeriodConfig,
 
+    /// Run an action automatically after a period without input
+    #[serde(default)]
+    pub auto_trigger: AutoTriggerConfig,
+
+    /// Accessibility settings; active when the `[accessibility]` table is present
+    #[serde(default)]
+    pub accessibility: Option<AccessibilityConfig>,
+
     /// Theme file to load (optional)
     pub theme: Option<String>,
 
@@ -78,16 +101,159 @@ pub struct Config {
 
     /// Performance settings
     pub performance: PerformanceSettings,
+
+    /// Focus "Yes" instead of "No" when the confirmation dialog opens
+    #[serde(default)]
+    pub confirm_default_yes: bool,
+
+    /// Cancel the confirmation dialog after this many seconds without an answer
+    #[serde(default)]
+    pub confirm_timeout_secs: Option<u64>,
+
+    /// Close the menu after this many seconds without a key press
+    #[serde(default)]
+    pub auto_quit_secs: Option<u64>,
+
+    /// Label of the confirm button in the confirmation dialog
+    #[serde(default = "default_confirm_yes_label")]
+    pub confirm_yes_label: String,
+
+    /// Label of the cancel button in the confirmation dialog
+    #[serde(default = "default_confirm_no_label")]
+    pub confirm_no_label: String,
+
+    /// Show "on user@hostname" below the question in the confirmation dialog
+    #[serde(default = "default_true")]
+    pub show_host_in_confirm: bool,
+
+    /// Show how many applications are open when confirming logout or shutdown
+    #[serde(default)]
+    pub show_open_windows: bool,
+
+    /// Open application count above which the warning is shown in yellow
+    #[serde(default = "default_warn_open_windows_threshold")]
+    pub warn_open_windows_threshold: usize,
+
+    /// Allow action shortcuts from the animation menu and confirmation dialog
+    #[serde(default = "default_true")]
+    pub global_shortcuts: bool,
+
+    /// How often template variables in the title are refreshed, in seconds
+    #[serde(default = "default_title_update_interval_secs")]
+    pub title_update_interval_secs: u64,
+
+    /// Entries moved per mouse wheel step in menus (1-5)
+    #[serde(default = "default_mouse_scroll_speed")]
+    pub mouse_scroll_speed: u8,
+
+    /// Where action icons are drawn: "left", "right", "none"
+    #[serde(default = "default_icon_position")]
+    pub icon_position: String,
+
+    /// Show each action's shortcut key next to its label
+    #[serde(default = "default_true")]
+    pub show_shortcut: bool,
+
+    /// Spaces between an action's icon and its label
+    #[serde(default = "default_icon_padding")]
+    pub icon_padding: u8,
+
+    /// Cells every icon takes up, 1 or 2, overriding each action's `icon_cell_width`
+    #[serde(default)]
+    pub force_icon_width: Option<u8>,
+
+    /// Line up icons, shortcuts and labels of the vertical list in columns
+    #[serde(default)]
+    pub align_columns: bool,
+
+    /// Group the vertical list by action category with divider rows
+    #[serde(default)]
+    pub show_categories: bool,
+
+    /// Kill a running action after this many seconds (0 = wait forever)
+    #[serde(default = "default_command_timeout_secs")]
+    pub command_timeout_secs: u64,
+
+    /// Menu order: "default", "alphabetical", "frequency", "recent"
+    #[serde(default = "default_sort_mode")]
+    pub sort_mode: String,
+
+    /// Start on the last executed action instead of the first one
+    #[serde(default = "default_true")]
+    pub remember_last: bool,
+
+    /// Keep the animation out of the menu box so its text stays readable
+    #[serde(default = "default_true")]
+    pub animation_behind_menu: bool,
+
+    /// Directories scanned for .desktop files marked as rexit actions
+    #[serde(default = "default_desktop_actions_dirs")]
+    pub desktop_actions_dirs: Vec<String>,
+
+    /// Append timestamped state transitions to this file, for debugging
+    #[serde(default)]
+    pub debug_log: Option<PathBuf>,
+}
+
+fn default_icon_position() -> String {
+    "left".to_string()
+}
+
+fn default_icon_padding() -> u8 {
+    1
+}
+
+fn default_warn_open_windows_threshold() -> usize {
+    5
+}
+
+fn default_title_update_interval_secs() -> u64 {
+    60
+}
+
+fn default_mouse_scroll_speed() -> u8 {
+    1
+}
+
+fn default_command_timeout_secs() -> u64 {
+    30
+}
+
+fn default_sort_mode() -> String {
+    "default".to_string()
+}
+
+fn default_desktop_actions_dirs() -> Vec<String> {
+    vec!["${XDG_DATA_HOME:-$HOME/.local/share}/applications".to_string()]
+}
+
+fn default_pre_logout_kill() -> Vec<String> {
+    vec!["kanshi".to_string(), "wlr-randr".to_string()]
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_confirm_yes_label() -> String {
+    "[Y] Yes".to_string()
+}
+
+fn default_confirm_no_label() -> String {
+    "[N] No".to_string()
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PerformanceSettings {
     /// Enable automatic quality reduction under high CPU load
     pub auto_degrade: bool,
-    /// Target frame time in milliseconds (higher = less CPU usage)
+    /// Upper bound for the frame rate (lower = less CPU usage)
     pub target_fps: u32,
     /// Disable animations when battery is low (laptops)
     pub disable_on_low_battery: bool,
+    /// Show FPS and frame time in the top-right corner
+    #[serde(default)]
+    pub debug_overlay: bool,
 }
 
 impl Default for PerformanceSettings {
@@ -96,6 +262,7 @@ impl Default for PerformanceSettings {
             auto_degrade: true,
             target_fps: 30,
             disable_on_low_battery: false,
+            debug_overlay: false,
         }
     }
 }
@@ -103,7 +270,35 @@ impl Default for PerformanceSettings {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BorderConfig {
     pub enabled: bool,
-    pub style: String, // "plain", "rounded", "double", "thick"
+    pub style: String, // "plain", "rounded", "double", "thick", "custom"
+    /// Glyphs used when `style = "custom"`
+    #[serde(default)]
+    pub border_chars: Option<BorderCharsConfig>,
+}
+
+/// Border glyphs for the "custom" style, one character each
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BorderCharsConfig {
+    pub top_left: String,
+    pub top_right: String,
+    pub bottom_left: String,
+    pub bottom_right: String,
+    pub horizontal: String,
+    pub vertical: String,
+}
+
+impl Default for BorderCharsConfig {
+    fn default() -> Self {
+        Self {
+            top_left: "┌".to_string(),
+            top_right: "┐".to_string(),
+            bottom_left: "└".to_string(),
+            bottom_right: "┘".to_string(),
+            horizontal: "─".to_string(),
+            vertical: "│".to_string(),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -118,6 +313,14 @@ pub struct ColorConfig {
     pub help_fg: String,
     pub help_key_fg: String,
     pub help_key_modifier: Vec<String>,
+    /// Opacity of the animation background, 0-255 (default: 255 = opaque)
+    /// 0 leaves the terminal's own, possibly transparent, background visible
+    #[serde(default = "default_background_opacity")]
+    pub background_opacity: u8,
+}
+
+fn default_background_opacity() -> u8 {
+    255
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -126,6 +329,41 @@ pub struct KeyConfig {
     pub down: Vec<String>,
     pub select: Vec<String>,
     pub quit: Vec<String>,
+    /// Keys that confirm the action in the confirmation dialog
+    #[serde(default = "default_confirm_yes_keys")]
+    pub confirm_yes_keys: Vec<String>,
+    /// Keys that cancel the action in the confirmation dialog
+    #[serde(default = "default_confirm_no_keys")]
+    pub confirm_no_keys: Vec<String>,
+    /// Keys that open the keybinding overlay
+    #[serde(default = "default_help_keys")]
+    pub help: Vec<String>,
+    /// Keys that open the animation menu
+    #[serde(default = "default_animation_menu_keys")]
+    pub animation_menu: Vec<String>,
+    /// Keys that open the theme menu
+    #[serde(default = "default_theme_menu_keys")]
+    pub theme_menu: Vec<String>,
+}
+
+fn default_confirm_yes_keys() -> Vec<String> {
+    vec!["y".to_string(), "Y".to_string()]
+}
+
+fn default_confirm_no_keys() -> Vec<String> {
+    vec!["n".to_string(), "N".to_string()]
+}
+
+fn default_help_keys() -> Vec<String> {
+    vec!["?".to_string(), "F1".to_string()]
+}
+
+fn default_animation_menu_keys() -> Vec<String> {
+    vec!["a".to_string()]
+}
+
+fn default_theme_menu_keys() -> Vec<String> {
+    vec!["t".to_string()]
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -143,11 +381,36 @@ pub struct ActionConfig {
     pub favorite: bool,
     /// Optional keyboard shortcut for quick access (e.g., "s", "1", "Ctrl-s")
     pub shortcut: String,
+    /// Expand $VAR, ${VAR} and the `{session}`-style variables in command and
+    /// args before running (default: true); with `shell` the shell expands $VAR
+    /// itself, so shell variables like `$1` and loop variables keep working
+    #[serde(default = "default_true")]
+    pub expand_env: bool,
+    /// Run through `sh -c` so pipes, `&&` and redirects work (default: false)
+    #[serde(default)]
+    pub shell: bool,
+    /// Group shown above this action when `show_categories` is set (default: "Other")
+    #[serde(default)]
+    pub category: Option<String>,
+    /// Short description, e.g. the `Comment=` of an imported .desktop file
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Ids of actions shown in a sub-menu instead of running this action
+    #[serde(default)]
+    pub submenu: Option<Vec<String>>,
+    /// Cells the icon takes up, 1 or 2; shorter icons get trailing spaces
+    /// (default: the width of the icon's first character)
+    #[serde(default)]
+    pub icon_cell_width: Option<u8>,
+    /// Times a failed command is run again before the error is shown (default: 0)
+    #[serde(default)]
+    pub max_retries: u8,
 }
 
 /// Theme configuration for loading themes from files
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ThemeConfig {
+    #[serde(default)]
     pub name: String,
     pub colors: ColorConfig,
     pub border: BorderConfig,
@@ -158,46 +421,76 @@ pub struct ThemeConfig {
 #[derive(Debug, Clone)]
 pub struct PerformanceMonitor {
     last_check: Instant,
-    frame_times: VecDeque<u64>,
+    frame_start: Option<Instant>,
+    frame_times: VecDeque<Duration>,
     degraded_mode: bool,
-    last_frame_time: u64,
+    last_frame_time: Duration,
 }
 
-impl PerformanceMonitor {
-    pub fn new() -> Self {
+impl Default for PerformanceMonitor {
+    fn default() -> Self {
         Self {
             last_check: Instant::now(),
+            frame_start: None,
             frame_times: VecDeque::with_capacity(30),
             degraded_mode: false,
-            last_frame_time: 0,
+            last_frame_time: Duration::ZERO,
         }
     }
+}
+
+impl PerformanceMonitor {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Start timing a frame; pair with `end_frame` once it has been drawn
+    pub fn begin_frame(&mut self) {
+        self.frame_start = Some(Instant::now());
+    }
 
-    pub fn record_frame(&mut self, frame_time_ms: u64) {
-        self.last_frame_time = frame_time_ms;
-        self.frame_times.push_back(frame_time_ms);
+    /// Record the time since `begin_frame`, keeping the last 30 frames
+    pub fn end_frame(&mut self) {
+        let Some(start) = self.frame_start.take() else {
+            return;
+        };
+        self.last_frame_time = start.elapsed();
+        self.frame_times.push_back(self.last_frame_time);
         if self.frame_times.len() > 30 {
             self.frame_times.pop_front();
         }
     }
 
+    /// Average time of the recorded frames
+    pub fn avg_frame_time(&self) -> Duration {
+        if self.frame_times.is_empty() {
+            return Duration::ZERO;
+        }
+        self.frame_times.iter().sum::<Duration>() / self.frame_times.len() as u32
+    }
+
+    /// Frames per second the renderer could sustain at the average frame time
+    pub fn avg_fps(&self) -> f64 {
+        let avg = self.avg_frame_time().as_secs_f64();
+        if avg > 0.0 {
+            1.0 / avg
+        } else {
+            0.0
+        }
+    }
+
+    pub fn last_frame_time(&self) -> Duration {
+        self.last_frame_time
+    }
+
     pub fn update(&mut self) {
         let now = Instant::now();
         let elapsed = now.duration_since(self.last_check).as_secs();
 
         if elapsed >= 2 {
-            // Calculate average frame time
-            let avg_frame_time = if !self.frame_times.is_empty() {
-                self.frame_times.iter().sum::<u64>() / self.frame_times.len() as u64
-            } else {
-                0
-            };
-
             // Enable degraded mode if frame times are consistently long (>100ms)
-            self.degraded_mode = avg_frame_time > 100 || self.last_frame_time > 150;
-
-            // Clear frame times for next measurement period
-            self.frame_times.clear();
+            self.degraded_mode = self.avg_frame_time() > Duration::from_millis(100)
+                || self.last_frame_time > Duration::from_millis(150);
             self.last_check = now;
         }
     }
@@ -209,37 +502,80 @@ impl PerformanceMonitor {
     pub fn should_skip_frame(&self, frame_counter: u64) -> bool {
         if self.degraded_mode {
             // Skip every other frame in degraded mode
-            frame_counter % 2 == 0
+            frame_counter.is_multiple_of(2)
         } else {
             false
         }
     }
 }
 
-/// Check if Nerd Fonts are available in the terminal
-pub fn has_nerd_fonts() -> bool {
-    // Check environment variable override
-    if let Ok(val) = std::env::var("REXIT_USE_EMOJI") {
-        return val != "1" && val != "true";
-    }
+/// Terminals known to render Nerd Font glyphs and 24-bit color
+const MODERN_TERMINALS: &[&str] = &["iterm", "kitty", "wezterm", "alacritty", "ghostty"];
+
+/// What the terminal supports, probed from the environment
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TermCapabilities {
+    /// 24-bit RGB colors
+    pub truecolor: bool,
+    /// The xterm 256-color palette
+    pub colors_256: bool,
+    /// Nerd Font icons
+    pub nerd_fonts: bool,
+}
 
-    // Try to detect by checking common Nerd Font indicators
-    if let Ok(term_program) = std::env::var("TERM_PROGRAM") {
-        match term_program.as_str() {
-            "Apple_Terminal" => return false, // macOS Terminal doesn't support Nerd Fonts well
-            _ => {}
+impl TermCapabilities {
+    pub fn detect() -> Self {
+        let var = |name: &str| std::env::var(name).unwrap_or_default().to_lowercase();
+        let term_program = var("TERM_PROGRAM");
+        let term = var("TERM");
+        let colorterm = var("COLORTERM");
+
+        // Kitty sets KITTY_WINDOW_ID even when TERM is overridden, e.g. inside ssh
+        let modern = std::env::var_os("KITTY_WINDOW_ID").is_some()
+            || MODERN_TERMINALS
+                .iter()
+                .any(|name| term_program.contains(name) || term.contains(name));
+
+        let truecolor = modern || colorterm == "truecolor" || colorterm == "24bit";
+        let colors_256 = truecolor || term.contains("256color");
+
+        // macOS Terminal and the Linux console can't draw Nerd Font glyphs;
+        // other terminals are assumed to have a patched font
+        let nerd_fonts = modern || (term_program != "apple_terminal" && term != "linux");
+
+        Self {
+            truecolor,
+            colors_256,
+            nerd_fonts,
         }
     }
 
-    // Check if we're in a Linux console (no Nerd Fonts support)
-    if let Ok(term) = std::env::var("TERM") {
-        if term == "linux" {
-            return false;
+    /// Short description of the color support, e.g. for `--check-config`
+    pub fn color_depth(&self) -> &'static str {
+        if self.truecolor {
+            "24-bit"
+        } else if self.colors_256 {
+            "256 colors"
+        } else {
+            "16 colors"
         }
     }
+}
 
-    // Default to assuming Nerd Fonts are available on modern terminals
-    true
+/// Capabilities of the current terminal, detected once per run
+pub fn term_capabilities() -> TermCapabilities {
+    static CAPABILITIES: OnceLock<TermCapabilities> = OnceLock::new();
+    *CAPABILITIES.get_or_init(TermCapabilities::detect)
+}
+
+/// Check if Nerd Fonts are available in the terminal
+pub fn has_nerd_fonts() -> bool {
+    // Check environment variable override
+    if let Ok(val) = std::env::var("REXIT_USE_EMOJI") {
+        return val != "1" && val != "true";
+    }
+
+    term_capabilities().nerd_fonts
 }
 
 /// Get the appropriate icon based on Nerd Font availability
@@ -247,7 +583,7 @@ pub fn get_icon(config: &ActionConfig) -> &str {
     if has_nerd_fonts() {
         &config.icon
     } else {
-        config.icon_fallback.as_deref().unwrap_or_else(|| {
+        config.icon_fallback.as_deref().unwrap_or(
             // Default emoji fallbacks
             match config.icon.as_str() {
                 "⏻" => "⏻",
@@ -257,8 +593,20 @@ pub fn get_icon(config: &ActionConfig) -> &str {
                 "🚪" => "🚪",
                 "❌" => "❌",
                 _ => "•", // default bullet
-            }
-        })
+            },
+        )
+    }
+}
+
+/// Icon shown for an action, preferring the emoji fallback when requested
+fn resolve_icon(config: &ActionConfig, use_emoji: bool) -> String {
+    if use_emoji {
+        config
+            .icon_fallback
+            .clone()
+            .unwrap_or_else(|| get_icon(config).to_string())
+    } else {
+        config.icon.clone()
     }
 }
 
@@ -267,6 +615,9 @@ pub struct HelpConfig {
     pub enabled: bool,
     pub template: String,
     pub separator: String,
+    /// Show "? for help" in the help bar (default: true)
+    #[serde(default = "default_true")]
+    pub show_help_overlay_hint: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -283,6 +634,35 @@ pub struct LayoutConfig {
     pub max_width: u16,
     /// Padding inside the menu box (default: 1)
     pub padding: u16,
+    /// Maximum number of actions shown at once in the vertical layout; the list scrolls beyond that
+    #[serde(default)]
+    pub max_visible_items: Option<usize>,
+    /// Vertical placement: "top", "center", "bottom", "custom" (default: "center")
+    /// "top"/"bottom" keep `vertical_margin` rows to that edge, "custom" treats it
+    /// as a percentage of the free space (0 = top, 100 = bottom)
+    #[serde(default = "default_layout_position")]
+    pub position: String,
+    /// Fill the whole terminal with the vertical menu instead of a centered box
+    #[serde(default)]
+    pub fullscreen: bool,
+    /// Character filling the space around items in fullscreen mode (default: " ")
+    /// A blank leaves that space to the background animation
+    #[serde(default = "default_fullscreen_bg_char")]
+    pub fullscreen_bg_char: String,
+    /// Columns of the grid layout; unset picks 1-4 from the number of actions
+    #[serde(default)]
+    pub grid_columns: Option<u16>,
+    /// Page dots below the vertical menu when not all actions fit (default: true)
+    #[serde(default = "default_true")]
+    pub show_scroll_indicator: bool,
+}
+
+fn default_fullscreen_bg_char() -> String {
+    " ".to_string()
+}
+
+fn default_layout_position() -> String {
+    "center".to_string()
 }
 
 /// Responsive layout configuration for adapting to terminal size
@@ -301,6 +681,26 @@ pub struct ResponsiveConfig {
     /// Minimum terminal dimensions to show the UI (default: 20x5)
     pub min_terminal_width: u16,
     pub min_terminal_height: u16,
+    /// Below these dimensions the help bar warns that elements may be cut (default: 40x25)
+    #[serde(default = "default_warn_terminal_width")]
+    pub warn_terminal_width: u16,
+    #[serde(default = "default_warn_terminal_height")]
+    pub warn_terminal_height: u16,
+    /// With fewer actions, `layout_mode = "compact"` uses the vertical layout (default: 4)
+    #[serde(default = "default_min_actions_for_compact")]
+    pub min_actions_for_compact: usize,
+}
+
+fn default_warn_terminal_width() -> u16 {
+    40
+}
+
+fn default_warn_terminal_height() -> u16 {
+    25
+}
+
+fn default_min_actions_for_compact() -> usize {
+    4
 }
 
 impl Default for ResponsiveConfig {
@@ -313,6 +713,9 @@ impl Default for ResponsiveConfig {
             hide_border_when_small: true,
             min_terminal_width: 20,
             min_terminal_height: 5,
+            warn_terminal_width: default_warn_terminal_width(),
+            warn_terminal_height: default_warn_terminal_height(),
+            min_actions_for_compact: default_min_actions_for_compact(),
         }
     }
 }
@@ -320,21 +723,262 @@ impl Default for ResponsiveConfig {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AnimationConfig {
     /// Enable background animation
+    #[serde(default = "default_true")]
     pub enabled: bool,
-    /// Animation type: "matrix", "matrix_cjk", "rain", "thunder", "snow", "stars", "fireflies", "fireworks", "neon_grid", "perlin_flow", "cube_3d", "fractals", "bubbles", "confetti", "wave", "particles", "digital_rain", "heartbeat", "plasma", "scanlines", "aurora", "autumn", "dna", "synthwave", "smoke", "gradient_flow", "constellation", "fish_tank", "typing_code", "vortex", "circuit", "flow_field", "morse", "lissajous", "game_of_life", "ocean", "ripple", "fog", "flames", "sparks", "lava_lamp", "sun", "galaxy", "meteor_shower", "satellite", "pulsar", "pong", "snake", "tetris", "invaders", "fibonacci", "mandelbrot", "hex_grid", "rose", "butterflies", "spider_web", "vine_growth", "moss", "radar", "binary_clock", "signal", "wifi", "paint_splatter", "ink_bleed", "mosaic", "stained_glass", "hologram", "glitch", "old_film", "thermal", "none"
+    /// Animation type: "matrix", "matrix_cjk", "matrix_vertical", "matrix_diagonal", "rain", "rain_window", "thunder", "snow", "stars", "starfield_3d", "fireflies", "fireworks", "neon_grid", "perlin_flow", "cube_3d", "fractals", "bubbles", "confetti", "wave", "particles", "digital_rain", "heartbeat", "plasma", "plasma_pulse", "plasma_globe", "scanlines", "aurora", "aurora_borealis", "autumn", "dna", "synthwave", "smoke", "gradient_flow", "constellation", "fish_tank", "typing_code", "vortex", "circuit", "flow_field", "morse", "lissajous", "game_of_life", "cellular_automata", "ocean", "ripple", "fog", "flames", "sparks", "lava_lamp", "sun", "galaxy", "meteor_shower", "satellite", "pulsar", "pong", "snake", "tetris", "invaders", "fibonacci", "mandelbrot", "hex_grid", "rose", "butterflies", "spider_web", "vine_growth", "moss", "radar", "binary_clock", "signal", "wifi", "paint_splatter", "ink_bleed", "mosaic", "stained_glass", "hologram", "glitch", "old_film", "thermal", "typewriter", "clock", "waveform", "northern_lights", "dvd_logo", "bouncing_balls", "random", "none"
     pub animation_type: String,
     /// Animation speed in milliseconds (lower = faster)
     pub speed_ms: u64,
     /// Animation color (for single-color animations)
     pub color: String,
-    /// Animation density (0-100, higher = more particles)
+    /// Animation density (1-100, higher = more particles)
     pub density: u8,
     /// Reduce animation quality when CPU is high (default: true)
     pub adaptive_quality: bool,
     /// Minimum animation speed in degraded mode (default: 200ms)
     pub min_speed_ms: u64,
+    /// Animations never picked by "random" or when cycling
+    #[serde(default)]
+    pub random_exclude: Vec<String>,
+    /// Switch to another random animation every `cycle_interval_secs`
+    #[serde(default)]
+    pub cycle_animations: bool,
+    /// Seconds between animation switches when cycling (default: 30)
+    #[serde(default = "default_cycle_interval_secs")]
+    pub cycle_interval_secs: u64,
+    /// Second animation drawn on top of `animation_type`, e.g. "particles" (default: unset)
+    #[serde(default)]
+    pub animation_blend: Option<String>,
+    /// Opacity of the `animation_blend` layer, 0-255 (default: 128)
+    #[serde(default = "default_blend_opacity")]
+    pub blend_opacity: u8,
+    /// Message spelled by the "morse" animation (default: the GNU/Linux interjection)
+    #[serde(default)]
+    pub morse_text: Option<String>,
+    /// Pace of the "morse" animation: "slow", "normal", "fast" (default: "normal")
+    #[serde(default = "default_morse_speed")]
+    pub morse_speed: String,
+    /// Start the morse message over once it has been spelled (default: true)
+    #[serde(default = "default_true")]
+    pub morse_loop: bool,
+    /// Pattern stamped in the middle of "game_of_life": "random", "glider",
+    /// "r_pentomino", "gosper_glider_gun", "pulsar" (default: "random")
+    #[serde(default = "default_gol_pattern")]
+    pub gol_pattern: String,
+    /// Share of "game_of_life" cells alive at the start, 0.0-1.0 (default: 0.3)
+    #[serde(default = "default_gol_density")]
+    pub gol_density: f64,
+    /// Lines typed by the "typewriter" animation (default: the rexit logo)
+    #[serde(default = "default_typewriter_text")]
+    pub typewriter_text: Vec<String>,
+    /// Ticks the "typewriter" waits once the text is typed or erased (default: 60)
+    #[serde(default = "default_typewriter_pause_ticks")]
+    pub typewriter_pause_ticks: u64,
+    /// Column of the "typewriter" text (default: 2)
+    #[serde(default = "default_typewriter_x")]
+    pub typewriter_x: u16,
+    /// Row of the "typewriter" text (default: 1)
+    #[serde(default = "default_typewriter_y")]
+    pub typewriter_y: u16,
+    /// Time shown by the "clock" animation: "12h", "24h", "unix_timestamp" (default: "12h")
+    #[serde(default = "default_clock_format")]
+    pub clock_format: String,
+    /// Seconds between one-cell moves of the "clock" digits (default: 5)
+    #[serde(default = "default_clock_drift_secs")]
+    pub clock_drift_secs: u64,
+    /// Number of "waveform" bars, 5-80 (default: 20)
+    #[serde(default = "default_waveform_bars")]
+    pub waveform_bars: u8,
+    /// Mark each "waveform" bar's recent maximum with a slowly falling line (default: true)
+    #[serde(default = "default_true")]
+    pub waveform_peak_hold: bool,
+    /// Text bounced around by the "dvd_logo" animation (default: "rexit")
+    #[serde(default)]
+    pub dvd_text: Option<String>,
+    /// Leave a fading ghost behind the "dvd_logo" text (default: false)
+    #[serde(default)]
+    pub dvd_trail: bool,
+    /// Where "matrix" characters fall: "down", "up", "right", "left",
+    /// "diagonal_right", "diagonal_left" (default: "down")
+    #[serde(default = "default_matrix_direction")]
+    pub matrix_direction: String,
+    /// Heartbeat phase step per frame of the "plasma_pulse" animation (default: 0.15)
+    #[serde(default = "default_plasma_pulse_speed")]
+    pub plasma_pulse_speed: f32,
+    /// Heat spread per frame of the "thermal" animation, 0.0-0.25 (default: 0.1)
+    #[serde(default = "default_thermal_alpha")]
+    pub thermal_alpha: f32,
+    /// Distance a far "starfield_3d" star travels per frame (default: 0.02)
+    #[serde(default = "default_starfield_speed")]
+    pub starfield_speed: f32,
+    /// Number of "bouncing_balls" balls, 1-20 (default: 5)
+    #[serde(default = "default_ball_count")]
+    pub ball_count: u8,
+    /// Downward pull on the "bouncing_balls" balls per frame; above 0.0 they
+    /// also lose speed on every floor bounce (default: 0.0)
+    #[serde(default)]
+    pub ball_gravity: f32,
+    /// Restart the "snake" when it runs into itself; off, it crawls through
+    /// its own body (default: true)
+    #[serde(default = "default_true")]
+    pub snake_collision: bool,
+    /// Earlier "pong" ball positions drawn behind the ball (default: 3, 0 = none)
+    #[serde(default = "default_pong_ball_trail")]
+    pub pong_ball_trail: u8,
+    /// Burst of the "fireworks" rockets: "star", "ring", "weeping_willow" or
+    /// "random" for a different one each time (default: "star")
+    #[serde(default = "default_firework_style")]
+    pub firework_style: String,
+    /// Earlier points of each "lissajous" curve kept on screen, fading out
+    /// (default: 100, 0 = only the pen)
+    #[serde(default = "default_lissajous_trail")]
+    pub lissajous_trail: u16,
+    /// Farthest apart two "constellation" stars may be and still get a line
+    /// between them, in cells (default: 15.0)
+    #[serde(default = "default_constellation_connect_distance")]
+    pub constellation_connect_distance: f32,
+    /// Electrons running along the "circuit" traces (default: 5)
+    #[serde(default = "default_electron_count")]
+    pub electron_count: u8,
+    /// Light curtains of the "aurora_borealis" animation, 1-20 (default: 5)
+    #[serde(default = "default_aurora_curtain_count")]
+    pub aurora_curtain_count: u8,
+    /// Dim halo around each "aurora_borealis" curtain (default: true)
+    #[serde(default = "default_true")]
+    pub aurora_glow: bool,
+    /// Wolfram rule of the "cellular_automata" animation, e.g. 90 for a
+    /// Sierpinski triangle (default: 110)
+    #[serde(default = "default_ca_rule")]
+    pub ca_rule: u8,
+    /// Let "cellular_automata" cells at one edge see those at the other (default: true)
+    #[serde(default = "default_true")]
+    pub ca_wrap: bool,
+    /// Lightning arcs inside the "plasma_globe", 1-20 (default: 6)
+    #[serde(default = "default_plasma_globe_arcs")]
+    pub plasma_globe_arcs: u8,
+    /// Radius of the "plasma_globe" in rows (default: a third of the smaller
+    /// terminal side)
+    #[serde(default)]
+    pub plasma_globe_radius: Option<f32>,
+    /// Seed for reproducible animations, e.g. for screenshots (unset or 0 = random)
+    #[serde(default)]
+    pub seed: Option<u64>,
+}
+
+fn default_cycle_interval_secs() -> u64 {
+    30
+}
+
+fn default_blend_opacity() -> u8 {
+    128
+}
+
+fn default_morse_speed() -> String {
+    "normal".to_string()
+}
+
+fn default_gol_pattern() -> String {
+    "random".to_string()
+}
+
+fn default_gol_density() -> f64 {
+    0.3
+}
+
+/// Text of the "typewriter" animation when `typewriter_text` is not set
+const DEFAULT_TYPEWRITER_TEXT: &[&str] = &[
+    r" _ __ _____  _(_) |_",
+    r"| '__/ _ \ \/ / | __|",
+    r"| | |  __/>  <| | |_",
+    r"|_|  \___/_/\_\_|\__|",
+];
+
+fn default_typewriter_text() -> Vec<String> {
+    DEFAULT_TYPEWRITER_TEXT
+        .iter()
+        .map(|line| line.to_string())
+        .collect()
+}
+
+fn default_typewriter_pause_ticks() -> u64 {
+    60
+}
+
+fn default_typewriter_x() -> u16 {
+    2
+}
+
+fn default_typewriter_y() -> u16 {
+    1
+}
+
+fn default_clock_format() -> String {
+    "12h".to_string()
+}
+
+fn default_clock_drift_secs() -> u64 {
+    5
+}
+
+fn default_waveform_bars() -> u8 {
+    20
+}
+
+fn default_matrix_direction() -> String {
+    "down".to_string()
+}
+
+fn default_plasma_pulse_speed() -> f32 {
+    0.15
+}
+
+fn default_thermal_alpha() -> f32 {
+    0.1
+}
+
+fn default_starfield_speed() -> f32 {
+    0.02
+}
+
+fn default_ball_count() -> u8 {
+    5
+}
+
+fn default_pong_ball_trail() -> u8 {
+    3
+}
+
+fn default_firework_style() -> String {
+    "star".to_string()
+}
+
+fn default_lissajous_trail() -> u16 {
+    100
+}
+
+fn default_constellation_connect_distance() -> f32 {
+    15.0
+}
+
+fn default_electron_count() -> u8 {
+    5
+}
+
+fn default_aurora_curtain_count() -> u8 {
+    5
+}
+
+fn default_ca_rule() -> u8 {
+    110
+}
+
+fn default_plasma_globe_arcs() -> u8 {
+    6
 }
 
+/// Message of the "morse" animation when `morse_text` is not set
+const DEFAULT_MORSE_TEXT: &str = "I'd just like to interject for a moment. What you're refering to as Linux, is in fact, GNU/Linux, or as I've recently taken to calling it, GNU plus Linux. Linux is not an operating system unto itself, but rather another free component of a fully functioning GNU system made useful by the GNU corelibs, shell utilities and vital system components comprising a full OS as defined by POSIX. Many computer users run a modified version of the GNU system every day, without realizing it. Through a peculiar turn of events, the version of GNU which is widely used today is often called Linux, and many of its users are not aware that it is basically the GNU system, developed by the GNU Project. There really is a Linux, and these people are using it, but it is just a part of the system they use. Linux is the kernel: the program in the system that allocates the machine's resources to the other programs that you run. The kernel is an essential part of an operating system, but useless by itself; it can only function in the context of a complete operating system. Linux is normally used in combination with the GNU operating system: the whole system is basically GNU with Linux added, or GNU/Linux. All the so-called Linux distributions are really distributions of GNU/Linux!";
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GracePeriodConfig {
     /// Enable grace period for critical actions (default: true)
@@ -345,6 +989,209 @@ pub struct GracePeriodConfig {
     pub show_countdown: bool,
     /// Text shown during grace period countdown
     pub message_template: String,
+    /// Countdown visualization: "bar", "ring", "digits" (default: "bar")
+    #[serde(default = "default_progress_style")]
+    pub progress_style: String,
+    /// Send a desktop notification right before a critical action runs (default: false)
+    #[serde(default)]
+    pub pre_notify: bool,
+    /// Urgency passed to the notification command with -u (default: "critical")
+    #[serde(default = "default_pre_notify_urgency")]
+    pub pre_notify_urgency: String,
+    /// Notification command (default: "notify-send")
+    #[serde(default = "default_notify_command")]
+    pub notify_command: String,
+    /// Extra arguments passed to the notification command before the message
+    #[serde(default)]
+    pub notify_args: Vec<String>,
+    /// Hold a systemd-logind delay inhibitor during the countdown (default: false)
+    #[serde(default)]
+    pub acquire_inhibitor: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AutoTriggerConfig {
+    /// Trigger `action_id` when the menu sits idle (default: false)
+    pub enabled: bool,
+    /// Seconds without key or mouse input before the action runs (default: 300)
+    pub idle_secs: u64,
+    /// Id of the action to trigger (default: "lock")
+    pub action_id: String,
+    /// Show "Idle: Ns" in the help bar once fewer seconds than this remain (default: 10)
+    pub idle_warning_secs: u64,
+}
+
+impl Default for AutoTriggerConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            idle_secs: 300,
+            action_id: "lock".to_string(),
+            idle_warning_secs: 10,
+        }
+    }
+}
+
+/// Accessibility options for low-vision users and low-contrast terminals
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AccessibilityConfig {
+    /// White selection and border on black text; also turns animations off
+    /// unless `animation.enabled` is set explicitly (default: false)
+    pub high_contrast: bool,
+    /// Draw the selected item in bold (default: false)
+    pub bold_selected: bool,
+    /// Text put in front of the selected item (default: "▶ ")
+    pub selection_prefix: String,
+    /// Prefix each item with its 1-based position (default: false)
+    pub show_item_numbers: bool,
+}
+
+impl Default for AccessibilityConfig {
+    fn default() -> Self {
+        Self {
+            high_contrast: false,
+            bold_selected: false,
+            selection_prefix: "▶ ".to_string(),
+            show_item_numbers: false,
+        }
+    }
+}
+
+/// Override colors for the accessibility mode; called again after each theme merge
+fn apply_accessibility(config: &mut Config) {
+    let Some(accessibility) = &config.accessibility else {
+        return;
+    };
+    if accessibility.high_contrast {
+        config.colors.selected_bg = "white".to_string();
+        config.colors.selected_fg = "black".to_string();
+        config.colors.border = "white".to_string();
+    }
+    if accessibility.bold_selected
+        && !config
+            .colors
+            .selected_modifier
+            .iter()
+            .any(|m| m.eq_ignore_ascii_case("bold"))
+    {
+        config.colors.selected_modifier.push("bold".to_string());
+    }
+}
+
+/// High contrast turns animations off unless the config file enables them itself
+fn apply_high_contrast_animation(config: &mut Config, content: &str) {
+    let high_contrast = config
+        .accessibility
+        .as_ref()
+        .is_some_and(|a| a.high_contrast);
+    if !high_contrast {
+        return;
+    }
+    let explicit = toml::from_str::<toml::Table>(content)
+        .ok()
+        .and_then(|table| table.get("animation")?.get("enabled").cloned())
+        .is_some();
+    if !explicit {
+        config.animation.enabled = false;
+    }
+}
+
+/// Clamp `value` to `min..=max`, warning on stderr when it was out of range
+fn clamp_setting<T>(name: &str, value: &mut T, min: T, max: T)
+where
+    T: PartialOrd + Copy + std::fmt::Display,
+{
+    let clamped = if *value < min {
+        min
+    } else if *value > max {
+        max
+    } else {
+        return;
+    };
+    eprintln!(
+        "Warning: {} = {} is out of range ({}-{}), using {}",
+        name, value, min, max, clamped
+    );
+    *value = clamped;
+}
+
+/// Keep animation values in the range the animations are written for
+fn validate_animation_config(anim: &mut AnimationConfig) {
+    clamp_setting("animation.density", &mut anim.density, 1, 100);
+    clamp_setting("animation.speed_ms", &mut anim.speed_ms, 10, 5000);
+    clamp_setting("animation.min_speed_ms", &mut anim.min_speed_ms, 50, 5000);
+    // Larger steps make the heat equation blow up
+    let alpha = &mut anim.thermal_alpha;
+    clamp_setting("animation.thermal_alpha", alpha, 0.0, 0.25);
+    // The globe is drawn point by point, so its size bounds the work per frame
+    if let Some(radius) = &mut anim.plasma_globe_radius {
+        clamp_setting("animation.plasma_globe_radius", radius, 2.0, 250.0);
+    }
+    if let Some(blend) = &anim.animation_blend {
+        if !ANIMATION_TYPES.contains(&blend.as_str()) {
+            eprintln!(
+                "Warning: Unknown animation '{}' in animation.animation_blend",
+                blend
+            );
+            anim.animation_blend = None;
+        } else if shares_animation_state(blend, &anim.animation_type) {
+            eprintln!(
+                "Warning: animation.animation_blend '{}' can't be combined with '{}'",
+                blend, anim.animation_type
+            );
+            anim.animation_blend = None;
+        }
+    }
+}
+
+/// Animations that keep their frames in the same `AnimationState` fields, so
+/// running two of them at once would update that state twice per frame
+const SHARED_STATE_ANIMATIONS: &[&[&str]] = &[
+    &[
+        "matrix",
+        "matrix_vertical",
+        "matrix_diagonal",
+        "digital_rain",
+        "matrix_cjk",
+    ],
+    &["plasma", "plasma_pulse", "heartbeat"],
+];
+
+/// Whether animations `a` and `b` can't be layered over each other
+fn shares_animation_state(a: &str, b: &str) -> bool {
+    a == b
+        || SHARED_STATE_ANIMATIONS
+            .iter()
+            .any(|group| group.contains(&a) && group.contains(&b))
+}
+
+/// Keep the menu box large enough to hold a label
+fn validate_layout_config(layout: &mut LayoutConfig) {
+    clamp_setting("layout.min_width", &mut layout.min_width, 10, u16::MAX);
+    clamp_setting("layout.min_height", &mut layout.min_height, 3, u16::MAX);
+    clamp_setting("layout.padding", &mut layout.padding, 0, 5);
+}
+
+/// Clamp out-of-range values of a freshly loaded config
+fn validate_config(config: &mut Config) {
+    validate_animation_config(&mut config.animation);
+    validate_layout_config(&mut config.layout);
+    let fps = &mut config.performance.target_fps;
+    clamp_setting("performance.target_fps", fps, 1, 120);
+}
+
+fn default_pre_notify_urgency() -> String {
+    "critical".to_string()
+}
+
+fn default_notify_command() -> String {
+    "notify-send".to_string()
+}
+
+fn default_progress_style() -> String {
+    "bar".to_string()
 }
 
 impl Default for Config {
@@ -363,6 +1210,13 @@ impl Default for Config {
                 confirm: true,
                 favorite: true,
                 shortcut: "s".to_string(),
+                expand_env: true,
+                shell: false,
+                category: None,
+                description: None,
+                submenu: None,
+                icon_cell_width: None,
+                max_retries: 0,
             },
         );
 
@@ -378,6 +1232,13 @@ impl Default for Config {
                 confirm: true,
                 favorite: true,
                 shortcut: "r".to_string(),
+                expand_env: true,
+                shell: false,
+                category: None,
+                description: None,
+                submenu: None,
+                icon_cell_width: None,
+                max_retries: 0,
             },
         );
 
@@ -393,6 +1254,13 @@ impl Default for Config {
                 confirm: false,
                 favorite: false,
                 shortcut: "u".to_string(),
+                expand_env: true,
+                shell: false,
+                category: None,
+                description: None,
+                submenu: None,
+                icon_cell_width: None,
+                max_retries: 0,
             },
         );
 
@@ -408,6 +1276,13 @@ impl Default for Config {
                 confirm: false,
                 favorite: false,
                 shortcut: "l".to_string(),
+                expand_env: true,
+                shell: false,
+                category: None,
+                description: None,
+                submenu: None,
+                icon_cell_width: None,
+                max_retries: 0,
             },
         );
 
@@ -423,6 +1298,13 @@ impl Default for Config {
                 confirm: true,
                 favorite: false,
                 shortcut: "o".to_string(),
+                expand_env: true,
+                shell: false,
+                category: None,
+                description: None,
+                submenu: None,
+                icon_cell_width: None,
+                max_retries: 0,
             },
         );
 
@@ -438,12 +1320,19 @@ impl Default for Config {
                 confirm: false,
                 favorite: false,
                 shortcut: "c".to_string(),
+                expand_env: true,
+                shell: false,
+                category: None,
+                description: None,
+                submenu: None,
+                icon_cell_width: None,
+                max_retries: 0,
             },
         );
 
-        let key_up = vec!["Up".to_string(), "k".to_string()];
+        let key_up = vec!["Up".to_string(), "k".to_string(), "Shift-Tab".to_string()];
 
-        let key_down = vec!["Down".to_string(), "j".to_string()];
+        let key_down = vec!["Down".to_string(), "j".to_string(), "Tab".to_string()];
 
         let key_select = vec!["Enter".to_string()];
 
@@ -455,6 +1344,7 @@ impl Default for Config {
             border: BorderConfig {
                 enabled: true,
                 style: "rounded".to_string(),
+                border_chars: None,
             },
             colors: ColorConfig {
                 foreground: "white".to_string(),
@@ -467,18 +1357,25 @@ impl Default for Config {
                 help_fg: "gray".to_string(),
                 help_key_fg: "cyan".to_string(),
                 help_key_modifier: vec!["bold".to_string()],
+                background_opacity: default_background_opacity(),
             },
             keys: KeyConfig {
                 up: key_up,
                 down: key_down,
                 select: key_select,
                 quit: key_quit,
+                confirm_yes_keys: default_confirm_yes_keys(),
+                confirm_no_keys: default_confirm_no_keys(),
+                help: default_help_keys(),
+                animation_menu: default_animation_menu_keys(),
+                theme_menu: default_theme_menu_keys(),
             },
             actions,
             help_text: HelpConfig {
                 enabled: true,
                 template: "{keys} {action} | ".to_string(),
                 separator: " | ".to_string(),
+                show_help_overlay_hint: true,
             },
             layout: LayoutConfig {
                 auto_scale: true,
@@ -488,6 +1385,12 @@ impl Default for Config {
                 min_height: 10,
                 max_width: 60,
                 padding: 1,
+                max_visible_items: None,
+                position: default_layout_position(),
+                fullscreen: false,
+                fullscreen_bg_char: default_fullscreen_bg_char(),
+                grid_columns: None,
+                show_scroll_indicator: true,
             },
             animation: AnimationConfig {
                 enabled: true,
@@ -497,20 +1400,91 @@ impl Default for Config {
                 density: 50,
                 adaptive_quality: true,
                 min_speed_ms: 200,
+                random_exclude: Vec::new(),
+                cycle_animations: false,
+                cycle_interval_secs: default_cycle_interval_secs(),
+                animation_blend: None,
+                blend_opacity: default_blend_opacity(),
+                morse_text: None,
+                morse_speed: default_morse_speed(),
+                morse_loop: true,
+                gol_pattern: default_gol_pattern(),
+                gol_density: default_gol_density(),
+                typewriter_text: default_typewriter_text(),
+                typewriter_pause_ticks: default_typewriter_pause_ticks(),
+                typewriter_x: default_typewriter_x(),
+                typewriter_y: default_typewriter_y(),
+                clock_format: default_clock_format(),
+                clock_drift_secs: default_clock_drift_secs(),
+                waveform_bars: default_waveform_bars(),
+                waveform_peak_hold: true,
+                dvd_text: None,
+                dvd_trail: false,
+                matrix_direction: default_matrix_direction(),
+                plasma_pulse_speed: default_plasma_pulse_speed(),
+                thermal_alpha: default_thermal_alpha(),
+                starfield_speed: default_starfield_speed(),
+                ball_count: default_ball_count(),
+                ball_gravity: 0.0,
+                snake_collision: true,
+                pong_ball_trail: default_pong_ball_trail(),
+                firework_style: default_firework_style(),
+                lissajous_trail: default_lissajous_trail(),
+                constellation_connect_distance: default_constellation_connect_distance(),
+                electron_count: default_electron_count(),
+                aurora_curtain_count: default_aurora_curtain_count(),
+                aurora_glow: true,
+                ca_rule: default_ca_rule(),
+                ca_wrap: true,
+                plasma_globe_arcs: default_plasma_globe_arcs(),
+                plasma_globe_radius: None,
+                seed: None,
             },
             responsive: ResponsiveConfig::default(),
             layout_mode: "vertical".to_string(),
             wm_type: "auto".to_string(),
+            pre_logout_kill: default_pre_logout_kill(),
             grace_period: GracePeriodConfig {
                 enabled: true,
                 duration_secs: 5,
                 show_countdown: true,
                 message_template: "⏱️  {action} in {seconds}s... Press any key to cancel"
                     .to_string(),
+                progress_style: default_progress_style(),
+                pre_notify: false,
+                pre_notify_urgency: default_pre_notify_urgency(),
+                notify_command: default_notify_command(),
+                notify_args: Vec::new(),
+                acquire_inhibitor: false,
             },
             theme: None,
             use_emoji_icons: None,
             performance: PerformanceSettings::default(),
+            auto_trigger: AutoTriggerConfig::default(),
+            accessibility: None,
+            confirm_default_yes: false,
+            confirm_timeout_secs: None,
+            auto_quit_secs: None,
+            confirm_yes_label: default_confirm_yes_label(),
+            confirm_no_label: default_confirm_no_label(),
+            show_host_in_confirm: true,
+            show_open_windows: false,
+            warn_open_windows_threshold: default_warn_open_windows_threshold(),
+            global_shortcuts: true,
+            title_update_interval_secs: default_title_update_interval_secs(),
+            mouse_scroll_speed: default_mouse_scroll_speed(),
+            icon_position: default_icon_position(),
+            show_shortcut: true,
+            icon_padding: default_icon_padding(),
+            force_icon_width: None,
+            align_columns: false,
+            show_categories: false,
+            command_timeout_secs: default_command_timeout_secs(),
+            sort_mode: default_sort_mode(),
+            remember_last: true,
+            animation_behind_menu: true,
+            desktop_actions_dirs: default_desktop_actions_dirs(),
+            debug_log: None,
         }
     }
 }
@@ -520,6 +1494,20 @@ impl Default for Config {
 // ============================================================================
 
 fn parse_color(color_str: &str) -> Color {
+    // Palette indices are sent as-is unless the terminal can show the exact RGB
+    if let Some(index) = parse_color_index(color_str) {
+        if !term_capabilities().truecolor {
+            return Color::Indexed(index);
+        }
+    }
+    match parse_color_rgb(color_str) {
+        Color::Rgb(r, g, b) => downsample_rgb(r, g, b, term_capabilities()),
+        color => color,
+    }
+}
+
+/// Parse a color without regard to what the terminal can display
+fn parse_color_rgb(color_str: &str) -> Color {
     // Fast path for hex colors — no allocation needed
     if color_str.starts_with('#') && color_str.len() == 7 {
         if let (Ok(r), Ok(g), Ok(b)) = (
@@ -531,6 +1519,21 @@ fn parse_color(color_str: &str) -> Color {
         }
         return Color::White;
     }
+    // Shorthand hex: each digit is doubled, so "#f80" is "#ff8800"
+    if color_str.starts_with('#') && color_str.len() == 4 && color_str.is_ascii() {
+        let digit = |i: usize| u8::from_str_radix(&color_str[i..i + 1], 16).map(|v| v * 17);
+        if let (Ok(r), Ok(g), Ok(b)) = (digit(1), digit(2), digit(3)) {
+            return Color::Rgb(r, g, b);
+        }
+        return Color::White;
+    }
+    if let Some(color) = parse_css_color(color_str) {
+        return color;
+    }
+    if let Some(index) = parse_color_index(color_str) {
+        let (r, g, b) = xterm_color_to_rgb(index);
+        return Color::Rgb(r, g, b);
+    }
     match color_str.to_ascii_lowercase().as_str() {
         "black" => Color::Black,
         "red" => Color::Red,
@@ -548,10 +1551,205 @@ fn parse_color(color_str: &str) -> Color {
         "lightmagenta" => Color::LightMagenta,
         "lightcyan" => Color::LightCyan,
         "white" => Color::White,
+        "transparent" => Color::Reset,
         _ => Color::White,
     }
 }
 
+/// Parse an xterm palette index written as "color_N", e.g. "color_196"
+fn parse_color_index(color_str: &str) -> Option<u8> {
+    color_str
+        .trim()
+        .to_ascii_lowercase()
+        .strip_prefix("color_")?
+        .parse()
+        .ok()
+}
+
+/// The 16 system colors, using the xterm defaults
+const XTERM_SYSTEM_COLORS: [(Color, (u8, u8, u8)); 16] = [
+    (Color::Black, (0, 0, 0)),
+    (Color::Red, (205, 0, 0)),
+    (Color::Green, (0, 205, 0)),
+    (Color::Yellow, (205, 205, 0)),
+    (Color::Blue, (0, 0, 238)),
+    (Color::Magenta, (205, 0, 205)),
+    (Color::Cyan, (0, 205, 205)),
+    (Color::Gray, (229, 229, 229)),
+    (Color::DarkGray, (127, 127, 127)),
+    (Color::LightRed, (255, 0, 0)),
+    (Color::LightGreen, (0, 255, 0)),
+    (Color::LightYellow, (255, 255, 0)),
+    (Color::LightBlue, (92, 92, 255)),
+    (Color::LightMagenta, (255, 0, 255)),
+    (Color::LightCyan, (0, 255, 255)),
+    (Color::White, (255, 255, 255)),
+];
+
+/// Channel values of the xterm 6x6x6 color cube (indices 16-231)
+const XTERM_CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+/// RGB value of an entry in the standard xterm 256-color palette
+fn xterm_color_to_rgb(index: u8) -> (u8, u8, u8) {
+    match index {
+        0..=15 => XTERM_SYSTEM_COLORS[index as usize].1,
+        16..=231 => {
+            let cube = index - 16;
+            (
+                XTERM_CUBE_LEVELS[(cube / 36) as usize],
+                XTERM_CUBE_LEVELS[(cube / 6 % 6) as usize],
+                XTERM_CUBE_LEVELS[(cube % 6) as usize],
+            )
+        }
+        // Grayscale ramp 232-255 runs from 8 to 238 in steps of 10
+        232..=255 => {
+            let gray = 8 + (index - 232) * 10;
+            (gray, gray, gray)
+        }
+    }
+}
+
+/// Map an RGB color onto the 256-color palette or the 16 named colors
+/// when the terminal lacks 24-bit support
+fn downsample_rgb(r: u8, g: u8, b: u8, caps: TermCapabilities) -> Color {
+    if caps.truecolor {
+        return Color::Rgb(r, g, b);
+    }
+    if caps.colors_256 {
+        return Color::Indexed(rgb_to_ansi256(r, g, b));
+    }
+    nearest_named_color(r, g, b)
+}
+
+/// Nearest entry of the xterm 6x6x6 color cube or grayscale ramp
+fn rgb_to_ansi256(r: u8, g: u8, b: u8) -> u8 {
+    let level = |v: u8| {
+        (0..6)
+            .min_by_key(|&i| (XTERM_CUBE_LEVELS[i] as i32 - v as i32).abs())
+            .unwrap_or(0)
+    };
+    let (ri, gi, bi) = (level(r), level(g), level(b));
+    let cube = (
+        XTERM_CUBE_LEVELS[ri],
+        XTERM_CUBE_LEVELS[gi],
+        XTERM_CUBE_LEVELS[bi],
+    );
+
+    // Grayscale ramp 232-255 runs from 8 to 238 in steps of 10
+    let avg = (r as u32 + g as u32 + b as u32) / 3;
+    let gray_index = ((avg.saturating_sub(3)) / 10).min(23) as u8;
+    let gray = 8 + gray_index * 10;
+
+    if color_distance((r, g, b), (gray, gray, gray)) < color_distance((r, g, b), cube) {
+        232 + gray_index
+    } else {
+        16 + 36 * ri as u8 + 6 * gi as u8 + bi as u8
+    }
+}
+
+/// Closest of the 16 named terminal colors, using the xterm defaults
+fn nearest_named_color(r: u8, g: u8, b: u8) -> Color {
+    XTERM_SYSTEM_COLORS
+        .iter()
+        .min_by_key(|(_, rgb)| color_distance((r, g, b), *rgb))
+        .map(|(color, _)| *color)
+        .unwrap_or(Color::White)
+}
+
+fn color_distance(a: (u8, u8, u8), b: (u8, u8, u8)) -> u32 {
+    let d = |x: u8, y: u8| (x as i32 - y as i32).pow(2) as u32;
+    d(a.0, b.0) + d(a.1, b.1) + d(a.2, b.2)
+}
+
+/// Parse CSS-style `rgb(r, g, b)` and `hsl(h, s%, l%)` colors
+fn parse_css_color(color_str: &str) -> Option<Color> {
+    let lower = color_str.trim().to_ascii_lowercase();
+    let (func, args) = lower.split_once('(')?;
+    let args = args.strip_suffix(')')?;
+    let parts: Vec<&str> = args.split(',').map(str::trim).collect();
+    if parts.len() != 3 {
+        return None;
+    }
+    match func.trim() {
+        "rgb" => {
+            let r = parts[0].parse::<u8>().ok()?;
+            let g = parts[1].parse::<u8>().ok()?;
+            let b = parts[2].parse::<u8>().ok()?;
+            Some(Color::Rgb(r, g, b))
+        }
+        "hsl" => {
+            let h = parts[0].trim_end_matches("deg").parse::<f32>().ok()?;
+            let s = parts[1].trim_end_matches('%').parse::<f32>().ok()?;
+            let l = parts[2].trim_end_matches('%').parse::<f32>().ok()?;
+            if !(h.is_finite() && (0.0..=100.0).contains(&s) && (0.0..=100.0).contains(&l)) {
+                return None;
+            }
+            let (r, g, b) = hsl_to_rgb(h, s / 100.0, l / 100.0);
+            Some(Color::Rgb(r, g, b))
+        }
+        _ => None,
+    }
+}
+
+/// Convert HSL (hue in degrees, saturation and lightness in 0.0-1.0) to RGB
+fn hsl_to_rgb(h: f32, s: f32, l: f32) -> (u8, u8, u8) {
+    let h = h.rem_euclid(360.0);
+    let c = (1.0 - (2.0 * l - 1.0).abs()) * s;
+    let x = c * (1.0 - ((h / 60.0) % 2.0 - 1.0).abs());
+    let m = l - c / 2.0;
+    let (r, g, b) = match (h / 60.0) as u32 {
+        0 => (c, x, 0.0),
+        1 => (x, c, 0.0),
+        2 => (0.0, c, x),
+        3 => (0.0, x, c),
+        4 => (x, 0.0, c),
+        _ => (c, 0.0, x),
+    };
+    let to_u8 = |v: f32| ((v + m) * 255.0).round().clamp(0.0, 255.0) as u8;
+    (to_u8(r), to_u8(g), to_u8(b))
+}
+
+/// Border glyphs for `border.style`, including the user-defined "custom" set
+fn border_set(border: &BorderConfig) -> symbols::border::Set {
+    match border.style.as_str() {
+        "rounded" => symbols::border::ROUNDED,
+        "double" => symbols::border::DOUBLE,
+        "thick" => symbols::border::THICK,
+        "custom" => match &border.border_chars {
+            Some(chars) => symbols::border::Set {
+                top_left: intern_glyph(&chars.top_left),
+                top_right: intern_glyph(&chars.top_right),
+                bottom_left: intern_glyph(&chars.bottom_left),
+                bottom_right: intern_glyph(&chars.bottom_right),
+                vertical_left: intern_glyph(&chars.vertical),
+                vertical_right: intern_glyph(&chars.vertical),
+                horizontal_top: intern_glyph(&chars.horizontal),
+                horizontal_bottom: intern_glyph(&chars.horizontal),
+            },
+            None => symbols::border::PLAIN,
+        },
+        _ => symbols::border::PLAIN,
+    }
+}
+
+/// Give a border glyph a 'static lifetime, leaking each distinct glyph only once
+fn intern_glyph(glyph: &str) -> &'static str {
+    static GLYPHS: OnceLock<Mutex<HashSet<&'static str>>> = OnceLock::new();
+
+    // Border cells are one character wide
+    let glyph: String = glyph.chars().next().unwrap_or(' ').to_string();
+    let mut glyphs = GLYPHS
+        .get_or_init(Default::default)
+        .lock()
+        .unwrap_or_else(|e| e.into_inner());
+    if let Some(&interned) = glyphs.get(glyph.as_str()) {
+        return interned;
+    }
+    let interned: &'static str = Box::leak(glyph.into_boxed_str());
+    glyphs.insert(interned);
+    interned
+}
+
 fn parse_modifier(modifiers: &[String]) -> Modifier {
     let mut result = Modifier::empty();
     for modifier in modifiers {
@@ -570,6 +1768,107 @@ fn parse_modifier(modifiers: &[String]) -> Modifier {
     result
 }
 
+/// Expand `{hostname}`, `{user}`, `{time}` and `{date}` in the title template
+fn expand_title(template: &str) -> String {
+    if !template.contains('{') {
+        return template.to_string();
+    }
+
+    let now = chrono::Local::now();
+    template
+        .replace("{hostname}", &current_hostname())
+        .replace("{user}", &current_user())
+        .replace("{time}", &now.format("%H:%M").to_string())
+        .replace("{date}", &now.format("%Y-%m-%d").to_string())
+}
+
+fn current_user() -> String {
+    std::env::var("USER").unwrap_or_else(|_| "unknown".to_string())
+}
+
+/// Count the distinct programs the current user is running, as an estimate
+/// of the applications a logout would close; kernel threads are skipped
+fn count_open_windows() -> usize {
+    use std::os::unix::fs::MetadataExt;
+
+    let own_uid = fs::metadata("/proc/self").map(|m| m.uid()).ok();
+    let own_pid = std::process::id();
+    let Ok(entries) = fs::read_dir("/proc") else {
+        return 0;
+    };
+
+    let mut programs = HashSet::new();
+    for entry in entries.flatten() {
+        let is_other_process = entry
+            .file_name()
+            .to_str()
+            .and_then(|name| name.parse::<u32>().ok())
+            .is_some_and(|pid| pid != own_pid);
+        if !is_other_process {
+            continue;
+        }
+        let path = entry.path();
+        if fs::metadata(&path).map(|m| m.uid()).ok() != own_uid {
+            continue;
+        }
+        // Kernel threads have an empty command line
+        if fs::read(path.join("cmdline")).map_or(true, |cmdline| cmdline.is_empty()) {
+            continue;
+        }
+        if let Ok(comm) = fs::read_to_string(path.join("comm")) {
+            programs.insert(comm.trim().to_string());
+        }
+    }
+    programs.len()
+}
+
+/// IDs of the current user's processes called `name`
+fn process_ids(name: &str) -> Vec<u32> {
+    use std::os::unix::fs::MetadataExt;
+
+    // The kernel cuts process names to 15 bytes
+    let name = name.get(..15).unwrap_or(name);
+    let own_uid = fs::metadata("/proc/self").map(|m| m.uid()).ok();
+    let Ok(entries) = fs::read_dir("/proc") else {
+        return Vec::new();
+    };
+
+    entries
+        .flatten()
+        .filter_map(|entry| {
+            let pid = entry.file_name().to_str()?.parse::<u32>().ok()?;
+            let path = entry.path();
+            if fs::metadata(&path).map(|m| m.uid()).ok() != own_uid {
+                return None;
+            }
+            let comm = fs::read_to_string(path.join("comm")).ok()?;
+            (comm.trim() == name).then_some(pid)
+        })
+        .collect()
+}
+
+/// Send SIGTERM to every process of the current user with one of `names`
+fn stop_processes(names: &[String]) {
+    let pids: Vec<String> = names
+        .iter()
+        .flat_map(|name| process_ids(name))
+        .map(|pid| pid.to_string())
+        .collect();
+    if pids.is_empty() {
+        return;
+    }
+    let _ = Command::new("kill")
+        .arg("-TERM")
+        .args(&pids)
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status();
+}
+
+fn current_hostname() -> String {
+    sysinfo::System::host_name().unwrap_or_else(|| "unknown".to_string())
+}
+
 fn parse_title_alignment(s: &str) -> Alignment {
     match s {
         "left" => Alignment::Left,
@@ -605,12 +1904,84 @@ fn load_last_executed() -> Option<String> {
     None
 }
 
-fn save_last_executed(label: &str) {
+/// Index of the last executed action; older versions stored the label
+/// instead of the id, so both are tried
+fn last_executed_index(actions: &[Action]) -> Option<usize> {
+    let stored = load_last_executed()?;
+    actions
+        .iter()
+        .position(|a| a.id == stored)
+        .or_else(|| actions.iter().position(|a| a.label == stored))
+}
+
+fn save_last_executed(id: &str) {
     if let Some(path) = get_last_executed_path() {
         if let Some(parent) = path.parent() {
             let _ = fs::create_dir_all(parent);
         }
-        let _ = fs::write(&path, label);
+        let _ = fs::write(&path, id);
+    }
+}
+
+fn get_stats_path() -> Option<PathBuf> {
+    ProjectDirs::from("", "", "rexit").map(|dirs| dirs.config_dir().join("stats.json"))
+}
+
+/// How often and when each action was run, keyed by label
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct ActionStats {
+    #[serde(default)]
+    counts: HashMap<String, u32>,
+    /// Unix timestamp of the last run
+    #[serde(default)]
+    last_used: HashMap<String, i64>,
+}
+
+/// Read the stats file; a missing or corrupt file counts as empty
+fn load_stats() -> ActionStats {
+    get_stats_path()
+        .and_then(|path| fs::read_to_string(path).ok())
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn record_execution(label: &str) {
+    let Some(path) = get_stats_path() else {
+        return;
+    };
+    let mut stats = load_stats();
+    *stats.counts.entry(label.to_string()).or_insert(0) += 1;
+    stats
+        .last_used
+        .insert(label.to_string(), chrono::Local::now().timestamp());
+
+    if let Some(parent) = path.parent() {
+        let _ = fs::create_dir_all(parent);
+    }
+    if let Ok(json) = serde_json::to_string_pretty(&stats) {
+        let _ = fs::write(&path, json);
+    }
+}
+
+/// Order the menu according to `sort_mode`; unknown modes behave like "default"
+fn sort_actions(actions: &mut [Action], sort_mode: &str, stats: &ActionStats) {
+    let by_label = |a: &Action, b: &Action| a.label.to_lowercase().cmp(&b.label.to_lowercase());
+    match sort_mode {
+        "alphabetical" => actions.sort_by(by_label),
+        "frequency" => actions.sort_by(|a, b| {
+            let count = |action: &Action| stats.counts.get(&action.label).copied().unwrap_or(0);
+            count(b).cmp(&count(a)).then_with(|| by_label(a, b))
+        }),
+        "recent" => actions.sort_by(|a, b| {
+            let used = |action: &Action| stats.last_used.get(&action.label).copied();
+            used(b).cmp(&used(a)).then_with(|| by_label(a, b))
+        }),
+        // Favorites first, then by label
+        _ => actions.sort_by(|a, b| match (b.favorite, a.favorite) {
+            (true, false) => std::cmp::Ordering::Greater,
+            (false, true) => std::cmp::Ordering::Less,
+            _ => a.label.cmp(&b.label),
+        }),
     }
 }
 
@@ -618,12 +1989,17 @@ fn load_config() -> Config {
     if let Some(config_path) = get_config_path() {
         if config_path.exists() {
             match fs::read_to_string(&config_path) {
-                Ok(content) => match toml::from_str::<Config>(&content) {
-                    Ok(config) => {
+                Ok(content) => match parse_config(&content) {
+                    Ok(mut config) => {
+                        apply_high_contrast_animation(&mut config, &content);
+                        validate_config(&mut config);
                         return config;
                     }
                     Err(e) => {
                         eprintln!("Warning: Failed to parse config file: {}", e);
+                        for issue in config_issues(&content) {
+                            eprintln!("  {}", issue);
+                        }
                         eprintln!("Using default configuration.");
                     }
                 },
@@ -637,6 +2013,253 @@ fn load_config() -> Config {
     Config::default()
 }
 
+/// Parse a config file; keys and sections it leaves out keep their defaults
+fn parse_config(content: &str) -> Result<Config, toml::de::Error> {
+    let partial = toml::from_str::<toml::Value>(content)?;
+    merge_partial_config(Config::default(), partial)
+}
+
+/// Lay the keys present in `partial` over `base`, leaving everything else as is
+fn merge_partial_config(base: Config, partial: toml::Value) -> Result<Config, toml::de::Error> {
+    let Ok(mut merged) = toml::Value::try_from(base) else {
+        return partial.try_into();
+    };
+    merge_config_value(&mut merged, partial);
+    merged.try_into()
+}
+
+/// Merge a user config over the defaults; `[actions]` lists the whole menu,
+/// so default actions it leaves out are dropped rather than merged back in
+fn merge_config_value(base: &mut toml::Value, overlay: toml::Value) {
+    let user_actions = overlay.get("actions").and_then(toml::Value::as_table);
+    let base_actions = base.get_mut("actions").and_then(toml::Value::as_table_mut);
+    if let (Some(user_actions), Some(base_actions)) = (user_actions, base_actions) {
+        base_actions.retain(|id, _| user_actions.contains_key(id));
+    }
+    merge_toml_value(base, overlay);
+}
+
+/// Merge tables key by key; any other value in `overlay`, arrays included,
+/// replaces the one in `base` whole
+fn merge_toml_value(base: &mut toml::Value, overlay: toml::Value) {
+    match (base, overlay) {
+        (toml::Value::Table(base), toml::Value::Table(overlay)) => {
+            for (key, value) in overlay {
+                match base.get_mut(&key) {
+                    Some(existing) => merge_toml_value(existing, value),
+                    None => {
+                        base.insert(key, value);
+                    }
+                }
+            }
+        }
+        (base, overlay) => *base = overlay,
+    }
+}
+
+/// Explain a config that failed to deserialize, if it is at least valid TOML
+fn config_issues(content: &str) -> Vec<String> {
+    let Ok(mut value) = toml::from_str::<toml::Value>(content) else {
+        return Vec::new();
+    };
+    // Left-out keys are filled in from the defaults, so only report what is left
+    if let Ok(mut defaults) = toml::Value::try_from(Config::default()) {
+        merge_config_value(&mut defaults, value);
+        value = defaults;
+    }
+    validate_config_value(&value)
+}
+
+/// Compare a parsed config with the default one and list missing or mistyped keys
+///
+/// Every entry of `[actions]` is checked against the first default action. Keys
+/// the defaults don't contain (unset options, unknown keys) are not checked.
+fn validate_config_value(value: &toml::Value) -> Vec<String> {
+    let Ok(defaults) = toml::Value::try_from(Config::default()) else {
+        return Vec::new();
+    };
+    let mut issues = Vec::new();
+    validate_config_table(value, &defaults, &defaults, &mut Vec::new(), &mut issues);
+    issues
+}
+
+fn validate_config_table(
+    value: &toml::Value,
+    expected: &toml::Value,
+    defaults: &toml::Value,
+    path: &mut Vec<String>,
+    issues: &mut Vec<String>,
+) {
+    let (Some(table), Some(expected_table)) = (value.as_table(), expected.as_table()) else {
+        return;
+    };
+    let location = if path.is_empty() {
+        "Error at top level".to_string()
+    } else {
+        format!("Error in [{}]", path.join("."))
+    };
+
+    // Action ids are user-defined, so each action is checked against a default one
+    let action_template = (path.len() == 1 && path[0] == "actions")
+        .then(|| expected_table.values().next())
+        .flatten();
+
+    for key in expected_table.keys() {
+        if action_template.is_none()
+            && !table.contains_key(key)
+            && config_key_required(defaults, path, key)
+        {
+            issues.push(format!("{}: missing field '{}'", location, key));
+        }
+    }
+
+    for (key, item) in table {
+        let Some(expected_item) = action_template.or_else(|| expected_table.get(key)) else {
+            continue;
+        };
+
+        let compatible =
+            item.same_type(expected_item) || (item.is_integer() && expected_item.is_float());
+        if !compatible {
+            issues.push(format!(
+                "{}: field '{}' must be {}, got {}",
+                location,
+                key,
+                config_type_name(expected_item),
+                item.type_str()
+            ));
+            continue;
+        }
+
+        if item.is_table() {
+            path.push(key.clone());
+            validate_config_table(item, expected_item, defaults, path, issues);
+            path.pop();
+        }
+    }
+}
+
+/// Whether the config fails to load once `key` is removed from the defaults
+fn config_key_required(defaults: &toml::Value, path: &[String], key: &str) -> bool {
+    let mut config = defaults.clone();
+    let mut table = config.as_table_mut();
+    for (depth, segment) in path.iter().enumerate() {
+        // Keys below an action are looked up in the action used as template
+        let segment = match (depth, table.as_ref()) {
+            (1, Some(t)) if path[0] == "actions" => t.keys().next().cloned(),
+            _ => Some(segment.clone()),
+        };
+        table = segment
+            .and_then(|segment| table?.get_mut(&segment))
+            .and_then(toml::Value::as_table_mut);
+    }
+    match table {
+        Some(table) => {
+            table.remove(key);
+            config.try_into::<Config>().is_err()
+        }
+        None => false,
+    }
+}
+
+/// Type name with an article, as used in validation messages
+fn config_type_name(value: &toml::Value) -> String {
+    let name = value.type_str();
+    match name.chars().next() {
+        Some('a' | 'e' | 'i' | 'o' | 'u') => format!("an {}", name),
+        _ => format!("a {}", name),
+    }
+}
+
+/// Read a FreeDesktop .desktop file marked for rexit into an action
+///
+/// The file needs `Categories=X-Rexit` or `X-Rexit-Action=true` in its
+/// `[Desktop Entry]` group; the id is the file name without `.desktop`.
+fn import_desktop_file(path: &Path) -> Option<(String, ActionConfig)> {
+    let content = fs::read_to_string(path).ok()?;
+    let id = path.file_stem()?.to_str()?.to_string();
+
+    let mut entries: HashMap<&str, &str> = HashMap::new();
+    let mut in_entry = false;
+    for line in content.lines().map(str::trim) {
+        if line.starts_with('[') {
+            in_entry = line == "[Desktop Entry]";
+            continue;
+        }
+        if !in_entry || line.starts_with('#') {
+            continue;
+        }
+        if let Some((key, value)) = line.split_once('=') {
+            entries.entry(key.trim()).or_insert(value.trim());
+        }
+    }
+
+    let marked = entries
+        .get("Categories")
+        .is_some_and(|c| c.split(';').any(|c| c == "X-Rexit"))
+        || entries.get("X-Rexit-Action") == Some(&"true");
+    if !marked || entries.get("Hidden") == Some(&"true") {
+        return None;
+    }
+
+    let label = entries.get("Name")?.to_string();
+    // %f, %U and friends are placeholders for files and URLs, which rexit never passes
+    let command = entries
+        .get("Exec")?
+        .split_whitespace()
+        .filter(|word| !(word.len() == 2 && word.starts_with('%')))
+        .collect::<Vec<_>>()
+        .join(" ");
+    // Icon= usually names an icon theme entry; only glyphs can be drawn
+    let icon = entries
+        .get("Icon")
+        .filter(|icon| icon.chars().count() <= 2)
+        .map_or("•", |icon| icon)
+        .to_string();
+
+    Some((
+        id,
+        ActionConfig {
+            icon,
+            icon_fallback: None,
+            label,
+            command,
+            args: Vec::new(),
+            enabled: true,
+            confirm: false,
+            favorite: false,
+            shortcut: String::new(),
+            expand_env: false,
+            shell: true,
+            category: None,
+            description: entries.get("Comment").map(|c| c.to_string()),
+            submenu: None,
+            icon_cell_width: None,
+            max_retries: 0,
+        },
+    ))
+}
+
+/// Add actions from `desktop_actions_dirs`; actions defined in the config win
+fn merge_desktop_actions(config: &mut Config) {
+    for dir in &config.desktop_actions_dirs {
+        let Ok(entries) = fs::read_dir(expand_env_vars(dir)) else {
+            continue;
+        };
+        let mut paths: Vec<PathBuf> = entries
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter(|path| path.extension().is_some_and(|ext| ext == "desktop"))
+            .collect();
+        paths.sort();
+
+        for path in paths {
+            if let Some((id, action)) = import_desktop_file(&path) {
+                config.actions.entry(id).or_insert(action);
+            }
+        }
+    }
+}
+
 fn generate_default_config() -> String {
     String::from(
         r##"## rexit configuration file
@@ -644,16 +2267,38 @@ fn generate_default_config() -> String {
 ## All fields are optional - defaults will be used for missing values
 
 ## Window title
+## Variables: {hostname}, {user}, {time} (HH:MM), {date} (YYYY-MM-DD)
 title = " rexit "
 title_alignment = "center"  ## Options: "left", "center", "right"
+title_update_interval_secs = 60  ## How often title variables are refreshed
 
 ## Layout mode: "vertical", "horizontal", "grid", "compact"
 layout_mode = "vertical"
 
-## Window manager: "auto", "hyprland", "sway", "i3", "bspwm", "awesome"
+## Supported window managers, how "auto" detects them and how they log out:
+##   hyprland  HYPRLAND_INSTANCE_SIGNATURE      hyprctl dispatch exit
+##   sway      SWAYSOCK                         swaymsg exit
+##   niri      NIRI_SOCKET                      niri msg action quit --skip-confirmation
+##   river     RIVER_SOCK, RIVERCTLFD           riverctl exit
+##   labwc     LABWC_PID, a running labwc       labwc --exit
+##   i3        XDG_SESSION_DESKTOP              i3-msg exit
+##   bspwm     XDG_SESSION_DESKTOP              bspc quit
+##   awesome   XDG_SESSION_DESKTOP              awesome-client "awesome.quit()"
+##   kwin      KDE_FULL_SESSION                 qdbus org.kde.ksmserver /KSMServer logout 0 0 0
+##   openbox   OPENBOX_CONFIG_PATH              openbox --exit
+##   mutter    GNOME_SETUP_DISPLAY (GNOME)      gnome-session-quit --logout --no-prompt
+##   fluxbox   FLUXBOX_PID_PATH                 fluxbox-remote Exit
+##   fvwm      FVWM_USERDIR                     FvwmCommand Quit
+
+## Window manager: "auto" or one of the names above
 ## When set to "auto", rexit will detect your WM automatically
 wm_type = "auto"
 
+## Processes to stop with SIGTERM before logging out, e.g. display
+## helpers like kanshi or a wlr-randr loop started by the session;
+## set to [] to leave them running
+pre_logout_kill = ["kanshi", "wlr-randr"]
+
 ## Theme file (optional)
 ## Load a theme from ~/.config/rexit/themes/<name>.toml
 ## theme = "dracula"
@@ -662,16 +2307,78 @@ wm_type = "auto"
 ## Set to true if your terminal doesn't support Nerd Fonts
 ## use_emoji_icons = false
 
+## Close the menu after this many seconds without a key press (like --timeout)
+## auto_quit_secs = 30
+
+## Confirmation dialog
+confirm_default_yes = false   ## Focus "Yes" instead of "No" when the dialog opens
+## confirm_timeout_secs = 30    ## Cancel the dialog when left unanswered this long
+confirm_yes_label = "[Y] Yes"
+confirm_no_label = "[N] No"
+show_host_in_confirm = true   ## Show "on user@hostname" below the question
+show_open_windows = false     ## Show "N applications are open." for logout/shutdown
+warn_open_windows_threshold = 5  ## Highlight the count in yellow above this
+
+## Allow action shortcuts while the animation menu or a dialog is open
+global_shortcuts = true
+
+## Entries moved per mouse wheel step in menus (1-5)
+mouse_scroll_speed = 1
+
+## Action icons
+icon_position = "left"  ## Options: "left", "right", "none"
+icon_padding = 1        ## Spaces between icon and label
+## force_icon_width = 2  ## Cells every icon takes up (1 or 2), for terminals that
+##                       ## draw emoji narrower or wider than expected
+show_shortcut = true    ## Show "[key]" next to each label
+align_columns = false   ## Line up icons, shortcuts and labels like a table
+
+## Group the vertical list by each action's `category` ("Other" when unset)
+show_categories = false
+
+## Stop an action that is still running after this many seconds (0 = no limit).
+## Lock actions are never stopped, since that would unlock the session.
+command_timeout_secs = 30
+
+## Menu order: "default" (favorites first, then by label), "alphabetical",
+## "frequency" (most used first), "recent" (most recently used first)
+## Usage is counted in stats.json next to this file
+sort_mode = "default"
+
+## Start on the last executed action instead of the first one
+remember_last = true
+
+## Clear the animation inside the menu box (false draws it over the menu too)
+animation_behind_menu = true
+
+## Directories with .desktop files to add as actions. Only files with
+## "Categories=X-Rexit" or "X-Rexit-Action=true" are used; the file name
+## (without .desktop) becomes the action id.
+desktop_actions_dirs = ["${XDG_DATA_HOME:-$HOME/.local/share}/applications"]
+
+## Append every state change (menu, dialog, countdown, ...) to this file, like --debug-log
+## debug_log = "/tmp/rexit.log"
+
 [border]
 enabled = true
-style = "rounded"  ## Options: "plain", "rounded", "double", "thick"
+style = "rounded"  ## Options: "plain", "rounded", "double", "thick", "custom"
+## Glyphs for style = "custom" (any omitted glyph falls back to the plain one)
+## [border.border_chars]
+## top_left = "╭"
+## top_right = "╮"
+## bottom_left = "╰"
+## bottom_right = "╯"
+## horizontal = "─"
+## vertical = "│"
 
 [colors]
 ## Available colors:
 ## Standard: black, red, green, yellow, blue, magenta, cyan, gray, white
 ## Light variants: lightred, lightgreen, lightyellow, lightblue, lightmagenta, lightcyan
 ## Dark variants: darkgray
-## Hex: "#RRGGBB" (e.g., "#ff0000" for red)
+## Hex: "#RRGGBB" or "#RGB" (e.g., "#ff0000" or "#f00" for red)
+## CSS: "rgb(255, 0, 0)" or "hsl(0, 100%, 50%)"
+## Without 24-bit color support (COLORTERM) RGB colors are approximated
 foreground = "white"
 background = "black"
 border = "cyan"
@@ -682,15 +2389,39 @@ icon_color = "white"
 help_fg = "gray"
 help_key_fg = "cyan"
 help_key_modifier = ["bold"]
+## Opacity of the animation background (0-255). Lower values fade it towards
+## black; 0 shows the terminal's own background, e.g. with a compositor.
+## Values between 0 and 255 need 24-bit color support.
+background_opacity = 255
 
 [keys]
 ## Key names: Use crossterm KeyCode names
 ## Examples: "q", "Esc", "Enter", "Up", "Down", "Left", "Right", "Tab", "Backspace"
 ## Modifiers can be added with format: "Ctrl-q", "Alt-q", "Shift-Up"
-up = ["Up", "k"]
-down = ["Down", "j"]
+up = ["Up", "k", "Shift-Tab"]
+down = ["Down", "j", "Tab"]
 select = ["Enter"]
 quit = ["Esc", "q"]
+confirm_yes_keys = ["y", "Y"]  ## Confirm in the confirmation dialog
+confirm_no_keys = ["n", "N"]   ## Cancel in the confirmation dialog
+## Menus; keep these distinct from the action shortcuts
+help = ["?", "F1"]             ## Show all keybindings
+animation_menu = ["a"]         ## Pick a background animation
+theme_menu = ["t"]             ## Pick a theme (live preview)
+
+## Actions: $VAR, ${VAR} and ${VAR:-default} in command and args are expanded
+## from the environment; set expand_env = false on an action to pass them as is.
+## Args may also use {user}, {hostname}, {session} ($XDG_SESSION_ID or loginctl),
+## {display} ($DISPLAY or $WAYLAND_DISPLAY) and {seat} ($XDG_SEAT), e.g.
+## command = "loginctl"
+## args = ["terminate-session", "{session}"]  ## Logout that works with any WM
+## Set shell = true to run the command through `sh -c` (pipes, &&, redirects);
+## $VAR is then left for the shell to expand, so `$1` and loop variables work
+## Set max_retries = 2 to run a failing command again before showing the error
+## Set category = "Power" (or any name) to group actions when show_categories = true
+## Set submenu = ["reboot", "reboot_windows"] to open a menu of those actions
+## instead of running this one; actions listed in another action's submenu
+## are only shown there
 
 [actions.shutdown]
 icon = "⏻"  # Power symbol (was: \u{f011})
@@ -762,6 +2493,7 @@ shortcut = "c"
 enabled = true
 template = "{keys} {action} | "
 separator = " | "
+show_help_overlay_hint = true  ## Show "? for help" (F1 or ? lists all keybindings)
 
 [layout]
 ## Auto-scale menu to fit content (default: true)
@@ -776,6 +2508,22 @@ min_height = 10
 max_width = 60
 ## Padding inside the menu box (default: 1)
 padding = 1
+## Maximum number of actions visible at once, the list scrolls beyond that (default: unlimited)
+## max_visible_items = 8
+## Vertical placement: "top", "center", "bottom", "custom" (default: "center")
+## "top" and "bottom" keep vertical_margin rows to that edge, "custom" uses
+## vertical_margin as a percentage (0 = top, 100 = bottom).
+## Handy when launching from a keybind while working near the top of the screen;
+## set vertical_margin to a few rows (e.g. 2) and bind it in Hyprland with:
+##   bind = $mod, Escape, exec, kitty --class rexit -e rexit --position top
+position = "center"
+## Stretch the vertical menu over the whole terminal, spacing items evenly
+fullscreen = false
+fullscreen_bg_char = " "  ## Fills the space around items; " " shows the animation
+## Columns of the grid layout (default: 1-4 depending on the number of actions)
+## grid_columns = 3
+## Show "● ○ ○" page dots below the vertical menu when the list scrolls
+show_scroll_indicator = true
 
 [responsive]
 ## Responsive layout settings
@@ -784,12 +2532,16 @@ compact_threshold = 80            ## Switch to compact below this width
 minimal_threshold = 40            ## Switch to minimal below this width
 auto_adjust_spacing = true        ## Adjust spacing automatically
 hide_border_when_small = true     ## Hide border when terminal is small
-min_terminal_width = 20           ## Minimum terminal width required
-min_terminal_height = 5           ## Minimum terminal height required
+min_terminal_width = 20           ## Below this size only a "Terminal too small" hint is shown
+min_terminal_height = 5
+warn_terminal_width = 40          ## Below this size the help bar warns about cut elements
+warn_terminal_height = 25
+min_actions_for_compact = 4       ## Fewer actions show the vertical layout instead of compact
 
 [animation]
 ## Background animation settings
-## Animation types: "matrix", "matrix_cjk", "rain", "thunder", "snow", "stars", "fireflies", "fireworks", "neon_grid", "perlin_flow", "cube_3d", "fractals", "bubbles", "confetti", "wave", "particles", "digital_rain", "heartbeat", "plasma", "scanlines", "aurora", "autumn", "dna", "synthwave", "smoke", "gradient_flow", "constellation", "fish_tank", "typing_code", "vortex", "circuit", "flow_field", "morse", "lissajous", "game_of_life", "ocean", "ripple", "fog", "flames", "sparks", "lava_lamp", "sun", "galaxy", "meteor_shower", "satellite", "pulsar", "pong", "snake", "tetris", "invaders", "fibonacci", "mandelbrot", "hex_grid", "rose", "butterflies", "spider_web", "vine_growth", "moss", "radar", "binary_clock", "signal", "wifi", "paint_splatter", "ink_bleed", "mosaic", "stained_glass", "hologram", "glitch", "old_film", "thermal", "none"
+## Animation types: "matrix", "matrix_cjk", "matrix_vertical", "matrix_diagonal", "rain", "rain_window", "thunder", "snow", "stars", "starfield_3d", "fireflies", "fireworks", "neon_grid", "perlin_flow", "cube_3d", "fractals", "bubbles", "confetti", "wave", "particles", "digital_rain", "heartbeat", "plasma", "plasma_pulse", "plasma_globe", "scanlines", "aurora", "aurora_borealis", "autumn", "dna", "synthwave", "smoke", "gradient_flow", "constellation", "fish_tank", "typing_code", "vortex", "circuit", "flow_field", "morse", "lissajous", "game_of_life", "cellular_automata", "ocean", "ripple", "fog", "flames", "sparks", "lava_lamp", "sun", "galaxy", "meteor_shower", "satellite", "pulsar", "pong", "snake", "tetris", "invaders", "fibonacci", "mandelbrot", "hex_grid", "rose", "butterflies", "spider_web", "vine_growth", "moss", "radar", "binary_clock", "signal", "wifi", "paint_splatter", "ink_bleed", "mosaic", "stained_glass", "hologram", "glitch", "old_film", "thermal", "typewriter", "clock", "waveform", "northern_lights", "dvd_logo", "bouncing_balls", "none"
+## Use "random" to pick a different animation on every start
 enabled = true
 animation_type = "matrix"
 speed_ms = 80
@@ -797,6 +2549,66 @@ color = "green"
 density = 50
 adaptive_quality = true           ## Reduce quality under high CPU load
 min_speed_ms = 200                ## Minimum animation speed in degraded mode
+random_exclude = []               ## Never pick these randomly, e.g. ["mandelbrot", "game_of_life"]
+cycle_animations = false          ## Switch to a new random animation periodically
+cycle_interval_secs = 30
+## animation_blend = "particles"  ## Second animation drawn on top of the first
+blend_opacity = 128               ## Opacity of the blended animation (0-255)
+## "morse" animation
+## morse_text = "Hello world"     ## Message to spell (default: the GNU/Linux interjection)
+morse_speed = "normal"            ## Options: "slow", "normal", "fast"
+morse_loop = true                 ## Start over once the message is spelled
+## "game_of_life" animation
+gol_pattern = "random"            ## Options: "random", "glider", "r_pentomino", "gosper_glider_gun", "pulsar"
+gol_density = 0.3                 ## Share of cells alive at the start (0.0-1.0)
+## "typewriter" animation, typing and erasing these lines (default: the rexit logo)
+## typewriter_text = ["Goodbye!", "See you soon"]
+typewriter_pause_ticks = 60       ## Ticks to wait once the text is typed or erased
+typewriter_x = 2                  ## Position of the text
+typewriter_y = 1
+## "clock" animation
+clock_format = "12h"              ## Options: "12h", "24h", "unix_timestamp"
+clock_drift_secs = 5              ## Move the digits one cell this often to prevent burn-in
+## "waveform" animation
+waveform_bars = 20                ## Number of equalizer bars (5-80)
+waveform_peak_hold = true         ## Mark each bar's recent maximum
+## "dvd_logo" animation
+## dvd_text = "See you"           ## Bouncing text (default: "rexit")
+dvd_trail = false                 ## Leave a fading ghost behind the text
+## "matrix" animation
+matrix_direction = "down"         ## Options: "down", "up", "right", "left", "diagonal_right", "diagonal_left"
+## "plasma_pulse" animation
+plasma_pulse_speed = 0.15         ## How fast the plasma pulses
+## "thermal" animation
+thermal_alpha = 0.1               ## How fast heat spreads (0.0-0.25)
+## "starfield_3d" animation
+starfield_speed = 0.02            ## How fast the stars fly towards you
+## "bouncing_balls" animation
+ball_count = 5                    ## Number of balls (1-20)
+ball_gravity = 0.0                ## Pull towards the floor, e.g. 0.05 (0.0 = float freely)
+## "snake" animation
+snake_collision = true            ## Restart when the snake bites itself (false = crawl through)
+## "pong" animation
+pong_ball_trail = 3               ## Earlier ball positions drawn behind the ball (0 = none)
+## "fireworks" animation
+firework_style = "star"           ## Options: "star", "ring", "weeping_willow", "random"
+## "lissajous" animation
+lissajous_trail = 100             ## Points of each curve left behind the pen (0 = none)
+## "constellation" animation
+constellation_connect_distance = 15.0  ## Join stars closer than this many cells
+## "circuit" animation
+electron_count = 5                ## Electrons running along the traces
+## "aurora_borealis" animation
+aurora_curtain_count = 5          ## Curtains of light (1-20)
+aurora_glow = true                ## Dim halo around each curtain
+## "cellular_automata" animation
+ca_rule = 110                     ## Wolfram rule 0-255; 90 draws a Sierpinski triangle, 30 chaos
+ca_wrap = true                    ## Join the left and right edges
+## "plasma_globe" animation
+plasma_globe_arcs = 6             ## Lightning arcs in the globe (1-20)
+## plasma_globe_radius = 10.0     ## Globe radius in rows (default: a third of the smaller side)
+## Same seed, same animation: handy for screenshots and demos (0 = random)
+## seed = 42
 
 [grace_period]
 ## Grace period configuration for critical actions (shutdown, reboot)
@@ -805,12 +2617,37 @@ enabled = true
 duration_secs = 5
 show_countdown = true
 message_template = "⏱️  {action} in {seconds}s... Press any key to cancel"
+progress_style = "bar"  ## Options: "bar", "ring", "digits"
+## Notify running applications before the action executes
+pre_notify = false
+pre_notify_urgency = "critical"  ## Options: "low", "normal", "critical"
+notify_command = "notify-send"   ## e.g. "dunstify"
+notify_args = []
+## Hold a systemd-logind delay lock (via systemd-inhibit) while counting down
+acquire_inhibitor = false
+
+[auto_trigger]
+## Run an action when nobody touches the menu, e.g. when launched by an idle daemon.
+## The action still goes through confirmation and the grace period.
+enabled = false
+idle_secs = 300
+action_id = "lock"
+idle_warning_secs = 10  ## Show "Idle: Ns" in the help bar during the last seconds
+
+## Accessibility mode, enabled by adding this table
+## [accessibility]
+## high_contrast = false     ## White selection and border; turns animations off unless
+##                           ## animation.enabled is set explicitly
+## bold_selected = false     ## Draw the selected item in bold
+## selection_prefix = "▶ "   ## Marks the selected item without relying on color
+## show_item_numbers = false ## Prefix items with 1., 2., ... for screen readers
 
 [performance]
 ## Performance settings
 auto_degrade = true               ## Enable automatic quality reduction under high CPU
 target_fps = 30                   ## Target frame rate (higher = smoother but more CPU)
 disable_on_low_battery = false    ## Disable animations when battery is low (laptops)
+debug_overlay = false             ## Show FPS and render time in the top-right corner
 "##,
     )
 }
@@ -827,16 +2664,108 @@ fn get_theme_path(theme_name: &str) -> Option<PathBuf> {
     get_themes_dir().map(|dir| dir.join(format!("{}.toml", theme_name)))
 }
 
-fn load_theme(theme_name: &str) -> Option<ThemeConfig> {
-    let theme_path = get_theme_path(theme_name)?;
+/// Theme file extensions, in lookup order; "yaml", "yml" and "sh" hold base16 schemes
+const THEME_EXTENSIONS: &[&str] = &["toml", "yaml", "yml", "sh"];
+
+/// Which rexit color each base16 color is used for
+const BASE16_MAPPING: &[(&str, &str)] = &[
+    ("base00", "background"),
+    ("base00", "selected_fg"),
+    ("base03", "help_fg"),
+    ("base05", "foreground"),
+    ("base08", "icon_color"),
+    ("base0B", "selected_bg"),
+    ("base0B", "animation.color"),
+    ("base0D", "border"),
+    ("base0D", "help_key_fg"),
+];
 
-    if !theme_path.exists() {
-        eprintln!(
-            "Warning: Theme '{}' not found at {}",
-            theme_name,
-            theme_path.display()
-        );
+/// Find the theme file for `theme_name`, trying each of `THEME_EXTENSIONS`
+fn find_theme_file(theme_name: &str) -> Option<PathBuf> {
+    let themes_dir = get_themes_dir()?;
+    THEME_EXTENSIONS
+        .iter()
+        .map(|ext| themes_dir.join(format!("{}.{}", theme_name, ext)))
+        .find(|path| path.exists())
+}
+
+/// Read a base16 scheme, either YAML (`base00: "1d1f21"`) or a shell script
+/// exporting the colors (`export BASE16_COLOR_00_HEX="1d1f21"`)
+fn load_base16_theme(path: &Path) -> Option<ThemeConfig> {
+    let content = fs::read_to_string(path).ok()?;
+
+    let mut colors: HashMap<String, String> = HashMap::new();
+    for line in content.lines() {
+        let line = line.trim();
+        let line = line.strip_prefix("export ").unwrap_or(line);
+        let Some((key, value)) = line.split_once([':', '=']) else {
+            continue;
+        };
+
+        let key = key.trim().to_lowercase();
+        let base = if let Some(digit) = key
+            .strip_prefix("base16_color_0")
+            .and_then(|rest| rest.strip_suffix("_hex"))
+        {
+            format!("base0{}", digit)
+        } else {
+            key
+        };
+        if base.len() != 6 || !base.starts_with("base0") {
+            continue;
+        }
+
+        // Values may be quoted, carry a leading '#', or be written as "1d/1f/21"
+        let value = value.split_whitespace().next().unwrap_or("");
+        let hex: String = value
+            .trim_matches(|c| c == '"' || c == '\'')
+            .trim_start_matches('#')
+            .replace('/', "");
+        if hex.len() == 6 && hex.chars().all(|c| c.is_ascii_hexdigit()) {
+            colors.insert(base, format!("#{}", hex.to_lowercase()));
+        }
+    }
+
+    let name = path
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or_default();
+    let mut theme = theme_from_config(name, &Config::default());
+    for (base, field) in BASE16_MAPPING {
+        let color = colors.get(&base.to_lowercase())?.clone();
+        match *field {
+            "background" => theme.colors.background = color,
+            "foreground" => theme.colors.foreground = color,
+            "selected_fg" => theme.colors.selected_fg = color,
+            "selected_bg" => theme.colors.selected_bg = color,
+            "icon_color" => theme.colors.icon_color = color,
+            "help_fg" => theme.colors.help_fg = color,
+            "help_key_fg" => theme.colors.help_key_fg = color,
+            "border" => theme.colors.border = color,
+            "animation.color" => theme.animation.color = color,
+            _ => {}
+        }
+    }
+    Some(theme)
+}
+
+fn load_theme(theme_name: &str) -> Option<ThemeConfig> {
+    let Some(theme_path) = find_theme_file(theme_name) else {
+        if let Some(theme_path) = get_theme_path(theme_name) {
+            eprintln!(
+                "Warning: Theme '{}' not found at {}",
+                theme_name,
+                theme_path.display()
+            );
+        }
         return None;
+    };
+
+    let is_toml = theme_path.extension().is_some_and(|ext| ext == "toml");
+    if !is_toml {
+        if let Some(theme) = load_base16_theme(&theme_path) {
+            return Some(theme);
+        }
     }
 
     match fs::read_to_string(&theme_path) {
@@ -861,27 +2790,63 @@ fn list_available_themes() -> Vec<String> {
     if let Some(themes_dir) = get_themes_dir() {
         if let Ok(entries) = fs::read_dir(themes_dir) {
             for entry in entries.flatten() {
-                if let Some(name) = entry.file_name().to_str() {
-                    if name.ends_with(".toml") {
-                        themes.push(name[..name.len() - 5].to_string());
-                    }
+                let path = entry.path();
+                let is_theme = path
+                    .extension()
+                    .and_then(|ext| ext.to_str())
+                    .is_some_and(|ext| THEME_EXTENSIONS.contains(&ext));
+                if let (true, Some(stem)) = (is_theme, path.file_stem().and_then(|s| s.to_str())) {
+                    themes.push(stem.to_string());
                 }
             }
         }
     }
 
+    // A scheme may exist in more than one format
+    themes.sort();
+    themes.dedup();
     themes
 }
 
+/// Name shown in the theme menu for the built-in colors
+const DEFAULT_THEME_ENTRY: &str = "(default)";
+
+/// Build a theme from the colors, border and animation of a config
+fn theme_from_config(name: &str, config: &Config) -> ThemeConfig {
+    ThemeConfig {
+        name: name.to_string(),
+        colors: config.colors.clone(),
+        border: config.border.clone(),
+        animation: config.animation.clone(),
+    }
+}
+
+/// Apply a theme by name; "(default)" restores the built-in look
+fn preview_theme(config: &mut Config, theme_name: &str) {
+    let theme = if theme_name == DEFAULT_THEME_ENTRY {
+        Some(theme_from_config(theme_name, &Config::default()))
+    } else {
+        load_theme(theme_name)
+    };
+    if let Some(theme) = theme {
+        merge_theme_into_config(config, theme);
+    }
+}
+
 fn merge_theme_into_config(config: &mut Config, theme: ThemeConfig) {
     config.colors = theme.colors;
     config.border = theme.border;
-    config.animation.animation_type = theme.animation.animation_type;
+    config.animation.animation_type = if theme.animation.animation_type == "random" {
+        pick_random_animation(&random_exclude_list(&config.animation))
+    } else {
+        theme.animation.animation_type
+    };
     config.animation.speed_ms = theme.animation.speed_ms;
     config.animation.color = theme.animation.color;
     config.animation.density = theme.animation.density;
     config.animation.adaptive_quality = theme.animation.adaptive_quality;
     config.animation.min_speed_ms = theme.animation.min_speed_ms;
+    apply_accessibility(config);
 }
 
 fn check_command_exists(command: &str) -> bool {
@@ -907,35 +2872,359 @@ fn check_command_exists(command: &str) -> bool {
     false
 }
 
-fn find_lock_command() -> (String, Vec<String>) {
-    // Try common lock commands in order of preference
-    let lock_commands = [
-        ("hyprlock", vec![]),
-        ("swaylock", vec![]),
-        ("i3lock", vec![]),
-        ("i3lock-fancy", vec![]),
-        ("betterlockscreen", vec!["--lock".to_string()]),
-        ("xlock", vec![]),
-        ("slock", vec![]),
-        ("xflock4", vec![]),
-        ("gnome-screensaver-command", vec!["--lock".to_string()]),
-        ("xscreensaver-command", vec!["--lock".to_string()]),
-        ("loginctl", vec!["lock-session".to_string()]),
-    ];
+/// Expand `$VAR`, `${VAR}` and `${VAR:-default}` like sh; unset variables become empty
+fn expand_env_vars(s: &str) -> String {
+    let mut result = String::with_capacity(s.len());
+    let mut chars = s.chars().peekable();
 
-    for (cmd, args) in lock_commands {
-        if check_command_exists(cmd) {
-            return (cmd.to_string(), args);
+    while let Some(c) = chars.next() {
+        if c != '$' {
+            result.push(c);
+            continue;
         }
-    }
-
-    // Fallback to loginctl as it should work on most systemd systems
-    ("loginctl".to_string(), vec!["lock-session".to_string()])
-}
-
-// ============================================================================
-// KEY PARSING
-// ============================================================================
+
+        match chars.peek() {
+            Some('{') => {
+                chars.next();
+                let mut inner = String::new();
+                let mut closed = false;
+                for c in chars.by_ref() {
+                    if c == '}' {
+                        closed = true;
+                        break;
+                    }
+                    inner.push(c);
+                }
+                if !closed {
+                    // Unterminated "${", keep it literally
+                    result.push_str("${");
+                    result.push_str(&inner);
+                    continue;
+                }
+                let (name, default) = match inner.split_once(":-") {
+                    Some((name, default)) => (name, default),
+                    None => (inner.as_str(), ""),
+                };
+                match std::env::var(name) {
+                    Ok(value) if !value.is_empty() => result.push_str(&value),
+                    // Like sh, the default may itself refer to variables
+                    _ => result.push_str(&expand_env_vars(default)),
+                }
+            }
+            Some(&c) if c == '_' || c.is_ascii_alphabetic() => {
+                let mut name = String::new();
+                while let Some(&c) = chars.peek() {
+                    if c != '_' && !c.is_ascii_alphanumeric() {
+                        break;
+                    }
+                    name.push(c);
+                    chars.next();
+                }
+                result.push_str(&std::env::var(&name).unwrap_or_default());
+            }
+            Some('$') => {
+                chars.next();
+                result.push_str("$$");
+            }
+            // A lone "$" (or "$5") is kept as is
+            _ => result.push('$'),
+        }
+    }
+
+    result
+}
+
+/// Expand `{user}`, `{hostname}`, `{session}`, `{display}` and `{seat}` in an
+/// action argument, e.g. `loginctl terminate-session {session}`
+fn expand_action_vars(s: &str) -> String {
+    if !s.contains('{') {
+        return s.to_string();
+    }
+
+    let env = |name: &str| std::env::var(name).unwrap_or_default();
+    let mut result = s
+        .replace("{user}", &current_user())
+        .replace("{hostname}", &current_hostname())
+        .replace("{seat}", &env("XDG_SEAT"));
+    if result.contains("{display}") {
+        let display = std::env::var("DISPLAY")
+            .ok()
+            .filter(|display| !display.is_empty())
+            .unwrap_or_else(|| env("WAYLAND_DISPLAY"));
+        result = result.replace("{display}", &display);
+    }
+    // Asking loginctl is slow, so only do it when needed
+    if result.contains("{session}") {
+        result = result.replace("{session}", &current_session());
+    }
+    result
+}
+
+/// ID of the login session: `$XDG_SESSION_ID`, or the first word of
+/// `loginctl session-status` ("2 - user (1000)"); empty if neither works.
+/// Looked up once, as loginctl can hang on D-Bus
+fn current_session() -> String {
+    static SESSION: OnceLock<String> = OnceLock::new();
+    SESSION.get_or_init(detect_session).clone()
+}
+
+fn detect_session() -> String {
+    if let Ok(id) = std::env::var("XDG_SESSION_ID") {
+        if !id.is_empty() {
+            return id;
+        }
+    }
+    let mut command = Command::new("loginctl");
+    command.arg("session-status");
+    output_with_timeout(&mut command, Duration::from_secs(2))
+        .ok()
+        .and_then(|status| status.split_whitespace().next().map(str::to_string))
+        .unwrap_or_default()
+}
+
+/// Quote a word for `sh`, leaving plain words untouched
+fn shell_escape(s: &str) -> String {
+    let safe = |c: char| c.is_ascii_alphanumeric() || "_-./=:,+@%".contains(c);
+    if !s.is_empty() && s.chars().all(safe) {
+        return s.to_string();
+    }
+    format!("'{}'", s.replace('\'', "'\\''"))
+}
+
+/// Quote a word for `sh` like `shell_escape`, but in double quotes so the
+/// shell still expands `$VAR` and `${VAR}` in it; any other `$`, such as the
+/// one of a `$(command)`, stays literal
+fn shell_quote_expanding(s: &str) -> String {
+    let safe = |c: char| c.is_ascii_alphanumeric() || "_-./=:,+@%".contains(c);
+    if !s.is_empty() && s.chars().all(safe) {
+        return s.to_string();
+    }
+    let starts_variable = |c: &char| *c == '{' || *c == '_' || c.is_ascii_alphabetic();
+    let mut quoted = String::from("\"");
+    let mut chars = s.chars().peekable();
+    while let Some(c) = chars.next() {
+        let escape = match c {
+            '"' | '\\' | '`' => true,
+            '$' => !chars.peek().is_some_and(starts_variable),
+            _ => false,
+        };
+        if escape {
+            quoted.push('\\');
+        }
+        quoted.push(c);
+    }
+    quoted.push('"');
+    quoted
+}
+
+/// First working lock command; probing runs the candidates, so the result is
+/// looked up once and reused for the rest of the session
+fn find_lock_command() -> (String, Vec<String>) {
+    static LOCK_COMMAND: OnceLock<(String, Vec<String>)> = OnceLock::new();
+    LOCK_COMMAND.get_or_init(detect_lock_command).clone()
+}
+
+fn detect_lock_command() -> (String, Vec<String>) {
+    // Try common lock commands in order of preference, along with an argument
+    // that makes them exit right away. Tools that would lock the screen
+    // whatever the arguments (slock runs them after locking, xflock4 ignores
+    // them) have no probe and are only looked up in PATH
+    let lock_commands: [(&str, &[&str], &str); 13] = [
+        ("hyprlock", &[], "--version"),
+        ("waylock", &[], "-version"),
+        ("gtklock", &[], "--help"),
+        ("swaylock", &[], "--version"),
+        ("i3lock", &[], "--version"),
+        ("i3lock-fancy", &[], "--help"),
+        ("betterlockscreen", &["--lock"], "--help"),
+        ("xlock", &[], "-help"),
+        ("slock", &[], ""),
+        ("xflock4", &[], ""),
+        ("gnome-screensaver-command", &["--lock"], "--help"),
+        ("xscreensaver-command", &["--lock"], "--help"),
+        ("loginctl", &["lock-session"], "--version"),
+    ];
+
+    for (cmd, args, probe_arg) in lock_commands {
+        if !check_command_exists(cmd) {
+            continue;
+        }
+        if probe_arg.is_empty() || probe_command(cmd, &[probe_arg]) {
+            let args = args.iter().map(|arg| arg.to_string()).collect();
+            return (cmd.to_string(), args);
+        }
+    }
+
+    // Fallback to loginctl as it should work on most systemd systems
+    ("loginctl".to_string(), vec!["lock-session".to_string()])
+}
+
+/// Screen lockers niri users start from their config
+const NIRI_LOCKERS: &[&str] = &["swaylock", "gtklock", "hyprlock", "waylock"];
+
+/// The screen locker named in `~/.config/niri/config.kdl`, if it is installed
+///
+/// niri has no lock command of its own; the locker is whatever the config
+/// spawns, either directly (`spawn-at-startup "swaylock"`, `spawn "swaylock"`
+/// in a bind) or as a swayidle argument (`"before-sleep" "swaylock -f"`).
+fn niri_lock_command() -> Option<(String, Vec<String>)> {
+    let path = BaseDirs::new()?.config_dir().join("niri/config.kdl");
+    let config = fs::read_to_string(path).ok()?;
+    config
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.starts_with("//") && line.contains("spawn"))
+        .find_map(|line| {
+            // Each quoted string is one argument of the spawned command
+            let quoted: Vec<&str> = line.split('"').skip(1).step_by(2).collect();
+            quoted.iter().enumerate().find_map(|(i, part)| {
+                let mut words = part.split_whitespace();
+                let cmd = words.next()?;
+                let name = Path::new(cmd).file_name()?.to_str()?;
+                if !NIRI_LOCKERS.contains(&name) || !check_command_exists(cmd) {
+                    return None;
+                }
+                let mut args: Vec<String> = words.map(str::to_string).collect();
+                if i == 0 {
+                    args.extend(quoted[1..].iter().map(|arg| arg.to_string()));
+                }
+                Some((cmd.to_string(), args))
+            })
+        })
+}
+
+/// Whether `cmd` starts and exits within two seconds when run with `probe_args`.
+/// Any exit code counts, as some tools exit with 1 even for `--help`; a broken
+/// install that fails to start or hangs does not
+fn probe_command(cmd: &str, probe_args: &[&str]) -> bool {
+    let mut command = Command::new(cmd);
+    command.args(probe_args).stdin(Stdio::null());
+    run_with_timeout(&mut command, Duration::from_secs(2)).is_ok()
+}
+
+/// Run a command and kill it if it has not exited within `timeout`
+fn run_with_timeout(cmd: &mut Command, timeout: std::time::Duration) -> Result<()> {
+    let mut child = cmd
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .context("Failed to spawn command")?;
+    wait_with_timeout(&mut child, timeout)
+}
+
+/// Like `run_with_timeout`, returning what the command printed to stdout
+fn output_with_timeout(cmd: &mut Command, timeout: std::time::Duration) -> Result<String> {
+    use std::io::Read;
+
+    let mut child = cmd
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .context("Failed to spawn command")?;
+    let mut stdout = child.stdout.take().context("Failed to capture stdout")?;
+    // Read on a thread so a command printing more than a pipe holds can't block
+    let reader = std::thread::spawn(move || {
+        let mut output = String::new();
+        stdout.read_to_string(&mut output).map(|_| output)
+    });
+    wait_with_timeout(&mut child, timeout)?;
+    reader
+        .join()
+        .map_err(|_| anyhow::anyhow!("Failed to read command output"))?
+        .context("Failed to read command output")
+}
+
+/// Wait for `child`, killing it once `timeout` has passed
+fn wait_with_timeout(child: &mut Child, timeout: std::time::Duration) -> Result<()> {
+    let deadline = Instant::now() + timeout;
+
+    loop {
+        if child.try_wait()?.is_some() {
+            return Ok(());
+        }
+        if Instant::now() >= deadline {
+            let _ = child.kill();
+            let _ = child.wait();
+            anyhow::bail!("Command timed out after {}s", timeout.as_secs());
+        }
+        std::thread::sleep(std::time::Duration::from_millis(50));
+    }
+}
+
+/// The clipboard tool of the running session: wl-copy on Wayland, xclip on X11
+fn clipboard_command() -> Option<(&'static str, &'static [&'static str])> {
+    let (cmd, args): (&str, &[&str]) = if std::env::var_os("WAYLAND_DISPLAY").is_some() {
+        ("wl-copy", &[])
+    } else {
+        ("xclip", &["-selection", "clipboard"])
+    };
+    check_command_exists(cmd).then_some((cmd, args))
+}
+
+/// Put `text` on the clipboard, returning whether it worked
+fn copy_to_clipboard(text: &str) -> bool {
+    use std::io::Write;
+
+    let Some((cmd, args)) = clipboard_command() else {
+        return false;
+    };
+    let Ok(mut child) = Command::new(cmd)
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+    else {
+        return false;
+    };
+    // xclip stays around to serve the selection, so do not wait for it
+    child
+        .stdin
+        .take()
+        .is_some_and(|mut stdin| stdin.write_all(text.as_bytes()).is_ok())
+}
+
+/// Warn about an imminent critical action through the notification command
+fn send_pre_notification(grace: &GracePeriodConfig, action: &str, seconds: u64) {
+    if grace.notify_command.is_empty() || !check_command_exists(&grace.notify_command) {
+        return;
+    }
+
+    let mut cmd = Command::new(&grace.notify_command);
+    cmd.args(&grace.notify_args);
+    if !grace.pre_notify_urgency.is_empty() {
+        cmd.arg("-u").arg(&grace.pre_notify_urgency);
+    }
+    cmd.arg(format!("rexit: {} in {}s", action, seconds));
+
+    // A hanging notification daemon must not hold up the action
+    let _ = run_with_timeout(&mut cmd, std::time::Duration::from_secs(2));
+}
+
+/// Take a logind delay lock on shutdown and sleep; it lasts until the returned child is killed
+fn acquire_inhibitor() -> Option<Child> {
+    if !check_command_exists("systemd-inhibit") {
+        return None;
+    }
+    Command::new("systemd-inhibit")
+        .args([
+            "--who=rexit",
+            "--what=shutdown:sleep",
+            "--why=Giving user chance to cancel",
+            "--mode=delay",
+            "sleep",
+            "infinity",
+        ])
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .ok()
+}
+
+// ============================================================================
+// KEY PARSING
+// ============================================================================
 
 #[derive(Debug, Clone)]
 struct KeyBinding {
@@ -968,7 +3257,10 @@ fn parse_key(key_str: &str) -> Option<KeyBinding> {
     let key = match key_part {
         "Esc" | "esc" | "Escape" => KeyCode::Esc,
         "Enter" | "enter" | "Return" => KeyCode::Enter,
+        // Terminals report Shift+Tab as BackTab
+        "Tab" if shift => KeyCode::BackTab,
         "Tab" => KeyCode::Tab,
+        "BackTab" => KeyCode::BackTab,
         "Backspace" => KeyCode::Backspace,
         "Delete" | "Del" => KeyCode::Delete,
         "Insert" | "Ins" => KeyCode::Insert,
@@ -1017,7 +3309,16 @@ fn matches_key(key: &KeyBinding, event: &crossterm::event::KeyEvent) -> bool {
     let alt = modifiers.contains(crossterm::event::KeyModifiers::ALT);
     let shift = modifiers.contains(crossterm::event::KeyModifiers::SHIFT);
 
-    key.ctrl == ctrl && key.alt == alt && key.shift == shift
+    // Uppercase characters and symbols already imply Shift, so "Y" matches Shift+y
+    // and "?" matches however the layout produces it; so does BackTab, which
+    // some terminals send without the Shift flag
+    let implied_shift = match key.key {
+        KeyCode::Char(c) => !c.is_lowercase(),
+        KeyCode::BackTab => true,
+        _ => false,
+    };
+
+    key.ctrl == ctrl && key.alt == alt && (key.shift == shift || implied_shift)
 }
 
 // ============================================================================
@@ -1026,6 +3327,8 @@ fn matches_key(key: &KeyBinding, event: &crossterm::event::KeyEvent) -> bool {
 
 #[derive(Debug, Clone)]
 struct Action {
+    /// Key of the action in the `[actions]` table (e.g. "shutdown")
+    id: String,
     icon: String,
     label: String,
     command: String,
@@ -1033,17 +3336,124 @@ struct Action {
     confirm: bool,
     favorite: bool,
     shortcut: String,
+    expand_env: bool,
+    shell: bool,
+    category: Option<String>,
+    submenu: Vec<String>, // Ids of the actions in this action's sub-menu
+    icon_cell_width: Option<u8>,
+    max_retries: u8,
 }
 
 impl Action {
-    fn display_text(&self, show_shortcut: bool) -> String {
-        if show_shortcut && !self.shortcut.is_empty() {
-            format!("{} [{}] {}", self.icon, self.shortcut, self.label)
+    fn from_config(id: &str, config: &ActionConfig, use_emoji: bool) -> Self {
+        Self {
+            id: id.to_string(),
+            icon: resolve_icon(config, use_emoji),
+            label: config.label.clone(),
+            command: config.command.clone(),
+            args: config.args.clone(),
+            confirm: config.confirm,
+            favorite: config.favorite,
+            shortcut: config.shortcut.clone(),
+            expand_env: config.expand_env,
+            shell: config.shell,
+            category: config.category.clone(),
+            submenu: config.submenu.clone().unwrap_or_default(),
+            icon_cell_width: config.icon_cell_width,
+            max_retries: config.max_retries,
+        }
+    }
+
+    /// Adjust logout and lock commands to the window manager and installed lockers
+    fn adapt_to_system(&mut self, wm_type: &str) {
+        let label_lower = self.label.to_lowercase();
+        if label_lower.contains("logout") || label_lower.contains("exit") {
+            let (cmd, args) = get_logout_command(wm_type);
+            if !cmd.is_empty() {
+                self.command = cmd;
+                self.args = args;
+            }
+        }
+
+        // Check for lock command availability and fallback if needed
+        if self.is_lock() && !self.command_exists() {
+            let niri_lock = (wm_type == "niri").then(niri_lock_command).flatten();
+            let (cmd, args) = niri_lock.unwrap_or_else(find_lock_command);
+            self.command = cmd;
+            self.args = args;
+        }
+    }
+
+    /// Category heading of this action; uncategorized actions share "Other"
+    fn category_name(&self) -> &str {
+        self.category.as_deref().unwrap_or("Other")
+    }
+
+    fn display_text(&self, config: &Config) -> String {
+        if config.show_shortcut && !self.shortcut.is_empty() {
+            self.with_icon(&format!("[{}] {}", self.shortcut, self.label), config)
+        } else {
+            self.with_icon(&self.label, config)
+        }
+    }
+
+    /// Icon, shortcut and label padded to `widths`, in the order `icon_position` asks for
+    ///
+    /// Each piece carries its trailing gap, so joining them gives the whole row.
+    fn aligned_columns(&self, config: &Config, widths: &ColumnWidths) -> Vec<(Column, String)> {
+        let gap = " ".repeat(config.icon_padding as usize);
+        let shortcut = if self.shortcut.is_empty() {
+            String::new()
         } else {
-            format!("{} {}", self.icon, self.label)
+            format!("[{}]", self.shortcut)
+        };
+
+        let mut columns = Vec::with_capacity(3);
+        if widths.shortcut > 0 {
+            columns.push((
+                Column::Shortcut,
+                pad_to_width(&shortcut, widths.shortcut) + " ",
+            ));
+        }
+        columns.push((Column::Label, pad_to_width(&self.label, widths.label)));
+        let icon = pad_to_width(&self.padded_icon(config), widths.icon);
+        match config.icon_position.as_str() {
+            "right" => {
+                if let Some((_, label)) = columns.last_mut() {
+                    label.push_str(&gap);
+                }
+                columns.push((Column::Icon, icon));
+            }
+            "none" => {}
+            _ => columns.insert(0, (Column::Icon, icon + &gap)),
+        }
+        columns
+    }
+
+    /// Place the icon around `text` according to `icon_position` and `icon_padding`
+    fn with_icon(&self, text: &str, config: &Config) -> String {
+        let gap = " ".repeat(config.icon_padding as usize);
+        match config.icon_position.as_str() {
+            "right" => format!("{}{}{}", text, gap, self.padded_icon(config)),
+            "none" => text.to_string(),
+            _ => format!("{}{}{}", self.padded_icon(config), gap, text),
         }
     }
 
+    /// Icon padded with spaces to the cells it should take up
+    ///
+    /// `force_icon_width` wins over the action's `icon_cell_width`; without either,
+    /// the width of the icon's first character is used.
+    fn padded_icon(&self, config: &Config) -> String {
+        use unicode_width::UnicodeWidthChar;
+        let detected = self.icon.chars().next().and_then(|c| c.width());
+        let width = match config.force_icon_width.or(self.icon_cell_width) {
+            Some(width) => width.clamp(1, 2) as u16,
+            None => detected.unwrap_or(0) as u16,
+        };
+        pad_to_width(&self.icon, width)
+    }
+
     fn is_critical(&self) -> bool {
         // Auto-detect critical actions if confirm is not explicitly set
         let lower = self.label.to_lowercase();
@@ -1053,15 +3463,75 @@ impl Action {
             || lower.contains("halt")
     }
 
+    fn is_logout(&self) -> bool {
+        self.id == "logout" || self.label.to_lowercase().contains("logout")
+    }
+
+    fn is_lock(&self) -> bool {
+        self.id == "lock" || self.label.to_lowercase().contains("lock")
+    }
+
+    /// Command with environment variables expanded unless `expand_env` is off
+    /// or `sh` does the expanding
+    fn resolved_command(&self) -> String {
+        if self.expand_env && !self.shell {
+            expand_env_vars(&self.command)
+        } else {
+            self.command.clone()
+        }
+    }
+
+    /// Whether the command can run; with `shell` only `sh` itself has to exist
+    fn command_exists(&self) -> bool {
+        if self.shell {
+            !self.command.is_empty()
+        } else {
+            check_command_exists(&self.resolved_command())
+        }
+    }
+
+    fn build_command(&self) -> Command {
+        let args: Vec<String> = if !self.expand_env {
+            self.args.clone()
+        } else if self.shell {
+            self.args
+                .iter()
+                .map(|arg| expand_action_vars(arg))
+                .collect()
+        } else {
+            self.args
+                .iter()
+                .map(|arg| expand_action_vars(&expand_env_vars(arg)))
+                .collect()
+        };
+
+        if self.shell {
+            let quote = if self.expand_env {
+                shell_quote_expanding
+            } else {
+                shell_escape
+            };
+            let mut line = self.resolved_command();
+            for arg in &args {
+                line.push(' ');
+                line.push_str(&quote(arg));
+            }
+            let mut cmd = Command::new("sh");
+            cmd.arg("-c").arg(line);
+            cmd
+        } else {
+            let mut cmd = Command::new(self.resolved_command());
+            cmd.args(&args);
+            cmd
+        }
+    }
+
     fn execute(&self) -> Result<()> {
         if self.command.is_empty() {
             return Ok(());
         }
 
-        let mut cmd = Command::new(&self.command);
-        cmd.args(&self.args);
-
-        let status = cmd.status()
+        let status = self.build_command().status()
             .with_context(|| format!("Failed to execute command: {}", self.command))?;
         
         if !status.success() {
@@ -1070,33 +3540,117 @@ impl Action {
 
         Ok(())
     }
+
+    /// The command with its arguments, as shown when it fails
+    fn command_line(&self) -> String {
+        std::iter::once(self.command.as_str())
+            .chain(self.args.iter().map(String::as_str))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    /// Start the command without waiting for it, detached from the terminal
+    fn spawn(&self) -> Result<Child> {
+        self.build_command()
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+            .with_context(|| format!("Failed to execute command: {}", self.command))
+    }
 }
 
 // ============================================================================
 // APPLICATION STATE
 // ============================================================================
 
+/// Which button of the confirmation dialog has focus
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ConfirmFocus {
+    Yes,
+    No,
+}
+
+impl ConfirmFocus {
+    fn toggle(self) -> Self {
+        match self {
+            ConfirmFocus::Yes => ConfirmFocus::No,
+            ConfirmFocus::No => ConfirmFocus::Yes,
+        }
+    }
+}
+
+#[derive(Clone)]
 enum AppState {
     Selecting,
     Confirming {
-        action_index: usize,
+        action_ref: ActionRef,
+        focus: ConfirmFocus,
+        confirm_started: Option<std::time::Instant>,
     },
     GracePeriod {
-        action_index: usize,
+        action_ref: ActionRef,
         remaining_secs: u64,
         last_tick: std::time::Instant,
     },
     AnimationMenu,
+    ThemeMenu {
+        theme_index: usize,
+    },
+    Running {
+        action_ref: ActionRef,
+        child_pid: u32,
+        started: Instant,
+    },
+    CommandError {
+        action_ref: ActionRef,
+        message: String,
+        focus: ConfirmFocus,
+        copied: bool,
+    },
+    HelpOverlay,
+    SubMenu {
+        parent_index: usize,
+        sub_actions: Vec<Action>,
+        selected: usize,
+    },
 }
 
-/// Tracks easter egg state for Konami code
+/// Tracks easter egg state for Konami code and the secret word
 #[derive(Debug)]
 struct EasterEggState {
     sequence: Vec<KeyCode>,
     konami_code: Vec<KeyCode>,
     rainbow_mode: bool,
+    typed: String,      // Last letters typed, compared with SECRET_WORD
+    secret_mode: bool,  // Skull in the confirmation dialog, quotes in the grace period
+    quote_index: usize, // Quote of the current grace period
 }
 
+/// Typing this word toggles the secret mode
+const SECRET_WORD: &str = "rexit";
+
+/// Shown instead of the question in the confirmation dialog in secret mode
+const SKULL: [&str; 5] = [
+    "   _____   ",
+    r"  /     \  ",
+    " | () () | ",
+    r"  \  ^  /  ",
+    "   |||||   ",
+];
+
+/// Shown instead of the grace period message in secret mode
+const DRAMATIC_QUOTES: &[&str] = &[
+    "All we have to decide is what to do with the time that is given us.",
+    "This is the end, beautiful friend.",
+    "So long, and thanks for all the fish.",
+    "The rest is silence.",
+    "Parting is such sweet sorrow.",
+    "I'll be back.",
+    "Do not go gentle into that good night.",
+    "All those moments will be lost in time, like tears in rain.",
+];
+
 struct App {
     actions: Vec<Action>,
     selected_index: usize,
@@ -1104,20 +3658,55 @@ struct App {
     config: Config,
     animation_state: AnimationState,
     state: AppState,
-    last_executed: Option<String>, // label of last executed action
+    last_executed: Option<String>, // id of last executed action
     easter_egg: EasterEggState,
     animation_menu_index: usize,
     grace_period_cancelled: bool, // Track if grace period was cancelled
     performance_monitor: PerformanceMonitor,
+    ipc_receiver: Option<mpsc::Receiver<IpcMessage>>, // Commands from the IPC socket
+    scroll_offset: usize,                             // First row shown in the vertical list
+    visible_items: usize,                             // Rows available in the vertical list
+    title: String,                                    // Title with template variables expanded
+    title_updated: Instant,                           // When `title` was last expanded
+    theme_menu_entries: Vec<String>,                  // "(default)" plus installed themes
+    theme_menu_original: Option<Config>,              // Config to restore on cancel
+    animation_cycled: Instant,                        // When cycling last switched animation
+    running_child: Option<Child>,                     // Command shown in AppState::Running
+    retry_attempt: u8,                                // Automatic retries of the running action
+    error_log: Vec<(String, String)>,                 // (command, error) of each failed action
+    inhibitor: Option<Child>,                         // systemd-inhibit held while counting down
+    last_input: Instant,                              // Last key or mouse event, for auto_trigger
+    menu_opened_at: Instant,                          // Last key press, for auto_quit_secs
+    watch: bool,                                      // --watch: Esc and Cancel keep the menu open
+    term_caps: TermCapabilities,                      // Color depth and Nerd Font support
+    use_emoji: bool,                                  // Emoji instead of Nerd Font icons
+    submenu_choice: Option<Action>,                   // Sub-menu entry of ActionRef::SubmenuChoice
+    open_windows: Option<usize>,                      // Counted when a logout dialog opens
+    debug_log: Option<BufWriter<File>>,               // --debug-log, flushed on exit
+    debug_keys: bool,                                 // --debug-keys: log key presses too
+    logged_state: AppState,                           // State last written to debug_log
+}
+
+/// The action a dialog or running command belongs to
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ActionRef {
+    /// Entry of the main menu, by position in `App::actions`
+    Index(usize),
+    /// Entry picked in a sub-menu, kept in `App::submenu_choice`
+    SubmenuChoice,
 }
 
-const ANIMATION_TYPES: &[&str; 71] = &[
+const ANIMATION_TYPES: &[&str; 85] = &[
     "matrix",
     "matrix_cjk",
+    "matrix_vertical",
+    "matrix_diagonal",
     "rain",
+    "rain_window",
     "thunder",
     "snow",
     "stars",
+    "starfield_3d",
     "fireflies",
     "fireworks",
     "neon_grid",
@@ -1131,8 +3720,11 @@ const ANIMATION_TYPES: &[&str; 71] = &[
     "digital_rain",
     "heartbeat",
     "plasma",
+    "plasma_pulse",
+    "plasma_globe",
     "scanlines",
     "aurora",
+    "aurora_borealis",
     "autumn",
     "dna",
     "synthwave",
@@ -1147,6 +3739,7 @@ const ANIMATION_TYPES: &[&str; 71] = &[
     "morse",
     "lissajous",
     "game_of_life",
+    "cellular_automata",
     // New animations v1.1.5
     "ocean",
     "ripple",
@@ -1183,21 +3776,81 @@ const ANIMATION_TYPES: &[&str; 71] = &[
     "glitch",
     "old_film",
     "thermal",
+    "typewriter",
+    "clock",
+    "waveform",
+    "northern_lights",
+    "dvd_logo",
+    "bouncing_balls",
     "none",
 ];
 
+/// The user's `random_exclude` list as string slices
+fn random_exclude_list(animation: &AnimationConfig) -> Vec<&str> {
+    animation
+        .random_exclude
+        .iter()
+        .map(String::as_str)
+        .collect()
+}
+
+/// Pick a random animation, skipping "none" and everything in `exclude`
+fn pick_random_animation(exclude: &[&str]) -> String {
+    use rand::seq::SliceRandom;
+    let candidates: Vec<&str> = ANIMATION_TYPES
+        .iter()
+        .copied()
+        .filter(|a| *a != "none" && !exclude.contains(a))
+        .collect();
+    candidates
+        .choose(&mut rand::thread_rng())
+        .unwrap_or(&"none")
+        .to_string()
+}
+
+/// Terminal resizes up to this many cells per axis don't reinitialize the animation
+const ANIMATION_RESIZE_THRESHOLD: u16 = 5;
+
+/// Waypoints the "mandelbrot" animation flies between, looping back to the start:
+/// (real, imaginary, zoom, ticks to get there from the previous waypoint)
+const MANDELBROT_TOUR: &[(f32, f32, f32, f32)] = &[
+    (-0.5, 0.0, 1.0, 200.0),        // Whole set
+    (-0.7435, 0.1314, 20.0, 400.0), // Seahorse valley
+    (-0.722, 0.246, 25.0, 250.0),   // Seahorse spiral
+    (-0.5, 0.0, 1.0, 300.0),        // Whole set
+    (0.29, 0.0, 15.0, 400.0),       // Elephant valley
+    (0.3245, 0.0486, 30.0, 250.0),  // Elephant spiral
+    (-0.088, 0.654, 20.0, 400.0),   // Triple spiral valley
+    (0.0, 1.0, 20.0, 300.0),        // Dendrite at c = i
+    (-0.5, 0.0, 1.0, 300.0),        // Whole set
+    (-1.36, 0.0, 12.0, 400.0),      // Scepter valley
+    (-1.768, 0.0, 30.0, 300.0),     // Mini Mandelbrot on the needle
+];
+
 /// Animation state for background effects
 struct AnimationState {
     /// Current animation frame/tick
     tick: u64,
+    /// Random numbers for init and updates, seeded from `animation.seed` if set
+    rng: SmallRng,
+    seed: Option<u64>,
+    /// Area and animation type of the last init, to spot resizes and switches
+    last_area: Rect,
+    last_type: String,
+    /// Animation type the `animation_blend` layer was last set up for
+    blend_type: String,
     /// Matrix rain columns (x position, y position, speed, char)
     matrix_columns: Vec<MatrixColumn>,
     /// Rain drops (x position, y position, speed)
     rain_drops: Vec<RainDrop>,
+    /// Drops running down the glass of the "rain_window" animation
+    window_drops: Vec<WindowDrop>,
     /// Snow flakes (x position, y position, speed, size)
     snow_flakes: Vec<SnowFlake>,
     /// Stars (x position, y position, brightness, twinkle speed)
     stars: Vec<Star>,
+    /// Stars of the "starfield_3d" animation flying towards the viewer
+    stars_3d: Vec<Star3D>,
     /// Fireflies (x position, y position, dx, dy, brightness)
     fireflies: Vec<Firefly>,
     /// Bubbles (x position, y position, speed, size)
@@ -1236,6 +3889,8 @@ struct AnimationState {
     vortex_angle: f32,
     /// Circuit traces
     traces: Vec<CircuitTrace>,
+    /// Electrons running along the circuit traces
+    electrons: Vec<ElectronPacket>,
     /// Flow field particles
     flow_particles: Vec<FlowParticle>,
     /// Morse code
@@ -1293,10 +3948,20 @@ struct AnimationState {
     tetris: TetrisGame,
     /// Space invaders
     invaders: Vec<Invader>,
+    /// Player, shots and explosions of the space invaders
+    invader_game: InvaderGame,
     /// Fibonacci spiral angle
     fibonacci_angle: f32,
-    /// Mandelbrot offset
-    mandelbrot_offset: (f32, f32),
+    /// Mandelbrot view center, real part
+    mandelbrot_real: f32,
+    /// Mandelbrot view center, imaginary part
+    mandelbrot_imag: f32,
+    /// Mandelbrot magnification, 1.0 shows the whole set
+    mandelbrot_zoom: f32,
+    /// Index of the `MANDELBROT_TOUR` waypoint the view is heading to
+    mandelbrot_tour_idx: usize,
+    /// Ticks spent heading to the current waypoint
+    mandelbrot_tour_tick: f32,
     /// Hex grid phase
     hex_phase: f32,
     /// Rose curve parameters
@@ -1331,87 +3996,342 @@ struct AnimationState {
     glitch_timer: u8,
     /// Old film scratches
     scratches: Vec<FilmScratch>,
-    /// Thermal noise
-    thermal_noise: Vec<u8>,
-    /// Last update time
-    last_update: std::time::Instant,
+    /// Thermal temperatures (0.0-1.0) and the buffer the next step is computed into
+    thermal_noise: Vec<f32>,
+    thermal_next: Vec<f32>,
+    /// Typewriter cursor
+    typewriter: TypewriterState,
+    /// Clock text and position
+    clock: ClockState,
+    /// Equalizer bar heights (0.0-1.0) and their held peaks
+    waveform_bars: Vec<f32>,
+    waveform_peaks: Vec<f32>,
+    /// Northern lights curtains
+    northern_lights: NorthernLightsState,
+    /// Bouncing "dvd_logo" text, its color and recent positions
+    dvd_x: f32,
+    dvd_y: f32,
+    dvd_vx: f32,
+    dvd_vy: f32,
+    dvd_text: String,
+    dvd_color_idx: usize,
+    dvd_trail: VecDeque<(f32, f32)>,
+    /// Balls of the "bouncing_balls" animation
+    balls: Vec<Ball>,
+    /// Curtains of the "aurora_borealis" animation
+    aurora_curtains: Vec<AuroraCurtain>,
+    /// Current generation of the "cellular_automata" animation
+    ca_row: Vec<bool>,
+    /// Generations on screen, oldest first
+    ca_history: VecDeque<Vec<bool>>,
+    /// Lightning of the "plasma_globe" animation
+    plasma_arcs: Vec<PlasmaArc>,
 }
 
-struct MatrixColumn {
-    x: u16,
-    y: f32,
-    speed: f32,
-    char_idx: usize,
+/// Insertion point of the "typewriter" animation; `col` characters of `line` are shown
+#[derive(Default)]
+struct TypewriterState {
+    line: usize,
+    col: usize,
+    erasing: bool,
+    pause_timer: u64,
 }
 
-struct RainDrop {
-    x: u16,
-    y: f32,
-    speed: f32,
-    length: u16,
-}
+/// Colors the "dvd_logo" text switches through on every bounce
+const DVD_COLORS: [(u8, u8, u8); 8] = [
+    (255, 64, 64),
+    (255, 160, 0),
+    (255, 255, 0),
+    (64, 255, 64),
+    (0, 255, 255),
+    (64, 128, 255),
+    (192, 64, 255),
+    (255, 64, 192),
+];
 
-struct SnowFlake {
+/// Positions kept for the "dvd_logo" trail
+const DVD_TRAIL_LEN: usize = 6;
+
+/// Ball of the "bouncing_balls" animation; `radius` is in cells across, half
+/// as many rows since cells are about twice as tall as wide
+struct Ball {
     x: f32,
     y: f32,
-    speed: f32,
-    size: u8,
+    vx: f32,
+    vy: f32,
+    radius: f32,
+    color: (u8, u8, u8),
 }
 
-struct Star {
-    x: u16,
-    y: u16,
-    brightness: u8,
-    twinkle_speed: f32,
-    twinkle_offset: f32,
+/// Swaying curtains of the "northern_lights" animation
+#[derive(Default)]
+struct NorthernLightsState {
+    curtains: Vec<Curtain>,
+    phase: f32,
 }
 
-struct Firefly {
+struct Curtain {
     x: f32,
-    y: f32,
-    dx: f32,
-    dy: f32,
-    brightness: u8,
+    width: f32,
+    /// Hue in degrees, drifting slowly
+    hue: f32,
+    /// Row where the curtain starts; also shifts its sway
+    offset: f32,
 }
 
-struct Bubble {
-    x: f32,
-    y: f32,
-    speed: f32,
-    size: u8,
-    wobble: f32,
+/// Band of light of the "aurora_borealis" animation, hanging from near the top
+struct AuroraCurtain {
+    base_x: f32,
+    height: u16,
+    phase_offset: f32,
+    /// Position on the green, teal, blue, purple color cycle, in degrees
+    color_hue: f32,
+    sway_speed: f32,
 }
 
-struct Confetti {
-    x: f32,
-    y: f32,
-    dx: f32,
-    dy: f32,
-    color: u8, // hue 0-255
-    rotation: f32,
-    rotation_speed: f32,
-    character: char,
+/// Lightning arc of the "plasma_globe", from the center to the glass
+struct PlasmaArc {
+    angle: f32,
+    /// Share of the radius reached so far; new arcs grow out to the glass
+    length: f32,
+    /// Jagged path from the center, relative to it in rows
+    segments: Vec<(f32, f32)>,
+    color: (u8, u8, u8),
+    /// Frames left for a branch split off another arc; main arcs have none
+    branch_life: Option<u8>,
 }
 
-struct Particle {
-    x: f32,
-    y: f32,
-    dx: f32,
-    dy: f32,
-    life: u8,
-    max_life: u8,
-    color: Color,
+const PLASMA_ARC_COLORS: [(u8, u8, u8); 3] = [(255, 110, 255), (190, 120, 255), (130, 160, 255)];
+
+impl PlasmaArc {
+    fn new(angle: f32, rng: &mut SmallRng) -> Self {
+        use rand::Rng;
+
+        Self {
+            angle,
+            length: 0.0,
+            segments: Vec::new(),
+            color: PLASMA_ARC_COLORS[rng.gen_range(0..PLASMA_ARC_COLORS.len())],
+            branch_life: None,
+        }
+    }
 }
 
-struct PlasmaCell {
+/// Radius of the "plasma_globe" in rows, no larger than the screen
+fn plasma_globe_radius(area: Rect, config: &AnimationConfig) -> f32 {
+    let smaller_side = area.width.min(area.height) as f32;
+    config
+        .plasma_globe_radius
+        .unwrap_or(smaller_side / 3.0)
+        .min(smaller_side)
+        .max(2.0)
+}
+
+/// Green, teal, blue and purple as the curtain's `color_hue` goes round
+fn aurora_borealis_hue(color_hue: f32) -> f32 {
+    130.0 + 75.0 * (1.0 - color_hue.to_radians().cos())
+}
+
+/// Time shown by the "clock" animation and where it is drifting
+#[derive(Default)]
+struct ClockState {
+    text: String,
+    /// When the next second starts; the time is only read again then
+    next_read: Option<Instant>,
+    last_drift: u64,
     x: u16,
     y: u16,
-    value: f32,
+    dx: i8,
+    dy: i8,
 }
 
-struct Leaf {
-    x: f32,
-    y: f32,
+/// 5-row font of the "clock" animation, one glyph per digit
+const CLOCK_DIGITS: [[&str; 5]; 10] = [
+    ["███", "█ █", "█ █", "█ █", "███"],
+    [" █ ", "██ ", " █ ", " █ ", "███"],
+    ["███", "  █", "███", "█  ", "███"],
+    ["███", "  █", "███", "  █", "███"],
+    ["█ █", "█ █", "███", "  █", "  █"],
+    ["███", "█  ", "███", "  █", "███"],
+    ["███", "█  ", "███", "█ █", "███"],
+    ["███", "  █", "  █", "  █", "  █"],
+    ["███", "█ █", "███", "█ █", "███"],
+    ["███", "█ █", "███", "  █", "███"],
+];
+
+/// Rows of `text` in the clock font; colons get a narrow glyph and any other
+/// character (the AM/PM suffix) is written as-is on the bottom row
+fn clock_rows(text: &str) -> [String; 5] {
+    let mut rows: [String; 5] = Default::default();
+    for (i, ch) in text.chars().enumerate() {
+        let is_glyph = ch.is_ascii_digit() || ch == ':';
+        for (row_index, row) in rows.iter_mut().enumerate() {
+            if i > 0 && is_glyph {
+                row.push(' ');
+            }
+            match ch.to_digit(10) {
+                Some(digit) => row.push_str(CLOCK_DIGITS[digit as usize][row_index]),
+                None if ch == ':' => row.push(if row_index % 2 == 1 { '█' } else { ' ' }),
+                None => row.push(if row_index == 4 { ch } else { ' ' }),
+            }
+        }
+    }
+    rows
+}
+
+struct MatrixColumn {
+    /// Lane: the column, or the row when moving sideways
+    x: u16,
+    /// Distance traveled along the direction of motion
+    y: f32,
+    speed: f32,
+    char_idx: usize,
+    /// Columns shifted per row traveled; -1.0 or 1.0 on diagonals
+    vx: f32,
+}
+
+/// Unit step of the "matrix" characters; `matrix_vertical` and
+/// `matrix_diagonal` fix the axis, plain "matrix" follows `matrix_direction`
+fn matrix_direction(kind: &str, animation: &AnimationConfig) -> (i8, i8) {
+    match (kind, animation.matrix_direction.as_str()) {
+        ("matrix_vertical", _) => (0, -1),
+        ("matrix_diagonal", "diagonal_left") => (-1, 1),
+        ("matrix_diagonal", _) => (1, 1),
+        ("matrix", "up") => (0, -1),
+        ("matrix", "right") => (1, 0),
+        ("matrix", "left") => (-1, 0),
+        ("matrix", "diagonal_right") => (1, 1),
+        ("matrix", "diagonal_left") => (-1, 1),
+        _ => (0, 1),
+    }
+}
+
+/// Number of lanes and their length for a matrix moving in `direction`
+fn matrix_axes(direction: (i8, i8), area: Rect) -> (u16, f32) {
+    if direction.1 == 0 {
+        (area.height.max(1), area.width as f32)
+    } else {
+        (area.width.max(1), area.height as f32)
+    }
+}
+
+/// Sideways drift per row of a column moving in `direction`
+fn matrix_vx(direction: (i8, i8)) -> f32 {
+    if direction.1 != 0 {
+        direction.0 as f32
+    } else {
+        0.0
+    }
+}
+
+/// Screen cell `trail` steps behind the head of a matrix column
+fn matrix_cell(
+    col: &MatrixColumn,
+    trail: u16,
+    direction: (i8, i8),
+    size: Rect,
+) -> Option<(u16, u16)> {
+    let travel = (col.y as u16).checked_sub(trail)?;
+    let (x, y) = match direction {
+        (1, 0) => (travel, col.x),
+        (-1, 0) => (size.width.checked_sub(travel + 1)?, col.x),
+        (0, -1) => (col.x, size.height.checked_sub(travel + 1)?),
+        (0, _) => (col.x, travel),
+        // Diagonals wrap around the sides
+        _ => (
+            (col.x as f32 + travel as f32 * col.vx).rem_euclid(size.width.max(1) as f32) as u16,
+            travel,
+        ),
+    };
+    (x < size.width && y < size.height).then_some((x, y))
+}
+
+struct RainDrop {
+    x: u16,
+    y: f32,
+    speed: f32,
+    length: u16,
+}
+
+/// Drop running down a window pane, pooling at the bottom
+struct WindowDrop {
+    x: f32,
+    y: f32,
+    speed: f32,
+    /// Largest sideways step per tick
+    wobble: f32,
+    /// Cells of water trailing above the drop
+    streak_length: u16,
+}
+
+struct SnowFlake {
+    x: f32,
+    y: f32,
+    speed: f32,
+    size: u8,
+}
+
+struct Star {
+    x: u16,
+    y: u16,
+    brightness: u8,
+    twinkle_speed: f32,
+    twinkle_offset: f32,
+}
+
+/// Star in front of the viewer: x and y span -1.0 to 1.0, z shrinks from 1.0 (far) to 0.0
+struct Star3D {
+    x: f32,
+    y: f32,
+    z: f32,
+}
+
+struct Firefly {
+    x: f32,
+    y: f32,
+    dx: f32,
+    dy: f32,
+    brightness: u8,
+}
+
+struct Bubble {
+    x: f32,
+    y: f32,
+    speed: f32,
+    size: u8,
+    wobble: f32,
+}
+
+struct Confetti {
+    x: f32,
+    y: f32,
+    dx: f32,
+    dy: f32,
+    color: u8, // hue 0-255
+    rotation: f32,
+    rotation_speed: f32,
+    character: char,
+}
+
+struct Particle {
+    x: f32,
+    y: f32,
+    dx: f32,
+    dy: f32,
+    life: u8,
+    max_life: u8,
+    color: Color,
+}
+
+struct PlasmaCell {
+    x: u16,
+    y: u16,
+    value: f32,
+}
+
+struct Leaf {
+    x: f32,
+    y: f32,
     rotation: f32,
     rotation_speed: f32,
     speed: f32,
@@ -1445,6 +4365,37 @@ struct CircuitTrace {
     y: u16,
     direction: u8, // 0=up, 1=right, 2=down, 3=left
     life: u8,
+    path: VecDeque<(u16, u16)>, // Wire laid behind the head, oldest cell first
+}
+
+/// Cells of wire a circuit trace keeps behind its head
+const CIRCUIT_TRACE_LEN: usize = 24;
+
+impl CircuitTrace {
+    fn new(area: Rect, rng: &mut SmallRng) -> Self {
+        use rand::Rng;
+
+        let (x, y) = (rng.gen_range(0..area.width), rng.gen_range(0..area.height));
+        Self {
+            x,
+            y,
+            direction: rng.gen_range(0..4),
+            life: rng.gen_range(50..150),
+            path: VecDeque::from([(x, y)]),
+        }
+    }
+}
+
+/// Traces kept on screen; each draws a whole wire, so a few go a long way
+fn circuit_trace_count(area: Rect, density: u8) -> usize {
+    (area.width as usize * density as usize / 250).max(3)
+}
+
+struct ElectronPacket {
+    trace_idx: Option<usize>, // None until the electron is put on a trace
+    position: f32,            // Index into the trace's path
+    speed: f32,
+    color: (u8, u8, u8),
 }
 
 struct FlowParticle {
@@ -1461,6 +4412,14 @@ struct LissajousCurve {
     delta: f32,
     t: f32,
     color: u8,
+    history: VecDeque<(f32, f32)>, // Earlier pen positions in -1.0..=1.0, oldest first
+}
+
+impl LissajousCurve {
+    /// Pen position at `t`, in -1.0..=1.0 on both axes
+    fn point(&self, t: f32) -> (f32, f32) {
+        ((self.a * t + self.delta).sin(), (self.b * t).sin())
+    }
 }
 
 // New animation structs for v1.1.5
@@ -1514,6 +4473,17 @@ struct PongGame {
     paddle2_y: f32,
     score1: u8,
     score2: u8,
+    trail: VecDeque<(f32, f32)>,  // Earlier ball positions, newest first
+    flashes: Vec<(u16, u16, u8)>, // (x, y, ttl) where the ball hit a paddle
+}
+
+impl PongGame {
+    /// Mark where the ball hit a paddle for a few frames, standing in for the beep
+    fn flash(&mut self, area: Rect) {
+        let x = self.ball_x.clamp(0.0, area.width.saturating_sub(1) as f32);
+        let y = self.ball_y.clamp(0.0, area.height.saturating_sub(1) as f32);
+        self.flashes.push((x as u16, y as u16, 3));
+    }
 }
 
 struct SnakeGame {
@@ -1523,6 +4493,29 @@ struct SnakeGame {
     tick_count: u8,
 }
 
+impl SnakeGame {
+    /// Three segments in the middle of `area`, heading right
+    fn new(area: Rect, rng: &mut SmallRng) -> Self {
+        use rand::Rng;
+
+        let start_x = area.width / 2;
+        let start_y = area.height / 2;
+        Self {
+            segments: vec![
+                (start_x, start_y),
+                (start_x - 1, start_y),
+                (start_x - 2, start_y),
+            ],
+            direction: 1,
+            food: (
+                rng.gen_range(5..area.width - 5),
+                rng.gen_range(3..area.height - 3),
+            ),
+            tick_count: 0,
+        }
+    }
+}
+
 struct TetrisGame {
     pieces: Vec<(u16, u16, u8)>, // x, y, piece_type
     falling_piece: Option<(u16, u16, u8)>,
@@ -1537,6 +4530,41 @@ struct Invader {
     anim_frame: bool,
 }
 
+/// Rows of invaders, `cols` wide, back at the top of the screen
+fn invader_wave(cols: usize) -> Vec<Invader> {
+    let mut invaders = Vec::new();
+    for row in 0..3 {
+        for col in 0..cols {
+            invaders.push(Invader {
+                x: (5 + col * 6) as f32,
+                y: (2 + row * 3) as f32,
+                invader_type: (row as u8) % 3,
+                direction: 1,
+                anim_frame: false,
+            });
+        }
+    }
+    invaders
+}
+
+struct InvaderGame {
+    player_x: f32,
+    bullets: Vec<Bullet>,
+    explosions: Vec<Explosion>,
+}
+
+struct Bullet {
+    x: f32,
+    y: f32,
+    moving_up: bool, // Fired by the player; invader shots fall down
+}
+
+struct Explosion {
+    x: u16,
+    y: u16,
+    timer: u8, // Frames left to show
+}
+
 struct Butterfly {
     x: f32,
     y: f32,
@@ -1652,6 +4680,71 @@ struct FireworkParticle {
     max_life: u8,
 }
 
+/// Seed patterns for "game_of_life", one string per row with 'O' for live cells
+const GOL_PATTERNS: &[(&str, &[&str])] = &[
+    ("glider", &[".O.", "..O", "OOO"]),
+    ("r_pentomino", &[".OO", "OO.", ".O."]),
+    (
+        "gosper_glider_gun",
+        &[
+            "........................O...........",
+            "......................O.O...........",
+            "............OO......OO............OO",
+            "...........O...O....OO............OO",
+            "OO........O.....O...OO..............",
+            "OO........O...O.OO....O.O...........",
+            "..........O.....O.......O...........",
+            "...........O...O....................",
+            "............OO......................",
+        ],
+    ),
+    (
+        "pulsar",
+        &[
+            "..OOO...OOO..",
+            ".............",
+            "O....O.O....O",
+            "O....O.O....O",
+            "O....O.O....O",
+            "..OOO...OOO..",
+            ".............",
+            "..OOO...OOO..",
+            "O....O.O....O",
+            "O....O.O....O",
+            "O....O.O....O",
+            ".............",
+            "..OOO...OOO..",
+        ],
+    ),
+];
+
+/// Stamp a named pattern in the center of the grid, clearing a one-cell margin
+/// around it; "random" and patterns larger than the grid leave it untouched
+fn place_pattern(grid: &mut [GameOfLifeCell], pattern: &str, width: usize, height: usize) {
+    let Some((_, rows)) = GOL_PATTERNS.iter().find(|(name, _)| *name == pattern) else {
+        return;
+    };
+    let pattern_width = rows[0].len();
+    let pattern_height = rows.len();
+    if pattern_width + 2 > width || pattern_height + 2 > height || grid.len() < width * height {
+        return;
+    }
+
+    let left = (width - pattern_width) / 2;
+    let top = (height - pattern_height) / 2;
+    for y in top - 1..=top + pattern_height {
+        for x in left - 1..=left + pattern_width {
+            let alive = rows
+                .get(y.wrapping_sub(top))
+                .and_then(|row| row.as_bytes().get(x.wrapping_sub(left)))
+                == Some(&b'O');
+            let cell = &mut grid[y * width + x];
+            cell.alive = alive;
+            cell.age = 0;
+        }
+    }
+}
+
 struct CubeRotation {
     angle_x: f32,
     angle_y: f32,
@@ -1705,7 +4798,10 @@ impl App {
         }
     }
 
-    fn new(config: Config) -> Self {
+    fn new(mut config: Config) -> Self {
+        apply_accessibility(&mut config);
+        let term_caps = term_capabilities();
+
         // Determine if we should use emoji icons
         let use_emoji = config.use_emoji_icons.unwrap_or_else(|| !has_nerd_fonts());
 
@@ -1713,40 +4809,43 @@ impl App {
             .actions
             .iter()
             .filter(|(_, action_config)| action_config.enabled)
-            .map(|(_id, action_config)| {
-                let icon = if use_emoji {
-                    action_config
-                        .icon_fallback
-                        .clone()
-                        .unwrap_or_else(|| get_icon(action_config).to_string())
-                } else {
-                    action_config.icon.clone()
-                };
-                Action {
-                    icon,
-                    label: action_config.label.clone(),
-                    command: action_config.command.clone(),
-                    args: action_config.args.clone(),
-                    confirm: action_config.confirm,
-                    favorite: action_config.favorite,
-                    shortcut: action_config.shortcut.clone(),
-                }
-            })
+            .map(|(id, action_config)| Action::from_config(id, action_config, use_emoji))
             .collect();
 
-        // Sort: favorites first, then by label
-        actions.sort_by(|a, b| match (b.favorite, a.favorite) {
-            (true, false) => std::cmp::Ordering::Greater,
-            (false, true) => std::cmp::Ordering::Less,
-            _ => a.label.cmp(&b.label),
-        });
+        // Actions listed in another action's sub-menu only appear there
+        let nested: HashSet<String> = actions
+            .iter()
+            .flat_map(|a| a.submenu.iter().filter(move |id| **id != a.id))
+            .cloned()
+            .collect();
+        actions.retain(|a| !nested.contains(&a.id));
+
+        sort_actions(&mut actions, &config.sort_mode, &load_stats());
+
+        // Keep each category together, in order of first appearance, with "Other" last
+        if config.show_categories {
+            let mut categories: Vec<String> = Vec::new();
+            for action in &actions {
+                let name = action.category_name();
+                if name != "Other" && !categories.iter().any(|c| c == name) {
+                    categories.push(name.to_string());
+                }
+            }
+            actions.sort_by_key(|a| {
+                categories
+                    .iter()
+                    .position(|c| c == a.category_name())
+                    .unwrap_or(categories.len())
+            });
+        }
 
-        // Load last executed action and find its index
+        // Start on the last executed action if it should be remembered
         let last_executed = load_last_executed();
-        let selected_index = last_executed
-            .as_ref()
-            .and_then(|label| actions.iter().position(|a| &a.label == label))
-            .unwrap_or(0);
+        let selected_index = if config.remember_last {
+            last_executed_index(&actions).unwrap_or(0)
+        } else {
+            0
+        };
 
         // Detect WM if set to auto
         let mut config = config;
@@ -1754,27 +4853,15 @@ impl App {
             config.wm_type = detect_wm();
         }
 
-        // Update logout command based on detected WM
-        for action in &mut actions {
-            let label_lower = action.label.to_lowercase();
-            if label_lower.contains("logout") || label_lower.contains("exit") {
-                let (cmd, args) = get_logout_command(&config.wm_type);
-                if !cmd.is_empty() {
-                    action.command = cmd;
-                    action.args = args;
-                }
-            }
+        // Resolve "random" to a concrete animation for this run
+        if config.animation.animation_type == "random" {
+            config.animation.animation_type =
+                pick_random_animation(&random_exclude_list(&config.animation));
         }
 
-        // Check for lock command availability and fallback if needed
+        // Update logout and lock commands based on the detected WM and lockers
         for action in &mut actions {
-            if action.label.to_lowercase().contains("lock") {
-                if !check_command_exists(&action.command) {
-                    let (cmd, args) = find_lock_command();
-                    action.command = cmd;
-                    action.args = args;
-                }
-            }
+            action.adapt_to_system(&config.wm_type);
         }
 
         let mut app = Self {
@@ -1789,7 +4876,30 @@ impl App {
             animation_menu_index: 0,
             grace_period_cancelled: false,
             performance_monitor: PerformanceMonitor::new(),
+            ipc_receiver: None,
+            scroll_offset: 0,
+            visible_items: 0,
+            title: String::new(),
+            title_updated: Instant::now(),
+            theme_menu_entries: Vec::new(),
+            theme_menu_original: None,
+            animation_cycled: Instant::now(),
+            running_child: None,
+            retry_attempt: 0,
+            error_log: Vec::new(),
+            inhibitor: None,
+            last_input: Instant::now(),
+            menu_opened_at: Instant::now(),
+            watch: false,
+            term_caps,
+            use_emoji,
+            submenu_choice: None,
+            open_windows: None,
+            debug_log: None,
+            debug_keys: false,
+            logged_state: AppState::Selecting,
         };
+        app.title = app.expanded_title();
 
         // Initialize animation based on terminal size
         let terminal_size = ratatui::layout::Rect::new(0, 0, 80, 24);
@@ -1830,9 +4940,70 @@ impl App {
         self.state = AppState::Selecting;
     }
 
+    fn open_theme_menu(&mut self) {
+        let mut themes = list_available_themes();
+        themes.sort();
+        self.theme_menu_entries = std::iter::once(DEFAULT_THEME_ENTRY.to_string())
+            .chain(themes)
+            .collect();
+
+        // Start on the active theme
+        let theme_index = self
+            .config
+            .theme
+            .as_ref()
+            .and_then(|name| self.theme_menu_entries.iter().position(|t| t == name))
+            .unwrap_or(0);
+        self.theme_menu_original = Some(self.config.clone());
+        self.state = AppState::ThemeMenu { theme_index };
+    }
+
+    /// Leave the theme menu, restoring the config from before the preview
+    fn close_theme_menu(&mut self, size: Rect) {
+        if let Some(original) = self.theme_menu_original.take() {
+            self.config = original;
+            self.animation_state.init(&self.config, size);
+        }
+        self.state = AppState::Selecting;
+    }
+
+    fn move_theme_selection(&mut self, forward: bool, size: Rect) {
+        let AppState::ThemeMenu { theme_index } = self.state else {
+            return;
+        };
+        let count = self.theme_menu_entries.len();
+        if count == 0 {
+            return;
+        }
+        let theme_index = if forward {
+            (theme_index + 1) % count
+        } else {
+            (theme_index + count - 1) % count
+        };
+        self.state = AppState::ThemeMenu { theme_index };
+
+        // Preview on top of the config the menu was opened with
+        if let Some(original) = &self.theme_menu_original {
+            self.config = original.clone();
+            preview_theme(&mut self.config, &self.theme_menu_entries[theme_index]);
+            self.animation_state.init(&self.config, size);
+        }
+    }
+
+    /// Keep the previewed theme and record it in the live config (not saved to disk)
+    fn select_theme(&mut self) {
+        if let AppState::ThemeMenu { theme_index } = self.state {
+            let name = &self.theme_menu_entries[theme_index];
+            self.config.theme = (name != DEFAULT_THEME_ENTRY).then(|| name.clone());
+        }
+        self.theme_menu_original = None;
+        self.state = AppState::Selecting;
+    }
+
     fn next(&mut self) {
         if !self.actions.is_empty() {
             self.selected_index = (self.selected_index + 1) % self.actions.len();
+            self.ensure_selected_visible();
         }
     }
 
@@ -1843,48 +5014,172 @@ impl App {
             } else {
                 self.selected_index = self.actions.len() - 1;
             }
+            self.ensure_selected_visible();
         }
     }
 
-    fn select(&mut self) -> Result<()> {
-        if let Some(action) = self.actions.get(self.selected_index) {
-            // Check if confirmation is needed (explicitly set OR auto-detected critical action)
-            let needs_confirm = action.confirm || action.is_critical();
-
-            if needs_confirm && !matches!(self.state, AppState::Confirming { .. }) {
-                // Enter confirmation mode
-                self.state = AppState::Confirming {
-                    action_index: self.selected_index,
-                };
-                return Ok(());
-            }
-
-            // Check if grace period is enabled for critical actions
-            let needs_grace = self.config.grace_period.enabled
-                && action.is_critical()
-                && self.config.grace_period.duration_secs > 0;
-
-            if needs_grace && !matches!(self.state, AppState::GracePeriod { .. }) {
-                // Enter grace period mode
-                self.state = AppState::GracePeriod {
-                    action_index: self.selected_index,
-                    remaining_secs: self.config.grace_period.duration_secs,
-                    last_tick: std::time::Instant::now(),
-                };
-                self.grace_period_cancelled = false;
-                return Ok(());
-            }
-
-            action.execute()?;
-            self.last_executed = Some(action.label.clone());
-            save_last_executed(&action.label);
+    /// Scroll the vertical list just enough to keep the selected action on screen
+    fn ensure_selected_visible(&mut self) {
+        if self.visible_items == 0 {
+            return;
         }
-        self.should_quit = true;
-        Ok(())
+        let (row, top, row_count) = {
+            let rows = vertical_rows(self);
+            let row = rows
+                .iter()
+                .position(|r| matches!(r, ListRow::Action(i) if *i == self.selected_index))
+                .unwrap_or(0);
+            // Bring the category divider right above the action into view as well
+            let top = if row > 0
+                && self.visible_items > 1
+                && matches!(rows[row - 1], ListRow::Divider(_))
+            {
+                row - 1
+            } else {
+                row
+            };
+            (row, top, rows.len())
+        };
+        if top < self.scroll_offset {
+            self.scroll_offset = top;
+        } else if row >= self.scroll_offset + self.visible_items {
+            self.scroll_offset = row + 1 - self.visible_items;
+        }
+        // Don't leave empty rows at the bottom (e.g. after the terminal grew)
+        let max_offset = row_count.saturating_sub(self.visible_items);
+        self.scroll_offset = self.scroll_offset.min(max_offset);
     }
 
-    fn select_at_index(&mut self, index: usize) -> Result<()> {
-        if index < self.actions.len() {
+    fn select(&mut self) -> Result<()> {
+        if self
+            .actions
+            .get(self.selected_index)
+            .is_some_and(|a| !a.submenu.is_empty())
+            && self.open_submenu(self.selected_index)
+        {
+            return Ok(());
+        }
+        self.start_action(ActionRef::Index(self.selected_index))
+    }
+
+    fn action_at(&self, action_ref: ActionRef) -> Option<&Action> {
+        match action_ref {
+            ActionRef::Index(index) => self.actions.get(index),
+            ActionRef::SubmenuChoice => self.submenu_choice.as_ref(),
+        }
+    }
+
+    /// Show the sub-menu of an action; false if none of its entries exist
+    fn open_submenu(&mut self, parent_index: usize) -> bool {
+        let Some(parent) = self.actions.get(parent_index) else {
+            return false;
+        };
+        let mut sub_actions: Vec<Action> = parent
+            .submenu
+            .iter()
+            .filter_map(|id| {
+                let config = self.config.actions.get(id).filter(|c| c.enabled)?;
+                let mut action = Action::from_config(id, config, self.use_emoji);
+                // Only one level deep: entries run even if they have a sub-menu
+                action.submenu.clear();
+                Some(action)
+            })
+            .collect();
+        if sub_actions.is_empty() {
+            return false;
+        }
+        for action in &mut sub_actions {
+            action.adapt_to_system(&self.config.wm_type);
+        }
+        self.state = AppState::SubMenu {
+            parent_index,
+            sub_actions,
+            selected: 0,
+        };
+        true
+    }
+
+    fn move_submenu_selection(&mut self, forward: bool) {
+        if let AppState::SubMenu {
+            sub_actions,
+            selected,
+            ..
+        } = &mut self.state
+        {
+            let len = sub_actions.len();
+            *selected = if forward {
+                (*selected + 1) % len
+            } else {
+                (*selected + len - 1) % len
+            };
+        }
+    }
+
+    /// Run the sub-menu entry at `index` through confirmation and grace period
+    fn select_submenu_item(&mut self, index: usize) -> Result<()> {
+        let AppState::SubMenu { sub_actions, .. } = &self.state else {
+            return Ok(());
+        };
+        let Some(action) = sub_actions.get(index) else {
+            return Ok(());
+        };
+        self.submenu_choice = Some(action.clone());
+        self.state = AppState::Selecting;
+        self.start_action(ActionRef::SubmenuChoice)
+    }
+
+    /// Confirm, start the grace period for, or run the action `action_ref` points to
+    fn start_action(&mut self, action_ref: ActionRef) -> Result<()> {
+        if let Some(action) = self.action_at(action_ref) {
+            // Check if confirmation is needed (explicitly set OR auto-detected critical action)
+            let needs_confirm = action.confirm || action.is_critical();
+            let ends_session = action.is_logout() || action.is_critical();
+
+            if needs_confirm && !matches!(self.state, AppState::Confirming { .. }) {
+                // Enter confirmation mode
+                self.open_windows =
+                    (self.config.show_open_windows && ends_session).then(count_open_windows);
+                self.state = AppState::Confirming {
+                    action_ref,
+                    focus: if self.config.confirm_default_yes {
+                        ConfirmFocus::Yes
+                    } else {
+                        ConfirmFocus::No
+                    },
+                    confirm_started: self
+                        .config
+                        .confirm_timeout_secs
+                        .map(|_| std::time::Instant::now()),
+                };
+                return Ok(());
+            }
+
+            // Check if grace period is enabled for critical actions
+            let needs_grace = self.config.grace_period.enabled
+                && action.is_critical()
+                && self.config.grace_period.duration_secs > 0;
+
+            if needs_grace && !matches!(self.state, AppState::GracePeriod { .. }) {
+                // Enter grace period mode
+                self.state = AppState::GracePeriod {
+                    action_ref,
+                    remaining_secs: self.config.grace_period.duration_secs,
+                    last_tick: std::time::Instant::now(),
+                };
+                self.grace_period_cancelled = false;
+                self.easter_egg.pick_quote();
+                self.acquire_inhibitor();
+                return Ok(());
+            }
+
+            return self.run_action(action_ref);
+        }
+        self.should_quit = true;
+        Ok(())
+    }
+
+    fn select_at_index(&mut self, index: usize) -> Result<()> {
+        if index < self.actions.len() {
             self.selected_index = index;
             self.select()
         } else {
@@ -1893,9 +5188,9 @@ impl App {
     }
 
     fn confirm_yes(&mut self) -> Result<()> {
-        if let AppState::Confirming { action_index } = self.state {
+        if let AppState::Confirming { action_ref, .. } = self.state {
             // Check if grace period is enabled for critical actions
-            if let Some(action) = self.actions.get(action_index) {
+            if let Some(action) = self.action_at(action_ref) {
                 let needs_grace = self.config.grace_period.enabled
                     && action.is_critical()
                     && self.config.grace_period.duration_secs > 0;
@@ -1903,17 +5198,17 @@ impl App {
                 if needs_grace {
                     // Enter grace period mode instead of executing immediately
                     self.state = AppState::GracePeriod {
-                        action_index,
+                        action_ref,
                         remaining_secs: self.config.grace_period.duration_secs,
                         last_tick: std::time::Instant::now(),
                     };
                     self.grace_period_cancelled = false;
+                    self.easter_egg.pick_quote();
+                    self.acquire_inhibitor();
                     return Ok(());
                 }
 
-                action.execute()?;
-                self.last_executed = Some(action.label.clone());
-                save_last_executed(&action.label);
+                return self.run_action(action_ref);
             }
             self.should_quit = true;
         }
@@ -1924,14 +5219,93 @@ impl App {
         self.state = AppState::Selecting;
     }
 
+    fn toggle_confirm_focus(&mut self) {
+        if let AppState::Confirming { focus, .. } = &mut self.state {
+            *focus = focus.toggle();
+        }
+    }
+
+    /// Menu border color, cycling through the hues in rainbow mode
+    fn menu_border_color(&self) -> Color {
+        if self.easter_egg.rainbow_mode {
+            rainbow_color(self.animation_state.tick, 120.0)
+        } else {
+            parse_color(&self.config.colors.border)
+        }
+    }
+
+    /// Menu title style; only colored in rainbow mode
+    fn menu_title_style(&self) -> Style {
+        if self.easter_egg.rainbow_mode {
+            Style::default().fg(rainbow_color(self.animation_state.tick, 240.0))
+        } else {
+            Style::default()
+        }
+    }
+
+    /// Seconds left before an unanswered confirmation dialog cancels itself
+    fn confirm_remaining_secs(&self) -> Option<u64> {
+        let AppState::Confirming {
+            confirm_started: Some(started),
+            ..
+        } = self.state
+        else {
+            return None;
+        };
+        let timeout = self.config.confirm_timeout_secs?;
+        Some(timeout.saturating_sub(started.elapsed().as_secs()))
+    }
+
+    /// Answer "No" for the user once confirm_timeout_secs has passed
+    fn update_confirm(&mut self) {
+        if self.confirm_remaining_secs() == Some(0) {
+            self.confirm_no();
+        }
+    }
+
+    /// Write the state change since the last call to the debug log, if there is one
+    fn log_state_change(&mut self) {
+        let Some(log) = self.debug_log.as_mut() else {
+            return;
+        };
+        if std::mem::discriminant(&self.logged_state) != std::mem::discriminant(&self.state) {
+            log_state_transition(log, &self.logged_state, &self.state);
+        }
+        self.logged_state = self.state.clone();
+    }
+
+    /// Write a key press to the debug log with --debug-keys
+    fn log_key(&mut self, key: &crossterm::event::KeyEvent) {
+        if let (true, Some(log)) = (self.debug_keys, self.debug_log.as_mut()) {
+            let time = chrono::Local::now().format("%H:%M:%S%.3f");
+            let _ = writeln!(log, "[{}] key {:?} {:?}", time, key.code, key.modifiers);
+        }
+    }
+
     fn cancel_grace_period(&mut self) {
         self.grace_period_cancelled = true;
         self.state = AppState::Selecting;
+        self.release_inhibitor();
+    }
+
+    /// Delay shutdown and sleep requests from elsewhere while the countdown runs
+    fn acquire_inhibitor(&mut self) {
+        if self.config.grace_period.acquire_inhibitor && self.inhibitor.is_none() {
+            self.inhibitor = acquire_inhibitor();
+        }
+    }
+
+    /// Drop the delay lock so the action itself is not held up by it
+    fn release_inhibitor(&mut self) {
+        if let Some(mut child) = self.inhibitor.take() {
+            let _ = child.kill();
+            let _ = child.wait();
+        }
     }
 
     fn update_grace_period(&mut self) -> Result<bool> {
         if let AppState::GracePeriod {
-            action_index,
+            action_ref,
             remaining_secs,
             last_tick,
         } = self.state
@@ -1943,17 +5317,22 @@ impl App {
                 let new_remaining = remaining_secs.saturating_sub(elapsed);
                 if new_remaining == 0 {
                     // Grace period expired, execute the action
-                    if let Some(action) = self.actions.get(action_index) {
-                        action.execute()?;
-                        self.last_executed = Some(action.label.clone());
-                        save_last_executed(&action.label);
+                    if let Some(action) = self.action_at(action_ref) {
+                        if self.config.grace_period.pre_notify {
+                            send_pre_notification(
+                                &self.config.grace_period,
+                                &action.label,
+                                new_remaining,
+                            );
+                        }
                     }
-                    self.should_quit = true;
-                    return Ok(true);
+                    self.release_inhibitor();
+                    self.run_action(action_ref)?;
+                    return Ok(self.should_quit);
                 } else {
                     // Update remaining time
                     self.state = AppState::GracePeriod {
-                        action_index,
+                        action_ref,
                         remaining_secs: new_remaining,
                         last_tick: now,
                     };
@@ -1963,8 +5342,217 @@ impl App {
         Ok(false)
     }
 
+    /// Count a finished action and, with `remember_last`, persist its id for the next start
+    fn remember_executed(&mut self, action: &Action) {
+        self.last_executed = Some(action.id.clone());
+        if self.config.remember_last {
+            save_last_executed(&action.id);
+        }
+        record_execution(&action.label);
+    }
+
+    /// Start an action in the background and show `AppState::Running` until it exits
+    fn run_action(&mut self, action_ref: ActionRef) -> Result<()> {
+        let Some(action) = self.action_at(action_ref).cloned() else {
+            self.should_quit = true;
+            return Ok(());
+        };
+
+        if action.command.is_empty() {
+            self.remember_executed(&action);
+            // In watch mode "Cancel" brings the menu back instead of exiting
+            if self.watch {
+                self.state = AppState::Selecting;
+            } else {
+                self.should_quit = true;
+            }
+            return Ok(());
+        }
+
+        if action.is_logout() && self.retry_attempt == 0 {
+            stop_processes(&self.config.pre_logout_kill);
+        }
+
+        let child = match action.spawn() {
+            Ok(child) => child,
+            Err(e) => return self.fail_action(action_ref, format!("{:#}", e)),
+        };
+        self.state = AppState::Running {
+            action_ref,
+            child_pid: child.id(),
+            started: Instant::now(),
+        };
+        self.running_child = Some(child);
+        Ok(())
+    }
+
+    /// Run a failed action again while it has `max_retries` left, otherwise
+    /// log the error and show it with the option to retry by hand
+    fn fail_action(&mut self, action_ref: ActionRef, message: String) -> Result<()> {
+        let Some(action) = self.action_at(action_ref).cloned() else {
+            return Ok(());
+        };
+        if self.retry_attempt < action.max_retries {
+            self.retry_attempt += 1;
+            return self.run_action(action_ref);
+        }
+
+        self.retry_attempt = 0;
+        let command = action.command_line();
+        self.error_log.push((command, message.clone()));
+        self.state = AppState::CommandError {
+            action_ref,
+            message,
+            focus: ConfirmFocus::Yes,
+            copied: false,
+        };
+        Ok(())
+    }
+
+    /// Choose the focused button of the error dialog: run the action again or
+    /// go back to the menu
+    fn error_choose(&mut self) -> Result<()> {
+        match self.state {
+            AppState::CommandError {
+                action_ref,
+                focus: ConfirmFocus::Yes,
+                ..
+            } => self.run_action(action_ref),
+            _ => {
+                self.state = AppState::Selecting;
+                Ok(())
+            }
+        }
+    }
+
+    /// Put the last error on the clipboard, noting in the dialog if it worked
+    fn copy_last_error(&mut self) {
+        let Some((command, message)) = self.error_log.last() else {
+            return;
+        };
+        let text = format!("{}: {}", command, message);
+        if let AppState::CommandError { copied, .. } = &mut self.state {
+            *copied = copy_to_clipboard(&text);
+        }
+    }
+
+    /// Poll the running command; quit once it succeeded, stop it on timeout
+    fn update_running(&mut self) -> Result<()> {
+        let AppState::Running {
+            action_ref,
+            started,
+            ..
+        } = self.state
+        else {
+            return Ok(());
+        };
+        let Some(action) = self.action_at(action_ref).cloned() else {
+            return Ok(());
+        };
+        let Some(child) = self.running_child.as_mut() else {
+            return Ok(());
+        };
+
+        if let Some(status) = child.try_wait()? {
+            self.running_child = None;
+            if !status.success() {
+                let message = format!("Command {} exited with status: {}", action.command, status);
+                return self.fail_action(action_ref, message);
+            }
+            self.retry_attempt = 0;
+            self.remember_executed(&action);
+            self.should_quit = true;
+            return Ok(());
+        }
+
+        // Killing a locker would unlock the session, so those may take as long as they like
+        let timeout = self.config.command_timeout_secs;
+        if timeout > 0 && !action.is_lock() && started.elapsed().as_secs() >= timeout {
+            let _ = child.kill();
+            let _ = child.wait();
+            let message = format!(
+                "{} did not finish within {}s and was stopped",
+                action.label, timeout
+            );
+            self.running_child = None;
+            return self.fail_action(action_ref, message);
+        }
+        Ok(())
+    }
+
+    /// Seconds left before `auto_trigger` fires, if it is enabled
+    fn idle_remaining_secs(&self) -> Option<u64> {
+        let auto_trigger = &self.config.auto_trigger;
+        if !auto_trigger.enabled {
+            return None;
+        }
+        Some(
+            auto_trigger
+                .idle_secs
+                .saturating_sub(self.last_input.elapsed().as_secs()),
+        )
+    }
+
+    /// Select the `auto_trigger` action once the menu has been idle long enough
+    fn update_auto_trigger(&mut self) -> Result<()> {
+        if !matches!(self.state, AppState::Selecting) || self.idle_remaining_secs() != Some(0) {
+            return Ok(());
+        }
+
+        // Restart the timer so a cancelled confirmation doesn't fire again immediately
+        self.last_input = Instant::now();
+        let action_id = &self.config.auto_trigger.action_id;
+        let Some(index) = self
+            .actions
+            .iter()
+            .position(|a| a.id.eq_ignore_ascii_case(action_id))
+        else {
+            return Ok(());
+        };
+        self.select_at_index(index)
+    }
+
+    /// Seconds left before the menu closes itself, if `auto_quit_secs` is set
+    fn auto_quit_remaining_secs(&self) -> Option<u64> {
+        let timeout = self.config.auto_quit_secs?;
+        let menu_shown = matches!(
+            self.state,
+            AppState::Selecting | AppState::HelpOverlay | AppState::SubMenu { .. }
+        );
+        menu_shown.then(|| timeout.saturating_sub(self.menu_opened_at.elapsed().as_secs()))
+    }
+
+    /// Quit once the menu has gone `auto_quit_secs` without a key press
+    fn update_auto_quit(&mut self) {
+        if self.auto_quit_remaining_secs() == Some(0) {
+            self.menu_opened_at = Instant::now();
+            self.quit();
+        }
+    }
+
     fn quit(&mut self) {
-        self.should_quit = true;
+        // Watch mode keeps the menu up until an action succeeds or Ctrl-C is pressed
+        if !self.watch {
+            self.should_quit = true;
+        }
+    }
+
+    /// Re-expand the title template once `title_update_interval_secs` has passed
+    fn refresh_title(&mut self) {
+        if self.title_updated.elapsed().as_secs() >= self.config.title_update_interval_secs {
+            self.title = self.expanded_title();
+            self.title_updated = Instant::now();
+        }
+    }
+
+    /// The configured title with variables expanded and the watch mode badge
+    fn expanded_title(&self) -> String {
+        let title = expand_title(&self.config.title);
+        if self.watch {
+            format!("{}[watch] ", title)
+        } else {
+            title
+        }
     }
 
     fn check_key(&self, key_str: &str, event: &crossterm::event::KeyEvent) -> bool {
@@ -1975,11 +5563,37 @@ impl App {
         }
     }
 
+    /// Time between two frames: `speed_ms`, or `min_speed_ms` when degraded,
+    /// but never more than `target_fps` frames per second
+    fn frame_interval(&self) -> Duration {
+        let target_frame_time = 1000 / self.config.performance.target_fps.max(1) as u64;
+        // Use min_speed_ms in degraded mode or if adaptive quality is enabled and CPU is high
+        let speed_ms =
+            if self.config.animation.adaptive_quality && self.performance_monitor.is_degraded() {
+                self.config.animation.min_speed_ms
+            } else {
+                self.config.animation.speed_ms
+            };
+        Duration::from_millis(speed_ms.max(target_frame_time))
+    }
+
     fn update_animation(&mut self, area: Rect) {
         if !self.config.animation.enabled || self.config.animation.animation_type == "none" {
             return;
         }
 
+        // Move on to another random animation once the cycle interval has passed
+        if self.config.animation.cycle_animations
+            && self.animation_cycled.elapsed().as_secs()
+                >= self.config.animation.cycle_interval_secs.max(1)
+        {
+            let mut exclude = random_exclude_list(&self.config.animation);
+            exclude.push(&self.config.animation.animation_type);
+            self.config.animation.animation_type = pick_random_animation(&exclude);
+            self.animation_state.init(&self.config, area);
+            self.animation_cycled = Instant::now();
+        }
+
         // Update performance monitor
         self.performance_monitor.update();
 
@@ -1992,172 +5606,188 @@ impl App {
             return;
         }
 
-        let now = std::time::Instant::now();
-        let elapsed = now
-            .duration_since(self.animation_state.last_update)
-            .as_millis() as u64;
+        self.animation_state.tick += 1;
 
-        // Calculate target frame time from FPS setting
-        let target_frame_time = 1000 / self.config.performance.target_fps as u64;
+        let primary = self.config.animation.animation_type.clone();
+        let blend = self.blend_animation().map(str::to_string);
 
-        // Use min_speed_ms in degraded mode or if adaptive quality is enabled and CPU is high
-        let speed_ms =
-            if self.config.animation.adaptive_quality && self.performance_monitor.is_degraded() {
-                self.config.animation.min_speed_ms.max(target_frame_time)
-            } else {
-                self.config.animation.speed_ms.max(target_frame_time)
-            };
+        // Reinitialize if terminal size changed significantly
+        if area.width > 0 && area.height > 0 {
+            // Small resizes keep the current state instead of re-randomizing it
+            let last_area = self.animation_state.last_area;
+            let resized = area.width.abs_diff(last_area.width) > ANIMATION_RESIZE_THRESHOLD
+                || area.height.abs_diff(last_area.height) > ANIMATION_RESIZE_THRESHOLD;
+            let type_changed = self.animation_state.last_type != primary;
 
-        if elapsed < speed_ms {
-            return;
+            if self.animation_needs_init(&primary) || resized || type_changed {
+                self.animation_state.init(&self.config, area);
+            }
+            if let Some(blend) = &blend {
+                let blend_changed = self.animation_state.blend_type != *blend;
+                if self.animation_needs_init(blend) || resized || blend_changed {
+                    self.animation_state.init_layer(blend, &self.config, area);
+                    self.animation_state.blend_type = blend.clone();
+                }
+            }
         }
 
-        // Record frame time for performance monitoring
-        self.performance_monitor.record_frame(elapsed);
-
-        self.animation_state.last_update = now;
-        self.animation_state.tick += 1;
+        self.update_animation_layer(&primary, area);
+        if let Some(blend) = &blend {
+            self.update_animation_layer(blend, area);
+        }
+    }
 
-        // Reinitialize if terminal size changed significantly
-        if area.width > 0 && area.height > 0 {
-            let needs_init = match self.config.animation.animation_type.as_str() {
-                "matrix" => {
-                    self.animation_state.matrix_columns.is_empty()
-                        && self.config.animation.density > 0
-                }
-                "rain" => {
-                    self.animation_state.rain_drops.is_empty() && self.config.animation.density > 0
-                }
-                "snow" => {
-                    self.animation_state.snow_flakes.is_empty() && self.config.animation.density > 0
-                }
-                "stars" => {
-                    self.animation_state.stars.is_empty() && self.config.animation.density > 0
-                }
-                "fireflies" => {
-                    self.animation_state.fireflies.is_empty() && self.config.animation.density > 0
-                }
-                "bubbles" => {
-                    self.animation_state.bubbles.is_empty() && self.config.animation.density > 0
-                }
-                "confetti" => {
-                    self.animation_state.confetti.is_empty() && self.config.animation.density > 0
-                }
-                "wave" => false,
-                "particles" => {
-                    self.animation_state.particles.is_empty() && self.config.animation.density > 0
-                }
-                "digital_rain" => {
-                    self.animation_state.matrix_columns.is_empty()
-                        && self.config.animation.density > 0
-                }
-                "heartbeat" => false,
-                "plasma" => self.animation_state.plasma.is_empty(),
-                "scanlines" => false,
-                "aurora" => false,
-                "autumn" => {
-                    self.animation_state.leaves.is_empty() && self.config.animation.density > 0
-                }
-                "dna" => self.animation_state.dna.is_empty(),
-                "synthwave" => false,
-                "smoke" => {
-                    self.animation_state.smoke.is_empty() && self.config.animation.density > 0
-                }
-                "gradient_flow" => false,
-                "constellation" => {
-                    self.animation_state.nodes.is_empty() && self.config.animation.density > 0
-                }
-                "fish_tank" => {
-                    self.animation_state.fish.is_empty() && self.config.animation.density > 0
-                }
-                "typing_code" => self.animation_state.code_lines.is_empty(),
-                "vortex" => false,
-                "circuit" => {
-                    self.animation_state.traces.is_empty() && self.config.animation.density > 0
-                }
-                "flow_field" => {
-                    self.animation_state.flow_particles.is_empty()
-                        && self.config.animation.density > 0
-                }
-                "morse" => self.animation_state.morse_message.is_empty(),
-                "lissajous" => self.animation_state.lissajous.is_empty(),
-                "game_of_life" => self.animation_state.gol_grid.is_empty(),
-                "matrix_cjk" => {
-                    self.animation_state.matrix_columns.is_empty()
-                        && self.config.animation.density > 0
-                }
-                "fireworks" => self.animation_state.fireworks.is_empty(),
-                "neon_grid" => false,
-                "perlin_flow" => false,
-                "cube_3d" => false,
-                "fractals" => false,
-                // New animations v1.1.5
-                "ocean" => false,
-                "ripple" => false,
-                "fog" => false,
-                "flames" => {
-                    self.animation_state.flames.is_empty() && self.config.animation.density > 0
-                }
-                "sparks" => {
-                    self.animation_state.sparks.is_empty() && self.config.animation.density > 0
-                }
-                "lava_lamp" => {
-                    self.animation_state.lava_blobs.is_empty() && self.config.animation.density > 0
-                }
-                "sun" => false,
-                "galaxy" => false,
-                "meteor_shower" => {
-                    self.animation_state.meteors.is_empty() && self.config.animation.density > 0
-                }
-                "satellite" => false,
-                "pulsar" => false,
-                "pong" => false,
-                "snake" => self.animation_state.snake.segments.is_empty(),
-                "tetris" => false,
-                "invaders" => {
-                    self.animation_state.invaders.is_empty() && self.config.animation.density > 0
-                }
-                "fibonacci" => false,
-                "mandelbrot" => false,
-                "hex_grid" => false,
-                "rose" => false,
-                "butterflies" => {
-                    self.animation_state.butterflies.is_empty() && self.config.animation.density > 0
-                }
-                "spider_web" => {
-                    self.animation_state.web_strands.is_empty() && self.config.animation.density > 0
-                }
-                "vine_growth" => {
-                    self.animation_state.vines.is_empty() && self.config.animation.density > 0
-                }
-                "moss" => self.animation_state.moss.is_empty() && self.config.animation.density > 0,
-                "radar" => false,
-                "binary_clock" => false,
-                "signal" => self.animation_state.signals.is_empty(),
-                "wifi" => false,
-                "paint_splatter" => false,
-                "ink_bleed" => false,
-                "mosaic" => self.animation_state.mosaic_tiles.is_empty(),
-                "stained_glass" => self.animation_state.glass_panels.is_empty(),
-                "hologram" => false,
-                "glitch" => false,
-                "old_film" => false,
-                "thermal" => false,
-                _ => false,
-            };
+    /// Second animation layer from `animation_blend`, unless it repeats the
+    /// primary one or shares its state
+    fn blend_animation(&self) -> Option<&str> {
+        let animation = &self.config.animation;
+        animation.animation_blend.as_deref().filter(|&blend| {
+            blend != "none" && !shares_animation_state(blend, &animation.animation_type)
+        })
+    }
 
-            if needs_init {
-                self.animation_state.init(&self.config, area);
+    /// Whether animation `kind` has no state to update yet
+    fn animation_needs_init(&self, kind: &str) -> bool {
+        match kind {
+            "matrix" | "matrix_vertical" | "matrix_diagonal" => {
+                self.animation_state.matrix_columns.is_empty() && self.config.animation.density > 0
+            }
+            "rain" => {
+                self.animation_state.rain_drops.is_empty() && self.config.animation.density > 0
+            }
+            "rain_window" => {
+                self.animation_state.window_drops.is_empty() && self.config.animation.density > 0
+            }
+            "snow" => {
+                self.animation_state.snow_flakes.is_empty() && self.config.animation.density > 0
+            }
+            "stars" => self.animation_state.stars.is_empty() && self.config.animation.density > 0,
+            "starfield_3d" => {
+                self.animation_state.stars_3d.is_empty() && self.config.animation.density > 0
+            }
+            "fireflies" => {
+                self.animation_state.fireflies.is_empty() && self.config.animation.density > 0
+            }
+            "bubbles" => {
+                self.animation_state.bubbles.is_empty() && self.config.animation.density > 0
+            }
+            "confetti" => {
+                self.animation_state.confetti.is_empty() && self.config.animation.density > 0
+            }
+            "wave" => false,
+            "particles" => {
+                self.animation_state.particles.is_empty() && self.config.animation.density > 0
+            }
+            "digital_rain" => {
+                self.animation_state.matrix_columns.is_empty() && self.config.animation.density > 0
+            }
+            "heartbeat" => false,
+            "plasma" | "plasma_pulse" => self.animation_state.plasma.is_empty(),
+            "scanlines" => false,
+            "aurora" => false,
+            "autumn" => self.animation_state.leaves.is_empty() && self.config.animation.density > 0,
+            "dna" => self.animation_state.dna.is_empty(),
+            "synthwave" => false,
+            "smoke" => self.animation_state.smoke.is_empty() && self.config.animation.density > 0,
+            "gradient_flow" => false,
+            "constellation" => {
+                self.animation_state.nodes.is_empty() && self.config.animation.density > 0
+            }
+            "fish_tank" => {
+                self.animation_state.fish.is_empty() && self.config.animation.density > 0
+            }
+            "typing_code" => self.animation_state.code_lines.is_empty(),
+            "vortex" => false,
+            "circuit" => {
+                self.animation_state.traces.is_empty() && self.config.animation.density > 0
+            }
+            "flow_field" => {
+                self.animation_state.flow_particles.is_empty() && self.config.animation.density > 0
+            }
+            "morse" => self.animation_state.morse_message.is_empty(),
+            "lissajous" => self.animation_state.lissajous.is_empty(),
+            "game_of_life" => self.animation_state.gol_grid.is_empty(),
+            "matrix_cjk" => {
+                self.animation_state.matrix_columns.is_empty() && self.config.animation.density > 0
+            }
+            "fireworks" => self.animation_state.fireworks.is_empty(),
+            "neon_grid" => false,
+            "perlin_flow" => false,
+            "cube_3d" => false,
+            "fractals" => false,
+            // New animations v1.1.5
+            "ocean" => false,
+            "ripple" => false,
+            "fog" => false,
+            "flames" => self.animation_state.flames.is_empty() && self.config.animation.density > 0,
+            "sparks" => self.animation_state.sparks.is_empty() && self.config.animation.density > 0,
+            "lava_lamp" => {
+                self.animation_state.lava_blobs.is_empty() && self.config.animation.density > 0
+            }
+            "sun" => false,
+            "galaxy" => false,
+            "meteor_shower" => {
+                self.animation_state.meteors.is_empty() && self.config.animation.density > 0
+            }
+            "satellite" => false,
+            "pulsar" => false,
+            "pong" => false,
+            "snake" => self.animation_state.snake.segments.is_empty(),
+            "tetris" => false,
+            "invaders" => {
+                self.animation_state.invaders.is_empty() && self.config.animation.density > 0
             }
+            "fibonacci" => false,
+            "mandelbrot" => false,
+            "hex_grid" => false,
+            "rose" => false,
+            "butterflies" => {
+                self.animation_state.butterflies.is_empty() && self.config.animation.density > 0
+            }
+            "spider_web" => {
+                self.animation_state.web_strands.is_empty() && self.config.animation.density > 0
+            }
+            "vine_growth" => {
+                self.animation_state.vines.is_empty() && self.config.animation.density > 0
+            }
+            "moss" => self.animation_state.moss.is_empty() && self.config.animation.density > 0,
+            "radar" => false,
+            "binary_clock" => false,
+            "signal" => self.animation_state.signals.is_empty(),
+            "wifi" => false,
+            "paint_splatter" => false,
+            "ink_bleed" => false,
+            "mosaic" => self.animation_state.mosaic_tiles.is_empty(),
+            "stained_glass" => self.animation_state.glass_panels.is_empty(),
+            "hologram" => false,
+            "glitch" => false,
+            "old_film" => false,
+            "thermal" => false,
+            "typewriter" => false,
+            "clock" => false,
+            "waveform" => self.animation_state.waveform_bars.is_empty(),
+            "northern_lights" => self.animation_state.northern_lights.curtains.is_empty(),
+            "dvd_logo" => self.animation_state.dvd_text.is_empty(),
+            "bouncing_balls" => self.animation_state.balls.is_empty(),
+            "aurora_borealis" => self.animation_state.aurora_curtains.is_empty(),
+            "cellular_automata" => self.animation_state.ca_row.is_empty(),
+            "plasma_globe" => self.animation_state.plasma_arcs.is_empty(),
+            _ => false,
         }
+    }
 
-        // Update based on animation type
-        match self.config.animation.animation_type.as_str() {
-            "matrix" => self.animation_state.update_matrix(area, &self.config),
+    /// Advance animation `kind` by one frame
+    fn update_animation_layer(&mut self, kind: &str, area: Rect) {
+        match kind {
+            "matrix" | "matrix_vertical" | "matrix_diagonal" => {
+                self.animation_state.update_matrix(kind, area, &self.config)
+            }
             "rain" => self.animation_state.update_rain(area, &self.config),
+            "rain_window" => self.animation_state.update_rain_window(area, &self.config),
             "thunder" => self.animation_state.update_thunder(),
             "snow" => self.animation_state.update_snow(area, &self.config),
             "stars" => self.animation_state.update_stars(area, &self.config),
+            "starfield_3d" => self.animation_state.update_starfield_3d(area, &self.config),
             "fireflies" => self.animation_state.update_fireflies(area, &self.config),
             "bubbles" => self.animation_state.update_bubbles(area, &self.config),
             "confetti" => self.animation_state.update_confetti(area, &self.config),
@@ -2166,6 +5796,7 @@ impl App {
             "digital_rain" => self.animation_state.update_digital_rain(area, &self.config),
             "heartbeat" => self.animation_state.update_heartbeat(),
             "plasma" => self.animation_state.update_plasma(),
+            "plasma_pulse" => self.animation_state.update_plasma_pulse(&self.config),
             "scanlines" => self.animation_state.update_scanlines(area),
             "aurora" => self.animation_state.update_aurora(),
             "autumn" => self.animation_state.update_autumn(area, &self.config),
@@ -2181,11 +5812,11 @@ impl App {
             "vortex" => self.animation_state.update_vortex(),
             "circuit" => self.animation_state.update_circuit(area, &self.config),
             "flow_field" => self.animation_state.update_flow_field(area, &self.config),
-            "morse" => self.animation_state.update_morse(),
-            "lissajous" => self.animation_state.update_lissajous(),
-            "game_of_life" => self.animation_state.update_game_of_life(),
-            "matrix_cjk" => self.animation_state.update_matrix(area, &self.config),
-            "fireworks" => self.animation_state.update_fireworks(area),
+            "morse" => self.animation_state.update_morse(&self.config),
+            "lissajous" => self.animation_state.update_lissajous(&self.config),
+            "game_of_life" => self.animation_state.update_game_of_life(&self.config),
+            "matrix_cjk" => self.animation_state.update_matrix(kind, area, &self.config),
+            "fireworks" => self.animation_state.update_fireworks(area, &self.config),
             "neon_grid" => self.animation_state.update_neon_grid(),
             "perlin_flow" => self.animation_state.update_perlin_flow(),
             "cube_3d" => self.animation_state.update_cube_3d(),
@@ -2229,7 +5860,20 @@ impl App {
             "hologram" => self.animation_state.update_hologram(area),
             "glitch" => self.animation_state.update_glitch(),
             "old_film" => self.animation_state.update_old_film(area, &self.config),
-            "thermal" => self.animation_state.update_thermal(area),
+            "thermal" => self.animation_state.update_thermal(area, &self.config),
+            "typewriter" => self.animation_state.update_typewriter(&self.config),
+            "clock" => self.animation_state.update_clock(area, &self.config),
+            "waveform" => self.animation_state.update_waveform(&self.config),
+            "northern_lights" => self.animation_state.update_northern_lights(area),
+            "dvd_logo" => self.animation_state.update_dvd_logo(area, &self.config),
+            "bouncing_balls" => self
+                .animation_state
+                .update_bouncing_balls(area, &self.config),
+            "aurora_borealis" => self.animation_state.update_aurora_borealis(),
+            "cellular_automata" => self
+                .animation_state
+                .update_cellular_automata(area, &self.config),
+            "plasma_globe" => self.animation_state.update_plasma_globe(area, &self.config),
             _ => {}
         }
     }
@@ -2252,9 +5896,37 @@ impl EasterEggState {
                 KeyCode::Char('a'),
             ],
             rainbow_mode: false,
+            typed: String::new(),
+            secret_mode: false,
+            quote_index: 0,
         }
     }
 
+    /// Toggle the secret mode once SECRET_WORD has been typed
+    fn check_secret_word(&mut self, key: KeyCode) -> bool {
+        let KeyCode::Char(c) = key else {
+            self.typed.clear();
+            return false;
+        };
+        self.typed.push(c.to_ascii_lowercase());
+        while self.typed.chars().count() > SECRET_WORD.len() {
+            self.typed.remove(0);
+        }
+
+        if self.typed == SECRET_WORD {
+            self.secret_mode = !self.secret_mode;
+            self.typed.clear();
+            return true;
+        }
+        false
+    }
+
+    /// Choose the quote shown during the next grace period
+    fn pick_quote(&mut self) {
+        use rand::Rng;
+        self.quote_index = rand::thread_rng().gen_range(0..DRAMATIC_QUOTES.len());
+    }
+
     fn check_konami(&mut self, key: KeyCode) -> bool {
         // Normalize character keys to lowercase for consistent matching
         let normalized = match key {
@@ -2276,53 +5948,94 @@ impl EasterEggState {
     }
 }
 
+/// Variables Wayland compositors export to the programs they run, checked
+/// when WAYLAND_DISPLAY is set
+const WAYLAND_COMPOSITOR_MARKERS: &[(&str, &str)] = &[
+    ("HYPRLAND_INSTANCE_SIGNATURE", "hyprland"),
+    ("SWAYSOCK", "sway"),
+    ("NIRI_SOCKET", "niri"),
+    ("RIVER_SOCK", "river"),
+    ("RIVERCTLFD", "river"),
+    ("LABWC_PID", "labwc"),
+];
+
+/// Environment variables set by window managers without their own IPC socket
+const WM_ENV_MARKERS: &[(&str, &str)] = &[
+    ("KWIN_FULL_SESSION", "kwin"),
+    ("KDE_FULL_SESSION", "kwin"),
+    ("OPENBOX_CONFIG_PATH", "openbox"),
+    ("MUTTER_DISABLE_MIPMAPS", "mutter"),
+    ("GNOME_SETUP_DISPLAY", "mutter"),
+    ("FLUXBOX_PID_PATH", "fluxbox"),
+    ("FVWM_USERDIR", "fvwm"),
+];
+
+/// Names in XDG_SESSION_DESKTOP or XDG_CURRENT_DESKTOP and the wm_type they mean
+const WM_DESKTOP_NAMES: &[(&str, &str)] = &[
+    ("hyprland", "hyprland"),
+    ("sway", "sway"),
+    ("i3", "i3"),
+    ("bspwm", "bspwm"),
+    ("awesome", "awesome"),
+    ("kde", "kwin"),
+    ("plasma", "kwin"),
+    ("gnome", "mutter"),
+    ("openbox", "openbox"),
+    ("fluxbox", "fluxbox"),
+    ("fvwm", "fvwm"),
+    ("niri", "niri"),
+    ("river", "river"),
+    ("labwc", "labwc"),
+];
+
 /// Detect the current window manager
 fn detect_wm() -> String {
-    // Check environment variables
-    if let Ok(wayland_display) = std::env::var("WAYLAND_DISPLAY") {
-        if !wayland_display.is_empty() {
-            // It's Wayland - check for specific compositors
-            if let Ok(hyprland) = std::env::var("HYPRLAND_INSTANCE_SIGNATURE") {
-                if !hyprland.is_empty() {
-                    return "hyprland".to_string();
-                }
-            }
-            if let Ok(sway_sock) = std::env::var("SWAYSOCK") {
-                if !sway_sock.is_empty() {
-                    return "sway".to_string();
-                }
-            }
-        }
+    if let Some(wm) = detect_wm_from(|var| std::env::var(var).ok()) {
+        return wm;
+    }
+
+    // labwc only exports LABWC_PID to the programs it starts itself
+    if !process_ids("labwc").is_empty() {
+        return "labwc".to_string();
     }
 
-    // Check XDG_SESSION_DESKTOP
-    if let Ok(desktop) = std::env::var("XDG_SESSION_DESKTOP") {
-        let desktop_lower = desktop.to_lowercase();
-        if desktop_lower.contains("hyprland") {
-            return "hyprland".to_string();
-        } else if desktop_lower.contains("sway") {
-            return "sway".to_string();
-        } else if desktop_lower.contains("i3") {
-            return "i3".to_string();
-        } else if desktop_lower.contains("bspwm") {
-            return "bspwm".to_string();
-        } else if desktop_lower.contains("awesome") {
-            return "awesome".to_string();
+    // Default to hyprland if we can't detect
+    "hyprland".to_string()
+}
+
+/// Window manager told by the environment variables `get` looks up, if any
+fn detect_wm_from(get: impl Fn(&str) -> Option<String>) -> Option<String> {
+    let is_set = |var: &str| get(var).is_some_and(|value| !value.is_empty());
+    let found = |markers: &[(&str, &str)]| {
+        markers
+            .iter()
+            .find(|(var, _)| is_set(var))
+            .map(|(_, wm)| wm.to_string())
+    };
+
+    // It's Wayland - check for specific compositors
+    if is_set("WAYLAND_DISPLAY") {
+        if let Some(wm) = found(WAYLAND_COMPOSITOR_MARKERS) {
+            return Some(wm);
         }
     }
 
-    // Check XDG_CURRENT_DESKTOP
-    if let Ok(current) = std::env::var("XDG_CURRENT_DESKTOP") {
-        let current_lower = current.to_lowercase();
-        if current_lower.contains("hyprland") {
-            return "hyprland".to_string();
-        } else if current_lower.contains("sway") {
-            return "sway".to_string();
-        }
+    // Check variables exported by KWin, Openbox, Mutter, Fluxbox and FVWM
+    if let Some(wm) = found(WM_ENV_MARKERS) {
+        return Some(wm);
     }
 
-    // Default to hyprland if we can't detect
-    "hyprland".to_string()
+    // Check the desktop names, the session's first
+    ["XDG_SESSION_DESKTOP", "XDG_CURRENT_DESKTOP"]
+        .into_iter()
+        .filter_map(&get)
+        .find_map(|desktop| {
+            let desktop = desktop.to_lowercase();
+            WM_DESKTOP_NAMES
+                .iter()
+                .find(|(name, _)| desktop.contains(name))
+                .map(|(_, wm)| wm.to_string())
+        })
 }
 
 /// Get the logout command for a specific window manager
@@ -2335,6 +6048,35 @@ fn get_logout_command(wm: &str) -> (String, Vec<String>) {
             "awesome-client".to_string(),
             vec!["awesome.quit()".to_string()],
         ),
+        "kwin" => (
+            "qdbus".to_string(),
+            vec![
+                "org.kde.ksmserver".to_string(),
+                "/KSMServer".to_string(),
+                "logout".to_string(),
+                "0".to_string(),
+                "0".to_string(),
+                "0".to_string(),
+            ],
+        ),
+        "openbox" => ("openbox".to_string(), vec!["--exit".to_string()]),
+        "mutter" => (
+            "gnome-session-quit".to_string(),
+            vec!["--logout".to_string(), "--no-prompt".to_string()],
+        ),
+        "fluxbox" => ("fluxbox-remote".to_string(), vec!["Exit".to_string()]),
+        "fvwm" => ("FvwmCommand".to_string(), vec!["Quit".to_string()]),
+        "river" => ("riverctl".to_string(), vec!["exit".to_string()]),
+        "labwc" => ("labwc".to_string(), vec!["--exit".to_string()]),
+        "niri" => (
+            "niri".to_string(),
+            vec![
+                "msg".to_string(),
+                "action".to_string(),
+                "quit".to_string(),
+                "--skip-confirmation".to_string(),
+            ],
+        ),
         _ => (
             "hyprctl".to_string(),
             vec!["dispatch".to_string(), "exit".to_string()],
@@ -2343,13 +6085,29 @@ fn get_logout_command(wm: &str) -> (String, Vec<String>) {
 }
 
 impl AnimationState {
+    /// Random numbers for drawing a frame, which can't advance `rng`;
+    /// with a seed they depend only on the seed and the tick
+    fn frame_rng(&self) -> SmallRng {
+        match self.seed {
+            Some(seed) => SmallRng::seed_from_u64(seed.wrapping_add(self.tick)),
+            None => SmallRng::seed_from_u64(rand::random()),
+        }
+    }
+
     fn new() -> Self {
         Self {
             tick: 0,
+            rng: SmallRng::from_entropy(),
+            seed: None,
+            last_area: Rect::default(),
+            last_type: String::new(),
+            blend_type: String::new(),
             matrix_columns: Vec::new(),
             rain_drops: Vec::new(),
+            window_drops: Vec::new(),
             snow_flakes: Vec::new(),
             stars: Vec::new(),
+            stars_3d: Vec::new(),
             fireflies: Vec::new(),
             bubbles: Vec::new(),
             confetti: Vec::new(),
@@ -2370,6 +6128,7 @@ impl AnimationState {
             code_char_idx: 0,
             vortex_angle: 0.0,
             traces: Vec::new(),
+            electrons: Vec::new(),
             flow_particles: Vec::new(),
             morse_message: Vec::new(),
             morse_idx: 0,
@@ -2417,6 +6176,8 @@ impl AnimationState {
                 paddle2_y: 10.0,
                 score1: 0,
                 score2: 0,
+                trail: VecDeque::new(),
+                flashes: Vec::new(),
             },
             snake: SnakeGame {
                 segments: Vec::new(),
@@ -2430,8 +6191,17 @@ impl AnimationState {
                 tick_count: 0,
             },
             invaders: Vec::new(),
+            invader_game: InvaderGame {
+                player_x: 0.0,
+                bullets: Vec::new(),
+                explosions: Vec::new(),
+            },
             fibonacci_angle: 0.0,
-            mandelbrot_offset: (-0.5, 0.0),
+            mandelbrot_real: MANDELBROT_TOUR[0].0,
+            mandelbrot_imag: MANDELBROT_TOUR[0].1,
+            mandelbrot_zoom: MANDELBROT_TOUR[0].2,
+            mandelbrot_tour_idx: 1,
+            mandelbrot_tour_tick: 0.0,
             hex_phase: 0.0,
             rose_angle: 0.0,
             butterflies: Vec::new(),
@@ -2450,25 +6220,56 @@ impl AnimationState {
             glitch_timer: 0,
             scratches: Vec::new(),
             thermal_noise: Vec::new(),
-            last_update: std::time::Instant::now(),
+            thermal_next: Vec::new(),
+            typewriter: TypewriterState::default(),
+            clock: ClockState::default(),
+            waveform_bars: Vec::new(),
+            waveform_peaks: Vec::new(),
+            northern_lights: NorthernLightsState::default(),
+            dvd_x: 0.0,
+            dvd_y: 0.0,
+            dvd_vx: 0.0,
+            dvd_vy: 0.0,
+            dvd_text: String::new(),
+            dvd_color_idx: 0,
+            dvd_trail: VecDeque::new(),
+            balls: Vec::new(),
+            aurora_curtains: Vec::new(),
+            ca_row: Vec::new(),
+            ca_history: VecDeque::new(),
+            plasma_arcs: Vec::new(),
         }
     }
 
     fn init(&mut self, config: &Config, area: Rect) {
+        self.seed = config.animation.seed.filter(|&seed| seed != 0);
+        if let Some(seed) = self.seed {
+            self.rng = SmallRng::seed_from_u64(seed);
+        }
+        self.last_area = area;
+        self.last_type = config.animation.animation_type.clone();
+        self.init_layer(&config.animation.animation_type, config, area);
+    }
+
+    /// Set up animation `kind`, either the primary animation or the `animation_blend` layer
+    fn init_layer(&mut self, kind: &str, config: &Config, area: Rect) {
         use rand::Rng;
-        let mut rng = rand::thread_rng();
+        let rng = &mut self.rng;
 
-        match config.animation.animation_type.as_str() {
-            "matrix" => {
+        match kind {
+            "matrix" | "matrix_vertical" | "matrix_diagonal" => {
+                let direction = matrix_direction(kind, &config.animation);
+                let (lanes, length) = matrix_axes(direction, area);
                 let density = config.animation.density as usize;
-                let count = ((area.width as usize * density) / 100).max(1);
+                let count = ((lanes as usize * density) / 100).max(1);
                 self.matrix_columns.clear();
                 for _ in 0..count {
                     self.matrix_columns.push(MatrixColumn {
-                        x: rng.gen_range(0..area.width),
-                        y: rng.gen_range(0.0..area.height as f32),
+                        x: rng.gen_range(0..lanes),
+                        y: rng.gen_range(0.0..length.max(1.0)),
                         speed: rng.gen_range(0.2..1.5),
                         char_idx: rng.gen_range(0..MATRIX_CHARS.len()),
+                        vx: matrix_vx(direction),
                     });
                 }
             }
@@ -2485,6 +6286,20 @@ impl AnimationState {
                     });
                 }
             }
+            "rain_window" => {
+                let density = config.animation.density as usize;
+                let count = ((area.width as usize * density) / 60).max(3);
+                self.window_drops.clear();
+                for _ in 0..count {
+                    self.window_drops.push(WindowDrop {
+                        x: rng.gen_range(0.0..area.width as f32),
+                        y: rng.gen_range(0.0..area.height as f32),
+                        speed: rng.gen_range(0.1..0.6),
+                        wobble: rng.gen_range(0.0..0.3),
+                        streak_length: 0,
+                    });
+                }
+            }
             "snow" => {
                 let density = config.animation.density as usize;
                 let count = ((area.width as usize * area.height as usize * density) / 500).max(10);
@@ -2512,6 +6327,18 @@ impl AnimationState {
                     });
                 }
             }
+            "starfield_3d" => {
+                let density = config.animation.density as usize;
+                let count = ((area.width as usize * area.height as usize * density) / 1000).max(20);
+                self.stars_3d.clear();
+                for _ in 0..count {
+                    self.stars_3d.push(Star3D {
+                        x: rng.gen_range(-1.0..1.0),
+                        y: rng.gen_range(-1.0..1.0),
+                        z: rng.gen_range(0.05..1.0),
+                    });
+                }
+            }
             "fireflies" => {
                 let density = config.animation.density as usize;
                 let count = ((area.width as usize * area.height as usize * density) / 800).max(3);
@@ -2591,13 +6418,15 @@ impl AnimationState {
                         y: rng.gen_range(0.0..area.height as f32),
                         speed: rng.gen_range(0.2..1.5),
                         char_idx: rng.gen_range(0..16), // 0-F for hex
+                        vx: 0.0,
                     });
                 }
             }
             "heartbeat" => {
                 self.heartbeat_phase = 0.0;
             }
-            "plasma" => {
+            "plasma" | "plasma_pulse" => {
+                self.heartbeat_phase = 0.0;
                 self.plasma.clear();
                 for y in 0..area.height {
                     for x in 0..area.width {
@@ -2721,17 +6550,12 @@ impl AnimationState {
                 self.vortex_angle = 0.0;
             }
             "circuit" => {
-                let density = config.animation.density as usize;
-                let count = ((area.width as usize * density) / 25).max(3);
+                let count = circuit_trace_count(area, config.animation.density);
                 self.traces.clear();
                 for _ in 0..count {
-                    self.traces.push(CircuitTrace {
-                        x: rng.gen_range(0..area.width),
-                        y: rng.gen_range(0..area.height),
-                        direction: rng.gen_range(0..4),
-                        life: rng.gen_range(50..150),
-                    });
+                    self.traces.push(CircuitTrace::new(area, rng));
                 }
+                self.electrons.clear();
             }
             "flow_field" => {
                 let density = config.animation.density as usize;
@@ -2748,7 +6572,13 @@ impl AnimationState {
                 }
             }
             "morse" => {
-                self.morse_message = "I'd just like to interject for a moment. What you're refering to as Linux, is in fact, GNU/Linux, or as I've recently taken to calling it, GNU plus Linux. Linux is not an operating system unto itself, but rather another free component of a fully functioning GNU system made useful by the GNU corelibs, shell utilities and vital system components comprising a full OS as defined by POSIX. Many computer users run a modified version of the GNU system every day, without realizing it. Through a peculiar turn of events, the version of GNU which is widely used today is often called Linux, and many of its users are not aware that it is basically the GNU system, developed by the GNU Project. There really is a Linux, and these people are using it, but it is just a part of the system they use. Linux is the kernel: the program in the system that allocates the machine's resources to the other programs that you run. The kernel is an essential part of an operating system, but useless by itself; it can only function in the context of a complete operating system. Linux is normally used in combination with the GNU operating system: the whole system is basically GNU with Linux added, or GNU/Linux. All the so-called Linux distributions are really distributions of GNU/Linux!".chars().collect();
+                self.morse_message = config
+                    .animation
+                    .morse_text
+                    .as_deref()
+                    .unwrap_or(DEFAULT_MORSE_TEXT)
+                    .chars()
+                    .collect();
                 self.morse_idx = 0;
                 self.morse_timer = 0;
                 self.morse_display = String::new();
@@ -2762,6 +6592,7 @@ impl AnimationState {
                         delta: i as f32 * 0.5,
                         t: 0.0,
                         color: (i * 50) as u8,
+                        history: VecDeque::new(),
                     });
                 }
             }
@@ -2769,17 +6600,24 @@ impl AnimationState {
                 self.gol_width = area.width as usize;
                 self.gol_height = area.height as usize;
                 self.gol_grid.clear();
+                let density = config.animation.gol_density.clamp(0.0, 1.0);
                 for y in 0..self.gol_height {
                     for x in 0..self.gol_width {
                         self.gol_grid.push(GameOfLifeCell {
                             x,
                             y,
-                            alive: rng.gen_bool(0.3),
+                            alive: rng.gen_bool(density),
                             next_state: false,
                             age: 0,
                         });
                     }
                 }
+                place_pattern(
+                    &mut self.gol_grid,
+                    &config.animation.gol_pattern,
+                    self.gol_width,
+                    self.gol_height,
+                );
             }
             "matrix_cjk" => {
                 let density = config.animation.density as usize;
@@ -2791,6 +6629,7 @@ impl AnimationState {
                         y: rng.gen_range(0.0..area.height as f32),
                         speed: rng.gen_range(0.2..1.5),
                         char_idx: rng.gen_range(0..256),
+                        vx: 0.0,
                     });
                 }
             }
@@ -2918,24 +6757,12 @@ impl AnimationState {
                     paddle2_y: area.height as f32 / 2.0,
                     score1: 0,
                     score2: 0,
+                    trail: VecDeque::new(),
+                    flashes: Vec::new(),
                 };
             }
             "snake" => {
-                let start_x = area.width / 2;
-                let start_y = area.height / 2;
-                self.snake = SnakeGame {
-                    segments: vec![
-                        (start_x, start_y),
-                        (start_x - 1, start_y),
-                        (start_x - 2, start_y),
-                    ],
-                    direction: 1,
-                    food: (
-                        rng.gen_range(5..area.width - 5),
-                        rng.gen_range(3..area.height - 3),
-                    ),
-                    tick_count: 0,
-                };
+                self.snake = SnakeGame::new(area, rng);
             }
             "tetris" => {
                 self.tetris = TetrisGame {
@@ -2946,26 +6773,23 @@ impl AnimationState {
             }
             "invaders" => {
                 let density = config.animation.density as usize;
-                let rows = 3;
-                let cols = (density / 10).max(3).min(8);
-                self.invaders.clear();
-                for row in 0..rows {
-                    for col in 0..cols {
-                        self.invaders.push(Invader {
-                            x: (5 + col * 6) as f32,
-                            y: (2 + row * 3) as f32,
-                            invader_type: (row as u8) % 3,
-                            direction: 1,
-                            anim_frame: false,
-                        });
-                    }
-                }
+                self.invaders = invader_wave((density / 10).clamp(3, 8));
+                self.invader_game = InvaderGame {
+                    player_x: area.width as f32 / 2.0,
+                    bullets: Vec::new(),
+                    explosions: Vec::new(),
+                };
             }
             "fibonacci" => {
                 self.fibonacci_angle = 0.0;
             }
             "mandelbrot" => {
-                self.mandelbrot_offset = (-0.5, 0.0);
+                let (real, imag, zoom, _) = MANDELBROT_TOUR[0];
+                self.mandelbrot_real = real;
+                self.mandelbrot_imag = imag;
+                self.mandelbrot_zoom = zoom;
+                self.mandelbrot_tour_idx = 1;
+                self.mandelbrot_tour_tick = 0.0;
             }
             "hex_grid" => {
                 self.hex_phase = 0.0;
@@ -3138,24 +6962,131 @@ impl AnimationState {
                 }
             }
             "thermal" => {
-                self.thermal_noise.clear();
                 let count = (area.width * area.height) as usize;
-                self.thermal_noise = (0..count).map(|_| rng.gen_range(0..255)).collect();
+                self.thermal_noise = (0..count).map(|_| rng.gen_range(0.0..1.0)).collect();
+                self.thermal_next.clear();
+            }
+            "typewriter" => {
+                self.typewriter = TypewriterState::default();
+            }
+            "clock" => {
+                self.clock = ClockState {
+                    x: area.width / 3,
+                    y: area.height / 3,
+                    dx: 1,
+                    dy: 1,
+                    ..ClockState::default()
+                };
+            }
+            "waveform" => {
+                let count = config.animation.waveform_bars.clamp(5, 80) as usize;
+                self.waveform_bars = vec![0.0; count];
+                self.waveform_peaks = vec![0.0; count];
+            }
+            "northern_lights" => {
+                // Green, purple and blue bands
+                const HUES: [f32; 3] = [130.0, 280.0, 215.0];
+                let count = 3 + config.animation.density as usize / 20;
+                let width = area.width.max(1) as f32;
+                let height = area.height.max(1) as f32;
+                self.northern_lights = NorthernLightsState {
+                    curtains: (0..count)
+                        .map(|i| Curtain {
+                            x: rng.gen_range(0.0..width),
+                            width: rng.gen_range(4.0..12.0),
+                            hue: HUES[i % HUES.len()] + rng.gen_range(-15.0..15.0),
+                            offset: rng.gen_range(0.0..height / 3.0),
+                        })
+                        .collect(),
+                    phase: 0.0,
+                };
+            }
+            "dvd_logo" => {
+                self.dvd_text = config
+                    .animation
+                    .dvd_text
+                    .clone()
+                    .unwrap_or_else(|| "rexit".to_string());
+                let text_width = self.dvd_text.chars().count() as f32;
+                self.dvd_x = rng.gen_range(0.0..=(area.width as f32 - text_width).max(0.0));
+                self.dvd_y = rng.gen_range(0.0..=(area.height as f32 - 1.0).max(0.0));
+                // Cells are about twice as tall as wide
+                self.dvd_vx = if rng.gen_bool(0.5) { 0.5 } else { -0.5 };
+                self.dvd_vy = if rng.gen_bool(0.5) { 0.25 } else { -0.25 };
+                self.dvd_color_idx = rng.gen_range(0..DVD_COLORS.len());
+                self.dvd_trail.clear();
+            }
+            "bouncing_balls" => {
+                let count = config.animation.ball_count.clamp(1, 20) as usize;
+                let first_color = rng.gen_range(0..DVD_COLORS.len());
+                self.balls = (0..count)
+                    .map(|i| {
+                        let radius: f32 = rng.gen_range(1.0..3.0);
+                        let max_x = (area.width as f32 - radius).max(radius);
+                        let max_y = (area.height as f32 - radius * 0.5).max(radius * 0.5);
+                        let vx: f32 = rng.gen_range(0.3..1.0);
+                        let vy: f32 = rng.gen_range(0.3..1.0);
+                        Ball {
+                            x: rng.gen_range(radius..=max_x),
+                            y: rng.gen_range(radius * 0.5..=max_y),
+                            vx: if rng.gen_bool(0.5) { vx } else { -vx },
+                            vy: if rng.gen_bool(0.5) { vy } else { -vy },
+                            radius,
+                            color: DVD_COLORS[(first_color + i) % DVD_COLORS.len()],
+                        }
+                    })
+                    .collect();
+            }
+            "aurora_borealis" => {
+                let count = config.animation.aurora_curtain_count.clamp(1, 20) as usize;
+                let slot = area.width.max(1) as f32 / count as f32;
+                let height = area.height.max(4);
+                self.aurora_curtains = (0..count)
+                    .map(|i| AuroraCurtain {
+                        base_x: (i as f32 + rng.gen_range(0.2..0.8)) * slot,
+                        height: rng.gen_range(height / 3..=height * 4 / 5),
+                        phase_offset: rng.gen_range(0.0..std::f32::consts::TAU),
+                        color_hue: rng.gen_range(0.0..360.0),
+                        sway_speed: rng.gen_range(0.02..0.06),
+                    })
+                    .collect();
+            }
+            "cellular_automata" => {
+                // A single live cell shows each rule's pattern most clearly
+                let mut row = vec![false; area.width.max(1) as usize];
+                let middle = row.len() / 2;
+                row[middle] = true;
+                self.ca_history = VecDeque::from([row.clone()]);
+                self.ca_row = row;
+            }
+            "plasma_globe" => {
+                let count = config.animation.plasma_globe_arcs.clamp(1, 20) as usize;
+                let start = rng.gen_range(0.0..std::f32::consts::TAU);
+                self.plasma_arcs = (0..count)
+                    .map(|i| {
+                        let angle = start + i as f32 / count as f32 * std::f32::consts::TAU;
+                        PlasmaArc::new(angle, rng)
+                    })
+                    .collect();
             }
             _ => {}
         }
     }
 
-    fn update_matrix(&mut self, area: Rect, config: &Config) {
+    fn update_matrix(&mut self, kind: &str, area: Rect, config: &Config) {
         use rand::Rng;
-        let mut rng = rand::thread_rng();
+        let rng = &mut self.rng;
 
+        let direction = matrix_direction(kind, &config.animation);
+        let (lanes, length) = matrix_axes(direction, area);
+        let vx = matrix_vx(direction);
         for col in &mut self.matrix_columns {
             col.y += col.speed;
-            if col.y >= area.height as f32 {
+            if col.y >= length {
                 col.y = 0.0;
-                col.x = rng.gen_range(0..area.width);
+                col.x = rng.gen_range(0..lanes);
                 col.speed = rng.gen_range(0.2..1.5);
+                col.vx = vx;
             }
             if self.tick.is_multiple_of(3) {
                 col.char_idx = rng.gen_range(0..MATRIX_CHARS.len());
@@ -3163,20 +7094,21 @@ impl AnimationState {
         }
 
         // Randomly respawn columns to maintain density
-        let target_count = ((area.width as usize * config.animation.density as usize) / 100).max(1);
+        let target_count = ((lanes as usize * config.animation.density as usize) / 100).max(1);
         while self.matrix_columns.len() < target_count {
             self.matrix_columns.push(MatrixColumn {
-                x: rng.gen_range(0..area.width),
+                x: rng.gen_range(0..lanes),
                 y: 0.0,
                 speed: rng.gen_range(0.2..1.5),
                 char_idx: rng.gen_range(0..MATRIX_CHARS.len()),
+                vx,
             });
         }
     }
 
     fn update_rain(&mut self, area: Rect, config: &Config) {
         use rand::Rng;
-        let mut rng = rand::thread_rng();
+        let rng = &mut self.rng;
 
         for drop in &mut self.rain_drops {
             drop.y += drop.speed;
@@ -3197,9 +7129,51 @@ impl AnimationState {
         }
     }
 
+    fn update_rain_window(&mut self, area: Rect, config: &Config) {
+        use rand::Rng;
+        let rng = &mut self.rng;
+        let bottom = area.height.saturating_sub(1) as f32;
+
+        for drop in &mut self.window_drops {
+            if drop.y >= bottom {
+                // Pooled: the streak drains away before the drop starts over
+                drop.y = bottom;
+                if drop.streak_length > 0 {
+                    drop.streak_length -= 1;
+                } else {
+                    *drop = WindowDrop {
+                        x: rng.gen_range(0.0..area.width as f32),
+                        y: rng.gen_range(-3.0..0.0),
+                        speed: rng.gen_range(0.1..0.6),
+                        wobble: rng.gen_range(0.0..0.3),
+                        streak_length: 0,
+                    };
+                }
+                continue;
+            }
+
+            drop.speed = (drop.speed + 0.02).min(1.5);
+            drop.y += drop.speed;
+            drop.x = (drop.x + rng.gen_range(-drop.wobble..=drop.wobble))
+                .clamp(0.0, area.width.saturating_sub(1) as f32);
+            drop.streak_length = (drop.speed * 4.0) as u16;
+        }
+
+        let target_count = ((area.width as usize * config.animation.density as usize) / 60).max(3);
+        while self.window_drops.len() < target_count {
+            self.window_drops.push(WindowDrop {
+                x: rng.gen_range(0.0..area.width as f32),
+                y: rng.gen_range(-10.0..0.0),
+                speed: rng.gen_range(0.1..0.6),
+                wobble: rng.gen_range(0.0..0.3),
+                streak_length: 0,
+            });
+        }
+    }
+
     fn update_thunder(&mut self) {
         use rand::Rng;
-        let mut rng = rand::thread_rng();
+        let rng = &mut self.rng;
 
         // Random thunder flashes
         if self.thunder_flash > 0 {
@@ -3211,7 +7185,7 @@ impl AnimationState {
 
     fn update_snow(&mut self, area: Rect, config: &Config) {
         use rand::Rng;
-        let mut rng = rand::thread_rng();
+        let rng = &mut self.rng;
 
         for flake in &mut self.snow_flakes {
             flake.y += flake.speed;
@@ -3242,9 +7216,39 @@ impl AnimationState {
         }
     }
 
+    fn update_starfield_3d(&mut self, area: Rect, config: &Config) {
+        use rand::Rng;
+        let rng = &mut self.rng;
+        let speed = config.animation.starfield_speed;
+
+        for star in &mut self.stars_3d {
+            // Closer stars rush in faster
+            star.z -= speed * (1.0 + (1.0 - star.z) * 2.0);
+
+            let past_viewer = star.z <= 0.01;
+            if past_viewer || starfield_project(star, area).is_none() {
+                *star = Star3D {
+                    x: rng.gen_range(-1.0..1.0),
+                    y: rng.gen_range(-1.0..1.0),
+                    z: rng.gen_range(0.8..1.0),
+                };
+            }
+        }
+
+        let density = config.animation.density as usize;
+        let target_count = ((area.width as usize * area.height as usize * density) / 1000).max(20);
+        while self.stars_3d.len() < target_count {
+            self.stars_3d.push(Star3D {
+                x: rng.gen_range(-1.0..1.0),
+                y: rng.gen_range(-1.0..1.0),
+                z: rng.gen_range(0.8..1.0),
+            });
+        }
+    }
+
     fn update_stars(&mut self, area: Rect, config: &Config) {
         use rand::Rng;
-        let mut rng = rand::thread_rng();
+        let rng = &mut self.rng;
 
         for star in &mut self.stars {
             let twinkle = (self.tick as f32 * star.twinkle_speed + star.twinkle_offset).sin();
@@ -3289,7 +7293,7 @@ impl AnimationState {
 
     fn update_digital_rain(&mut self, area: Rect, config: &Config) {
         use rand::Rng;
-        let mut rng = rand::thread_rng();
+        let rng = &mut self.rng;
 
         for col in &mut self.matrix_columns {
             col.y += col.speed;
@@ -3310,6 +7314,7 @@ impl AnimationState {
                 y: 0.0,
                 speed: rng.gen_range(0.2..1.5),
                 char_idx: rng.gen_range(0..16),
+                vx: 0.0,
             });
         }
     }
@@ -3320,7 +7325,7 @@ impl AnimationState {
 
     fn update_plasma(&mut self) {
         use rand::Rng;
-        let mut rng = rand::thread_rng();
+        let rng = &mut self.rng;
 
         for cell in &mut self.plasma {
             // Update plasma value based on position and time
@@ -3338,6 +7343,12 @@ impl AnimationState {
         }
     }
 
+    /// Plasma field whose brightness follows the heartbeat
+    fn update_plasma_pulse(&mut self, config: &Config) {
+        self.update_plasma();
+        self.heartbeat_phase += config.animation.plasma_pulse_speed;
+    }
+
     fn update_scanlines(&mut self, area: Rect) {
         self.scanline_pos = (self.scanline_pos + 1) % area.height;
     }
@@ -3348,7 +7359,7 @@ impl AnimationState {
 
     fn update_autumn(&mut self, area: Rect, config: &Config) {
         use rand::Rng;
-        let mut rng = rand::thread_rng();
+        let rng = &mut self.rng;
 
         for leaf in &mut self.leaves {
             leaf.y += leaf.speed;
@@ -3378,7 +7389,7 @@ impl AnimationState {
 
     fn update_dna(&mut self, area: Rect, _config: &Config) {
         use rand::Rng;
-        let mut rng = rand::thread_rng();
+        let rng = &mut self.rng;
 
         // Move DNA up
         for base in &mut self.dna {
@@ -3411,7 +7422,7 @@ impl AnimationState {
 
     fn update_smoke(&mut self, area: Rect, config: &Config) {
         use rand::Rng;
-        let mut rng = rand::thread_rng();
+        let rng = &mut self.rng;
 
         for particle in &mut self.smoke {
             particle.x += particle.dx;
@@ -3464,7 +7475,7 @@ impl AnimationState {
 
     fn update_fish_tank(&mut self, area: Rect, _config: &Config) {
         use rand::Rng;
-        let mut rng = rand::thread_rng();
+        let rng = &mut self.rng;
 
         for fish in &mut self.fish {
             // Move fish
@@ -3524,9 +7535,9 @@ impl AnimationState {
 
     fn update_circuit(&mut self, area: Rect, config: &Config) {
         use rand::Rng;
-        let mut rng = rand::thread_rng();
+        let rng = &mut self.rng;
 
-        for trace in &mut self.traces {
+        for (idx, trace) in self.traces.iter_mut().enumerate() {
             // Move in current direction
             match trace.direction {
                 0 => trace.y = trace.y.saturating_sub(1),
@@ -3534,6 +7545,20 @@ impl AnimationState {
                 2 => trace.y = (trace.y + 1).min(area.height - 1),
                 _ => trace.x = trace.x.saturating_sub(1),
             }
+            if trace.path.back() != Some(&(trace.x, trace.y)) {
+                trace.path.push_back((trace.x, trace.y));
+            }
+            if trace.path.len() > CIRCUIT_TRACE_LEN {
+                trace.path.pop_front();
+                // Electrons stay on their cell while the wire shifts under them
+                for electron in self
+                    .electrons
+                    .iter_mut()
+                    .filter(|e| e.trace_idx == Some(idx))
+                {
+                    electron.position = (electron.position - 1.0).max(0.0);
+                }
+            }
 
             trace.life = trace.life.saturating_sub(1);
 
@@ -3543,24 +7568,79 @@ impl AnimationState {
             }
         }
 
-        // Remove dead traces
+        // Remove dead traces, moving their electrons to another one
+        let mut new_index = Vec::with_capacity(self.traces.len());
+        let mut alive = 0;
+        for trace in &self.traces {
+            new_index.push((trace.life > 0).then_some(alive));
+            alive += (trace.life > 0) as usize;
+        }
         self.traces.retain(|t| t.life > 0);
+        for electron in &mut self.electrons {
+            electron.trace_idx = electron
+                .trace_idx
+                .and_then(|idx| new_index.get(idx).copied().flatten());
+        }
 
         // Spawn new traces
-        let target_count = ((area.width as usize * config.animation.density as usize) / 25).max(3);
+        let target_count = circuit_trace_count(area, config.animation.density);
         while self.traces.len() < target_count {
-            self.traces.push(CircuitTrace {
-                x: rng.gen_range(0..area.width),
-                y: rng.gen_range(0..area.height),
-                direction: rng.gen_range(0..4),
-                life: rng.gen_range(50..150),
+            self.traces.push(CircuitTrace::new(area, rng));
+        }
+
+        let electron_count = config.animation.electron_count as usize;
+        self.electrons.truncate(electron_count);
+        while self.electrons.len() < electron_count {
+            let colors = [(255, 255, 120), (120, 255, 255), (255, 255, 255)];
+            self.electrons.push(ElectronPacket {
+                trace_idx: None,
+                position: 0.0,
+                speed: rng.gen_range(0.3..0.8),
+                color: colors[rng.gen_range(0..colors.len())],
             });
         }
+
+        for electron in &mut self.electrons {
+            let Some(trace_idx) = electron.trace_idx else {
+                electron.trace_idx = Some(rng.gen_range(0..self.traces.len()));
+                electron.position = 0.0;
+                continue;
+            };
+
+            electron.position += electron.speed;
+            let path = &self.traces[trace_idx].path;
+            if electron.position < (path.len() - 1) as f32 {
+                continue;
+            }
+
+            // At the end of the wire, hop onto a trace passing next to it or start over
+            let (end_x, end_y) = path[path.len() - 1];
+            let touching: Vec<(usize, usize)> = self
+                .traces
+                .iter()
+                .enumerate()
+                .filter(|&(idx, _)| idx != trace_idx)
+                .filter_map(|(idx, trace)| {
+                    trace
+                        .path
+                        .iter()
+                        .position(|&(x, y)| x.abs_diff(end_x) <= 1 && y.abs_diff(end_y) <= 1)
+                        .map(|cell| (idx, cell))
+                })
+                .collect();
+            if touching.is_empty() {
+                electron.position = 0.0;
+            } else {
+                let (idx, cell) = touching[rng.gen_range(0..touching.len())];
+                electron.trace_idx = Some(idx);
+                electron.position = cell as f32;
+            }
+        }
     }
 
     fn update_flow_field(&mut self, area: Rect, config: &Config) {
         use rand::Rng;
-        let mut rng = rand::thread_rng();
+        let rng = &mut self.rng;
 
         for particle in &mut self.flow_particles {
             // Calculate flow field vector at position
@@ -3609,8 +7689,13 @@ impl AnimationState {
         }
     }
 
-    fn update_morse(&mut self) {
+    fn update_morse(&mut self, config: &Config) {
         // Morse timing: dot=1, dash=3, space=7 (in animation ticks)
+        let ticks = |base: u8| match config.animation.morse_speed.as_str() {
+            "slow" => base * 3,
+            "fast" => (base / 2).max(1),
+            _ => base,
+        };
         if self.morse_timer > 0 {
             self.morse_timer -= 1;
             return;
@@ -3664,11 +7749,11 @@ impl AnimationState {
             // Build display string
             if ch == ' ' {
                 self.morse_display.push_str("  ");
-                self.morse_timer = 7; // Space between words
+                self.morse_timer = ticks(7); // Space between words
             } else {
                 self.morse_display.push_str(morse);
                 self.morse_display.push(' ');
-                self.morse_timer = 3; // Space between letters
+                self.morse_timer = ticks(3); // Space between letters
             }
 
             // Keep display manageable
@@ -3677,23 +7762,28 @@ impl AnimationState {
             }
 
             self.morse_idx += 1;
-        } else {
+        } else if config.animation.morse_loop {
             // Loop back to start
             self.morse_idx = 0;
             self.morse_display.clear();
-            self.morse_timer = 14; // Pause before restarting
+            self.morse_timer = ticks(14); // Pause before restarting
         }
     }
 
-    fn update_lissajous(&mut self) {
+    fn update_lissajous(&mut self, config: &Config) {
+        let trail = config.animation.lissajous_trail as usize;
         for curve in &mut self.lissajous {
+            curve.history.push_back(curve.point(curve.t));
+            while curve.history.len() > trail {
+                curve.history.pop_front();
+            }
             curve.t += 0.05;
         }
     }
 
-    fn update_game_of_life(&mut self) {
+    fn update_game_of_life(&mut self, config: &Config) {
         use rand::Rng;
-        let mut rng = rand::thread_rng();
+        let rng = &mut self.rng;
         let width = self.gol_width;
         let height = self.gol_height;
 
@@ -3739,15 +7829,19 @@ impl AnimationState {
         // Apply next state and update age
         for cell in &mut self.gol_grid {
             if cell.alive && cell.next_state {
-                cell.age = cell.age + 1;
+                // Still lifes live forever, so stop counting at 255
+                cell.age = cell.age.saturating_add(1);
             } else if cell.next_state {
                 cell.age = 0;
             }
             cell.alive = cell.next_state;
         }
 
-        // Randomly seed new cells to prevent stagnation
-        if self.tick.is_multiple_of(100) && rng.gen_bool(0.3) {
+        // Randomly seed new cells to prevent stagnation; patterns are left to evolve
+        if config.animation.gol_pattern == "random"
+            && self.tick.is_multiple_of(100)
+            && rng.gen_bool(0.3)
+        {
             for _ in 0..10 {
                 let x = rng.gen_range(0..width);
                 let y = rng.gen_range(0..height);
@@ -3760,7 +7854,7 @@ impl AnimationState {
 
     fn update_bubbles(&mut self, area: Rect, config: &Config) {
         use rand::Rng;
-        let mut rng = rand::thread_rng();
+        let rng = &mut self.rng;
 
         for bubble in &mut self.bubbles {
             bubble.y -= bubble.speed;
@@ -3789,7 +7883,7 @@ impl AnimationState {
 
     fn update_confetti(&mut self, area: Rect, config: &Config) {
         use rand::Rng;
-        let mut rng = rand::thread_rng();
+        let rng = &mut self.rng;
 
         for conf in &mut self.confetti {
             conf.x += conf.dx;
@@ -3830,7 +7924,7 @@ impl AnimationState {
 
     fn update_particles(&mut self, area: Rect, config: &Config) {
         use rand::Rng;
-        let mut rng = rand::thread_rng();
+        let rng = &mut self.rng;
 
         for particle in &mut self.particles {
             particle.x += particle.dx;
@@ -3881,9 +7975,11 @@ impl AnimationState {
         }
     }
 
-    fn update_fireworks(&mut self, area: Rect) {
+    fn update_fireworks(&mut self, area: Rect, config: &Config) {
         use rand::Rng;
-        let mut rng = rand::thread_rng();
+        use std::f32::consts::TAU;
+        let rng = &mut self.rng;
+        let style = config.animation.firework_style.as_str();
 
         for firework in &mut self.fireworks {
             if !firework.exploded {
@@ -3895,17 +7991,35 @@ impl AnimationState {
                 // Explode when velocity slows down
                 if firework.vy >= -0.5 {
                     firework.exploded = true;
+                    let style = match style {
+                        "random" => ["star", "ring", "weeping_willow"][rng.gen_range(0..3)],
+                        style => style,
+                    };
                     let particle_count = rng.gen_range(15..30);
-                    for _ in 0..particle_count {
-                        let angle = rng.gen_range(0.0..std::f32::consts::TAU);
-                        let speed = rng.gen_range(0.5..2.5);
+                    for i in 0..particle_count {
+                        // Rings space their sparks evenly and fade them out
+                        // together; willows are slow and droop from the start
+                        let (angle, speed, droop, lives) = match style {
+                            "ring" => {
+                                let angle = i as f32 / particle_count as f32 * TAU;
+                                (angle, 1.5, 0.0, 30..=30)
+                            }
+                            "weeping_willow" => {
+                                let speed = rng.gen_range(0.3..1.2);
+                                (rng.gen_range(0.0..TAU), speed, 0.3, 40..=60)
+                            }
+                            _ => {
+                                let speed = rng.gen_range(0.5..2.5);
+                                (rng.gen_range(0.0..TAU), speed, 0.0, 30..=45)
+                            }
+                        };
                         firework.particles.push(FireworkParticle {
                             x: firework.x,
                             y: firework.y,
                             vx: angle.cos() * speed,
-                            vy: angle.sin() * speed,
-                            life: rng.gen_range(30..60),
-                            max_life: 60,
+                            vy: angle.sin() * speed + droop,
+                            life: rng.gen_range(lives.clone()),
+                            max_life: *lives.end(),
                         });
                     }
                 }
@@ -3985,14 +8099,14 @@ impl AnimationState {
 
     fn update_fog(&mut self) {
         use rand::Rng;
-        let mut rng = rand::thread_rng();
+        let rng = &mut self.rng;
         self.fog_density += rng.gen_range(-0.02..0.02);
         self.fog_density = self.fog_density.clamp(0.3, 0.8);
     }
 
     fn update_flames(&mut self, area: Rect, config: &Config) {
         use rand::Rng;
-        let mut rng = rand::thread_rng();
+        let rng = &mut self.rng;
 
         for flame in &mut self.flames {
             flame.height += rng.gen_range(-0.5..0.5);
@@ -4014,7 +8128,7 @@ impl AnimationState {
 
     fn update_sparks(&mut self, area: Rect, config: &Config) {
         use rand::Rng;
-        let mut rng = rand::thread_rng();
+        let rng = &mut self.rng;
 
         for spark in &mut self.sparks {
             spark.x += spark.vx;
@@ -4041,7 +8155,7 @@ impl AnimationState {
 
     fn update_lava_lamp(&mut self, area: Rect, _config: &Config) {
         use rand::Rng;
-        let mut rng = rand::thread_rng();
+        let rng = &mut self.rng;
 
         for blob in &mut self.lava_blobs {
             blob.y += blob.dy;
@@ -4070,7 +8184,7 @@ impl AnimationState {
 
     fn update_meteor_shower(&mut self, area: Rect, config: &Config) {
         use rand::Rng;
-        let mut rng = rand::thread_rng();
+        let rng = &mut self.rng;
 
         for meteor in &mut self.meteors {
             meteor.x += meteor.vx;
@@ -4109,7 +8223,17 @@ impl AnimationState {
         self.pulsar_angle += 0.05;
     }
 
-    fn update_pong(&mut self, area: Rect, _config: &Config) {
+    fn update_pong(&mut self, area: Rect, config: &Config) {
+        for flash in &mut self.pong.flashes {
+            flash.2 -= 1;
+        }
+        self.pong.flashes.retain(|&(_, _, ttl)| ttl > 0);
+
+        let ball = (self.pong.ball_x, self.pong.ball_y);
+        let trail_len = config.animation.pong_ball_trail as usize;
+        self.pong.trail.push_front(ball);
+        self.pong.trail.truncate(trail_len);
+
         // Move ball
         self.pong.ball_x += self.pong.ball_vx;
         self.pong.ball_y += self.pong.ball_vy;
@@ -4123,21 +8247,25 @@ impl AnimationState {
         if self.pong.ball_x <= 2.0 {
             if (self.pong.ball_y - self.pong.paddle1_y).abs() < 3.0 {
                 self.pong.ball_vx = -self.pong.ball_vx;
+                self.pong.flash(area);
             } else {
                 // Reset ball
                 self.pong.ball_x = area.width as f32 / 2.0;
                 self.pong.ball_y = area.height as f32 / 2.0;
                 self.pong.ball_vx = 0.8;
+                self.pong.trail.clear();
                 self.pong.score2 += 1;
             }
         } else if self.pong.ball_x >= area.width as f32 - 2.0 {
             if (self.pong.ball_y - self.pong.paddle2_y).abs() < 3.0 {
                 self.pong.ball_vx = -self.pong.ball_vx;
+                self.pong.flash(area);
             } else {
                 // Reset ball
                 self.pong.ball_x = area.width as f32 / 2.0;
                 self.pong.ball_y = area.height as f32 / 2.0;
                 self.pong.ball_vx = -0.8;
+                self.pong.trail.clear();
                 self.pong.score1 += 1;
             }
         }
@@ -4160,9 +8288,9 @@ impl AnimationState {
         self.pong.paddle2_y = self.pong.paddle2_y.clamp(2.0, area.height as f32 - 3.0);
     }
 
-    fn update_snake(&mut self, area: Rect, _config: &Config) {
+    fn update_snake(&mut self, area: Rect, config: &Config) {
         use rand::Rng;
-        let mut rng = rand::thread_rng();
+        let rng = &mut self.rng;
 
         self.snake.tick_count += 1;
         if self.snake.tick_count < 3 {
@@ -4170,14 +8298,25 @@ impl AnimationState {
         }
         self.snake.tick_count = 0;
 
-        // Move snake
+        // Move snake, wrapping around the edges
         let head = self.snake.segments[0];
-        let new_head = match self.snake.direction {
-            0 => (head.0, head.1.saturating_sub(1)),
-            1 => ((head.0 + 1).min(area.width - 1), head.1),
-            2 => (head.0, (head.1 + 1).min(area.height - 1)),
-            _ => (head.0.saturating_sub(1), head.1),
+        let (dx, dy) = match self.snake.direction {
+            0 => (0, -1),
+            1 => (1, 0),
+            2 => (0, 1),
+            _ => (-1, 0),
         };
+        let new_head = (
+            (head.0 as i32 + dx).rem_euclid(area.width as i32) as u16,
+            (head.1 as i32 + dy).rem_euclid(area.height as i32) as u16,
+        );
+
+        // The tail moves out of the way, so only the rest of the body counts
+        let body = &self.snake.segments[..self.snake.segments.len() - 1];
+        if config.animation.snake_collision && body.contains(&new_head) {
+            self.snake = SnakeGame::new(area, rng);
+            return;
+        }
 
         // Check food collision
         if new_head == self.snake.food {
@@ -4191,15 +8330,15 @@ impl AnimationState {
             self.snake.segments.insert(0, new_head);
         }
 
-        // Random direction change occasionally
+        // Random direction change occasionally, never straight back into the neck
         if rng.gen_bool(0.1) {
-            self.snake.direction = rng.gen_range(0..4);
+            self.snake.direction = (self.snake.direction + rng.gen_range(3..6)) % 4;
         }
     }
 
     fn update_tetris(&mut self, area: Rect, _config: &Config) {
         use rand::Rng;
-        let mut rng = rand::thread_rng();
+        let rng = &mut self.rng;
 
         self.tetris.tick_count += 1;
         if self.tetris.tick_count < 5 {
@@ -4218,7 +8357,9 @@ impl AnimationState {
         }
     }
 
-    fn update_invaders(&mut self, area: Rect, _config: &Config) {
+    fn update_invaders(&mut self, area: Rect, config: &Config) {
+        use rand::Rng;
+
         let move_down = self.invaders.iter().any(|i| {
             (i.x <= 2.0 && i.direction < 0) || (i.x >= area.width as f32 - 3.0 && i.direction > 0)
         });
@@ -4230,25 +8371,91 @@ impl AnimationState {
             } else {
                 invader.x += invader.direction as f32 * 0.5;
             }
-            if self.tick % 10 == 0 {
+            if self.tick.is_multiple_of(10) {
                 invader.anim_frame = !invader.anim_frame;
             }
         }
 
-        // Reset if all went off bottom
-        if self.invaders.iter().all(|i| i.y > area.height as f32) {
-            self.invaders.clear();
-            for row in 0..3 {
-                for col in 0..5 {
-                    self.invaders.push(Invader {
-                        x: (5 + col * 6) as f32,
-                        y: (2 + row * 3) as f32,
-                        invader_type: (row as u8) % 3,
-                        direction: 1,
-                        anim_frame: false,
+        let game = &mut self.invader_game;
+        let player_y = area.height.saturating_sub(2) as f32;
+
+        // Steer the player under the lowest invader, like the pong paddles
+        if let Some(target) = self
+            .invaders
+            .iter()
+            .max_by(|a, b| a.y.total_cmp(&b.y))
+            .map(|i| i.x)
+        {
+            if game.player_x < target - 0.5 {
+                game.player_x += 0.4;
+            } else if game.player_x > target + 0.5 {
+                game.player_x -= 0.4;
+            }
+        }
+        let max_x = area.width.saturating_sub(1) as f32;
+        game.player_x = game.player_x.clamp(0.0, max_x);
+
+        // One player shot in flight at a time; invaders fire back now and then
+        let player_shooting = game.bullets.iter().any(|b| b.moving_up);
+        if !player_shooting && self.rng.gen_bool(0.1) {
+            game.bullets.push(Bullet {
+                x: game.player_x,
+                y: player_y - 1.0,
+                moving_up: true,
+            });
+        }
+        if !self.invaders.is_empty() && self.rng.gen_bool(0.03) {
+            let shooter = &self.invaders[self.rng.gen_range(0..self.invaders.len())];
+            game.bullets.push(Bullet {
+                x: shooter.x,
+                y: shooter.y + 1.0,
+                moving_up: false,
+            });
+        }
+
+        for bullet in &mut game.bullets {
+            bullet.y += if bullet.moving_up { -0.7 } else { 0.4 };
+        }
+
+        // Player shots take out the invader they hit; invaders are two cells wide
+        let invaders = &mut self.invaders;
+        game.bullets.retain(|bullet| {
+            if bullet.moving_up {
+                let hit = invaders.iter().position(|i| {
+                    bullet.x >= i.x && bullet.x < i.x + 2.0 && (bullet.y - i.y).abs() < 1.0
+                });
+                if let Some(index) = hit {
+                    let invader = invaders.remove(index);
+                    game.explosions.push(Explosion {
+                        x: invader.x as u16,
+                        y: invader.y as u16,
+                        timer: 6,
                     });
+                    return false;
                 }
+                bullet.y >= 0.0
+            } else if bullet.y >= player_y && (bullet.x - game.player_x).abs() < 1.0 {
+                game.explosions.push(Explosion {
+                    x: game.player_x as u16,
+                    y: player_y as u16,
+                    timer: 6,
+                });
+                false
+            } else {
+                bullet.y < area.height as f32
             }
+        });
+
+        for explosion in &mut game.explosions {
+            explosion.timer -= 1;
+        }
+        game.explosions.retain(|e| e.timer > 0);
+
+        // A new wave once all were shot or went off the bottom
+        if self.invaders.iter().all(|i| i.y > area.height as f32) {
+            let density = config.animation.density as usize;
+            self.invaders = invader_wave((density / 10).clamp(3, 8));
+            game.bullets.clear();
         }
     }
 
@@ -4257,7 +8464,20 @@ impl AnimationState {
     }
 
     fn update_mandelbrot(&mut self) {
-        self.mandelbrot_offset.0 += 0.001;
+        let (real, imag, zoom, duration) = MANDELBROT_TOUR[self.mandelbrot_tour_idx];
+
+        // Cover the remaining distance evenly over the remaining ticks; zoom
+        // geometrically so magnification feels steady at every depth
+        let step = 1.0 / (duration - self.mandelbrot_tour_tick).max(1.0);
+        self.mandelbrot_real += (real - self.mandelbrot_real) * step;
+        self.mandelbrot_imag += (imag - self.mandelbrot_imag) * step;
+        self.mandelbrot_zoom *= (zoom / self.mandelbrot_zoom).powf(step);
+
+        self.mandelbrot_tour_tick += 1.0;
+        if self.mandelbrot_tour_tick >= duration {
+            self.mandelbrot_tour_idx = (self.mandelbrot_tour_idx + 1) % MANDELBROT_TOUR.len();
+            self.mandelbrot_tour_tick = 0.0;
+        }
     }
 
     fn update_hex_grid(&mut self) {
@@ -4270,7 +8490,7 @@ impl AnimationState {
 
     fn update_butterflies(&mut self, area: Rect, _config: &Config) {
         use rand::Rng;
-        let mut rng = rand::thread_rng();
+        let rng = &mut self.rng;
 
         for butterfly in &mut self.butterflies {
             // Move towards target
@@ -4280,7 +8500,7 @@ impl AnimationState {
             butterfly.y += dy * 0.02;
 
             // Flap wings
-            if self.tick % 5 == 0 {
+            if self.tick.is_multiple_of(5) {
                 butterfly.wing_open = !butterfly.wing_open;
             }
 
@@ -4294,7 +8514,7 @@ impl AnimationState {
 
     fn update_spider_web(&mut self) {
         use rand::Rng;
-        let mut rng = rand::thread_rng();
+        let rng = &mut self.rng;
         for strand in &mut self.web_strands {
             strand.vibration = rng.gen_range(-0.1..0.1);
         }
@@ -4302,21 +8522,20 @@ impl AnimationState {
 
     fn update_vine_growth(&mut self, area: Rect, _config: &Config) {
         use rand::Rng;
-        let mut rng = rand::thread_rng();
+        let rng = &mut self.rng;
 
         for vine in &mut self.vines {
             vine.length = (vine.length as f32 + vine.growth_rate) as u16;
             if vine.length >= vine.max_length {
                 vine.length = 0;
                 vine.x = rng.gen_range(0.0..area.width as f32);
-                vine._y = area.height as f32;
             }
         }
     }
 
     fn update_moss(&mut self, area: Rect, config: &Config) {
         use rand::Rng;
-        let mut rng = rand::thread_rng();
+        let rng = &mut self.rng;
 
         for cell in &mut self.moss {
             cell.age = cell.age.saturating_add(1);
@@ -4344,9 +8563,9 @@ impl AnimationState {
         self.binary_time += 1;
     }
 
-    fn update_signal(&mut self, _area: Rect, _config: &Config) {
+    fn update_signal(&mut self, area: Rect, _config: &Config) {
         use rand::Rng;
-        let mut rng = rand::thread_rng();
+        let rng = &mut self.rng;
 
         for signal in &mut self.signals {
             signal.radius += 0.3;
@@ -4354,10 +8573,11 @@ impl AnimationState {
 
         self.signals.retain(|s| s.radius < s.max_radius);
 
-        if self.signals.len() < 2 && rng.gen_bool(0.05) {
+        // Waves start at least 5 columns from either edge
+        if self.signals.len() < 2 && area.width > 10 && rng.gen_bool(0.05) {
             self.signals.push(SignalWave {
-                x: rng.gen_range(5.._area.width - 5),
-                y: _area.height / 2,
+                x: rng.gen_range(5..area.width - 5),
+                y: area.height / 2,
                 radius: 0.0,
                 max_radius: rng.gen_range(10.0..30.0),
                 amplitude: rng.gen_range(100..200),
@@ -4382,7 +8602,7 @@ impl AnimationState {
 
     fn update_paint_splatter(&mut self, area: Rect, config: &Config) {
         use rand::Rng;
-        let mut rng = rand::thread_rng();
+        let rng = &mut self.rng;
 
         for splatter in &mut self.splatters {
             splatter.age = splatter.age.saturating_add(1);
@@ -4408,7 +8628,7 @@ impl AnimationState {
 
     fn update_ink_bleed(&mut self, area: Rect, _config: &Config) {
         use rand::Rng;
-        let mut rng = rand::thread_rng();
+        let rng = &mut self.rng;
 
         for drop in &mut self.ink_drops {
             drop.radius += 0.1;
@@ -4433,7 +8653,7 @@ impl AnimationState {
 
     fn update_mosaic(&mut self) {
         use rand::Rng;
-        let mut rng = rand::thread_rng();
+        let rng = &mut self.rng;
 
         for tile in &mut self.mosaic_tiles {
             if tile.changing {
@@ -4455,7 +8675,7 @@ impl AnimationState {
 
     fn update_stained_glass(&mut self) {
         use rand::Rng;
-        let mut rng = rand::thread_rng();
+        let rng = &mut self.rng;
 
         for panel in &mut self.glass_panels {
             panel.light_intensity =
@@ -4469,29 +8689,370 @@ impl AnimationState {
 
     fn update_glitch(&mut self) {
         use rand::Rng;
-        let mut rng = rand::thread_rng();
+        let rng = &mut self.rng;
         self.glitch_timer = rng.gen_range(0..10);
     }
 
     fn update_old_film(&mut self, area: Rect, _config: &Config) {
         use rand::Rng;
-        let mut rng = rand::thread_rng();
+        let rng = &mut self.rng;
+
+        for scratch in &mut self.scratches {
+            scratch.visible = rng.gen_bool(0.3);
+            scratch.y = (scratch.y + 1) % area.height;
+        }
+    }
+
+    /// One step of the heat equation, plus a little noise and the odd hot spot
+    fn update_thermal(&mut self, area: Rect, config: &Config) {
+        use rand::Rng;
+        let rng = &mut self.rng;
+        let (width, height) = (area.width as usize, area.height as usize);
+        let count = width * height;
+        if self.thermal_noise.len() != count {
+            self.thermal_noise = (0..count).map(|_| rng.gen_range(0.0..1.0)).collect();
+        }
+        if count == 0 {
+            return;
+        }
+
+        // Edges reflect heat: a missing neighbor counts as the cell itself
+        let alpha = config.animation.thermal_alpha;
+        let u = &self.thermal_noise;
+        self.thermal_next.clear();
+        self.thermal_next.extend((0..count).map(|i| {
+            let (x, y) = (i % width, i / width);
+            let left = if x > 0 { u[i - 1] } else { u[i] };
+            let right = if x + 1 < width { u[i + 1] } else { u[i] };
+            let up = if y > 0 { u[i - width] } else { u[i] };
+            let down = if y + 1 < height { u[i + width] } else { u[i] };
+            let laplacian = left + right + up + down - 4.0 * u[i];
+            (u[i] + alpha * laplacian + rng.gen_range(-0.02..0.02)).clamp(0.0, 1.0)
+        }));
+
+        // Without new heat or cold spots the field would settle into one flat color
+        if rng.gen_bool(0.2) {
+            let (cx, cy) = (rng.gen_range(0..width), rng.gen_range(0..height));
+            let radius = rng.gen_range(2..5usize);
+            let target = if rng.gen_bool(0.5) { 1.0 } else { 0.0 };
+            for y in cy.saturating_sub(radius)..(cy + radius + 1).min(height) {
+                for x in cx.saturating_sub(radius)..(cx + radius + 1).min(width) {
+                    if x.abs_diff(cx).pow(2) + y.abs_diff(cy).pow(2) <= radius * radius {
+                        self.thermal_next[y * width + x] = target;
+                    }
+                }
+            }
+        }
+        std::mem::swap(&mut self.thermal_noise, &mut self.thermal_next);
+    }
+
+    fn update_dvd_logo(&mut self, area: Rect, config: &Config) {
+        if config.animation.dvd_trail {
+            self.dvd_trail.push_front((self.dvd_x, self.dvd_y));
+            self.dvd_trail.truncate(DVD_TRAIL_LEN);
+        } else {
+            self.dvd_trail.clear();
+        }
+
+        let max_x = (area.width as f32 - self.dvd_text.chars().count() as f32).max(0.0);
+        let max_y = (area.height as f32 - 1.0).max(0.0);
+        self.dvd_x += self.dvd_vx;
+        self.dvd_y += self.dvd_vy;
+
+        let mut bounced = false;
+        if self.dvd_x <= 0.0 || self.dvd_x >= max_x {
+            self.dvd_x = self.dvd_x.clamp(0.0, max_x);
+            self.dvd_vx = if self.dvd_x <= 0.0 {
+                self.dvd_vx.abs()
+            } else {
+                -self.dvd_vx.abs()
+            };
+            bounced = true;
+        }
+        if self.dvd_y <= 0.0 || self.dvd_y >= max_y {
+            self.dvd_y = self.dvd_y.clamp(0.0, max_y);
+            self.dvd_vy = if self.dvd_y <= 0.0 {
+                self.dvd_vy.abs()
+            } else {
+                -self.dvd_vy.abs()
+            };
+            bounced = true;
+        }
+        if bounced {
+            self.dvd_color_idx = (self.dvd_color_idx + 1) % DVD_COLORS.len();
+        }
+    }
+
+    fn update_bouncing_balls(&mut self, area: Rect, config: &Config) {
+        let gravity = config.animation.ball_gravity;
+        let width = area.width as f32;
+        let height = area.height as f32;
+
+        for ball in &mut self.balls {
+            ball.vy += gravity;
+            ball.x += ball.vx;
+            // Cells are about twice as tall as wide
+            ball.y += ball.vy * 0.5;
+
+            let (min_x, max_x) = (ball.radius, (width - ball.radius).max(ball.radius));
+            let ry = ball.radius * 0.5;
+            let (min_y, max_y) = (ry, (height - ry).max(ry));
+            if ball.x <= min_x || ball.x >= max_x {
+                ball.x = ball.x.clamp(min_x, max_x);
+                ball.vx = if ball.x <= min_x {
+                    ball.vx.abs()
+                } else {
+                    -ball.vx.abs()
+                };
+            }
+            if ball.y <= min_y {
+                ball.y = min_y;
+                ball.vy = ball.vy.abs();
+            } else if ball.y >= max_y {
+                ball.y = max_y;
+                // With gravity every floor bounce loses some energy
+                let damping = if gravity > 0.0 { 0.85 } else { 1.0 };
+                ball.vy = -ball.vy.abs() * damping;
+            }
+        }
+
+        // Equal masses colliding elastically just trade velocities
+        for i in 0..self.balls.len() {
+            let (head, tail) = self.balls.split_at_mut(i + 1);
+            let a = &mut head[i];
+            for b in tail {
+                let dx = b.x - a.x;
+                let dy = (b.y - a.y) * 2.0;
+                let reach = a.radius + b.radius;
+                let approaching = (b.vx - a.vx) * dx + (b.vy - a.vy) * dy < 0.0;
+                if dx * dx + dy * dy < reach * reach && approaching {
+                    std::mem::swap(&mut a.vx, &mut b.vx);
+                    std::mem::swap(&mut a.vy, &mut b.vy);
+                }
+            }
+        }
+    }
+
+    /// Add the next generation at the bottom, scrolling older ones up
+    fn update_cellular_automata(&mut self, area: Rect, config: &Config) {
+        use rand::Rng;
+
+        let rule = config.animation.ca_rule;
+        let wrap = config.animation.ca_wrap;
+        let width = self.ca_row.len();
+        if width == 0 {
+            return;
+        }
+
+        let cell = |i: isize| -> bool {
+            if wrap {
+                self.ca_row[i.rem_euclid(width as isize) as usize]
+            } else {
+                i >= 0 && (i as usize) < width && self.ca_row[i as usize]
+            }
+        };
+        let next: Vec<bool> = (0..width as isize)
+            .map(|i| {
+                let pattern = (cell(i - 1) as u8) << 2 | (cell(i) as u8) << 1 | cell(i + 1) as u8;
+                rule >> pattern & 1 == 1
+            })
+            .collect();
+
+        // Rules that die out or freeze would leave a still screen; start over
+        // from random cells instead
+        self.ca_row = if next.iter().all(|&alive| !alive) || next == self.ca_row {
+            (0..width).map(|_| self.rng.gen_bool(0.5)).collect()
+        } else {
+            next
+        };
+
+        self.ca_history.push_back(self.ca_row.clone());
+        while self.ca_history.len() > area.height as usize {
+            self.ca_history.pop_front();
+        }
+    }
+
+    fn update_plasma_globe(&mut self, area: Rect, config: &Config) {
+        use rand::Rng;
+        use std::f32::consts::{PI, TAU};
+        let rng = &mut self.rng;
+
+        let radius = plasma_globe_radius(area, &config.animation);
+        for arc in &mut self.plasma_arcs {
+            arc.angle = (arc.angle + rng.gen_range(-0.06..0.06)).rem_euclid(TAU);
+            arc.length = (arc.length + 0.15).min(1.0);
+            if let Some(life) = &mut arc.branch_life {
+                *life = life.saturating_sub(1);
+            }
+
+            // A new jagged path every frame makes the arc flicker; the bend
+            // is largest halfway out, like a discharge curving through gas
+            let steps = (radius * arc.length * 1.5).max(2.0) as usize;
+            let bend = rng.gen_range(-0.4..0.4);
+            arc.segments = (0..=steps)
+                .map(|i| {
+                    let t = i as f32 / steps as f32;
+                    let theta = arc.angle + bend * (t * PI).sin() + rng.gen_range(-0.15..0.15);
+                    let r = t * radius * arc.length;
+                    (theta.cos() * r, theta.sin() * r)
+                })
+                .collect();
+        }
+
+        // Branches split off now and then and fade out again
+        let main_arcs = self.plasma_arcs.iter().filter(|a| a.branch_life.is_none());
+        if main_arcs.count() > 0 && self.plasma_arcs.len() < 40 && rng.gen_bool(0.05) {
+            let parent = &self.plasma_arcs[rng.gen_range(0..self.plasma_arcs.len())];
+            let mut branch = PlasmaArc::new(parent.angle + rng.gen_range(-0.5..0.5), rng);
+            branch.color = parent.color;
+            branch.length = 0.3;
+            branch.branch_life = Some(rng.gen_range(8..25));
+            self.plasma_arcs.push(branch);
+        }
+
+        // Branches that drift into another arc merge with it
+        let angles: Vec<(f32, bool)> = self
+            .plasma_arcs
+            .iter()
+            .map(|a| (a.angle, a.branch_life.is_none()))
+            .collect();
+        let mut index = 0;
+        self.plasma_arcs.retain(|arc| {
+            let me = index;
+            index += 1;
+            let Some(life) = arc.branch_life else {
+                return true;
+            };
+            let merged = angles.iter().enumerate().any(|(other, &(angle, main))| {
+                let apart = (arc.angle - angle).rem_euclid(TAU);
+                other != me && main && apart.min(TAU - apart) < 0.05
+            });
+            life > 0 && !merged
+        });
+    }
+
+    fn update_aurora_borealis(&mut self) {
+        for curtain in &mut self.aurora_curtains {
+            curtain.color_hue = (curtain.color_hue + 0.3).rem_euclid(360.0);
+        }
+    }
+
+    fn update_northern_lights(&mut self, area: Rect) {
+        use rand::Rng;
+        let rng = &mut self.rng;
+
+        let width = area.width.max(1) as f32;
+        let lights = &mut self.northern_lights;
+        lights.phase += 0.05;
+        for curtain in &mut lights.curtains {
+            curtain.x = (curtain.x + rng.gen_range(-0.1..=0.1)).rem_euclid(width);
+            curtain.hue = (curtain.hue + 0.1).rem_euclid(360.0);
+        }
+    }
+
+    /// Move every bar by a sum of sines at band-specific frequencies
+    fn update_waveform(&mut self, config: &Config) {
+        let count = config.animation.waveform_bars.clamp(5, 80) as usize;
+        if self.waveform_bars.len() != count {
+            self.waveform_bars = vec![0.0; count];
+            self.waveform_peaks = vec![0.0; count];
+        }
+
+        let t = self.tick as f32 * 0.1;
+        for (i, (bar, peak)) in self
+            .waveform_bars
+            .iter_mut()
+            .zip(self.waveform_peaks.iter_mut())
+            .enumerate()
+        {
+            let band = i as f32;
+            let freq = 1.0 + band * 0.13;
+            *bar = (0.45
+                + 0.25 * (t * freq + band * 0.7).sin()
+                + 0.15 * (t * freq * 2.3 + band * 1.9).sin()
+                + 0.1 * (t * freq * 4.1 + band * 0.3).sin())
+            .clamp(0.0, 1.0);
+            // Peaks jump up with the bar and sink back slowly
+            *peak = if *bar >= *peak {
+                *bar
+            } else {
+                (*peak - 0.01).max(*bar)
+            };
+        }
+    }
+
+    /// Refresh the time when a new second starts and drift every `clock_drift_secs`
+    fn update_clock(&mut self, area: Rect, config: &Config) {
+        let clock = &mut self.clock;
+        if clock.next_read.is_some_and(|next| Instant::now() < next) {
+            return;
+        }
+
+        let now = std::time::SystemTime::now();
+        let since_epoch = now
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default();
+        let wait = Duration::from_secs(1) - Duration::from_nanos(since_epoch.subsec_nanos() as u64);
+        clock.next_read = Some(Instant::now() + wait);
+
+        let local = chrono::DateTime::<chrono::Local>::from(now);
+        clock.text = match config.animation.clock_format.as_str() {
+            "24h" => local.format("%H:%M:%S").to_string(),
+            "unix_timestamp" => since_epoch.as_secs().to_string(),
+            _ => local.format("%I:%M:%S %p").to_string(),
+        };
 
-        for scratch in &mut self.scratches {
-            scratch.visible = rng.gen_bool(0.3);
-            scratch.y = (scratch.y + 1) % area.height;
+        // Bounce off the edges one cell at a time
+        let secs = since_epoch.as_secs();
+        if secs >= clock.last_drift + config.animation.clock_drift_secs.max(1) {
+            clock.last_drift = secs;
+            let width = clock_rows(&clock.text)[0].chars().count() as u16;
+            let max_x = area.width.saturating_sub(width);
+            let max_y = area.height.saturating_sub(5);
+            if clock.x == 0 || clock.x >= max_x {
+                clock.dx = if clock.x == 0 { 1 } else { -1 };
+            }
+            if clock.y == 0 || clock.y >= max_y {
+                clock.dy = if clock.y == 0 { 1 } else { -1 };
+            }
+            clock.x = clock.x.saturating_add_signed(clock.dx as i16).min(max_x);
+            clock.y = clock.y.saturating_add_signed(clock.dy as i16).min(max_y);
         }
     }
 
-    fn update_thermal(&mut self, area: Rect) {
-        use rand::Rng;
-        let mut rng = rand::thread_rng();
-        let count = (area.width * area.height) as usize;
-        if self.thermal_noise.len() != count {
-            self.thermal_noise = (0..count).map(|_| rng.gen_range(0..255)).collect();
+    /// Type one character per tick, pause, backspace everything, pause, repeat
+    fn update_typewriter(&mut self, config: &Config) {
+        let lines = &config.animation.typewriter_text;
+        let pause_ticks = config.animation.typewriter_pause_ticks;
+        let tw = &mut self.typewriter;
+        if lines.is_empty() {
+            return;
         }
-        for noise in &mut self.thermal_noise {
-            *noise = (*noise as i16 + rng.gen_range(-10..10)).clamp(0, 255) as u8;
+        if tw.pause_timer > 0 {
+            tw.pause_timer -= 1;
+            return;
+        }
+
+        if !tw.erasing {
+            match lines.get(tw.line) {
+                Some(line) if tw.col < line.chars().count() => tw.col += 1,
+                Some(_) => {
+                    tw.line += 1;
+                    tw.col = 0;
+                }
+                None => {
+                    tw.erasing = true;
+                    tw.pause_timer = pause_ticks;
+                }
+            }
+        } else if tw.col > 0 {
+            tw.col -= 1;
+        } else if tw.line > 0 {
+            tw.line -= 1;
+            tw.col = lines[tw.line].chars().count();
+        } else {
+            tw.erasing = false;
+            tw.pause_timer = pause_ticks;
         }
     }
 }
@@ -4549,9 +9110,7 @@ fn render_ripple(f: &mut Frame, state: &AnimationState, size: Rect, color: Color
             let x = center_x + rad.cos() * r;
             let y = center_y + rad.sin() * r * 0.5;
 
-            let px = x as u16;
-            let py = y as u16;
-            if px < size.width && py < size.height {
+            if let Some((px, py)) = cell_at(x, y, size) {
                 let span = Span::styled("◦", Style::default().fg(ring_color));
                 let text = Line::from(vec![span]);
                 let paragraph = Paragraph::new(text);
@@ -4564,7 +9123,7 @@ fn render_ripple(f: &mut Frame, state: &AnimationState, size: Rect, color: Color
 
 fn render_fog(f: &mut Frame, state: &AnimationState, size: Rect) {
     use rand::Rng;
-    let mut rng = rand::thread_rng();
+    let mut rng = state.frame_rng();
 
     let density = state.fog_density;
     for y in 0..size.height {
@@ -4630,9 +9189,7 @@ fn render_sparks(f: &mut Frame, state: &AnimationState, size: Rect) {
     f.render_widget(bg_fill, size);
 
     for spark in &state.sparks {
-        let x = spark.x as u16;
-        let y = spark.y as u16;
-        if x < size.width && y < size.height {
+        if let Some((x, y)) = cell_at(spark.x, spark.y, size) {
             let intensity = spark.brightness;
             let color = Color::Rgb(255, 200 + intensity / 5, intensity);
             let span = Span::styled("✦", Style::default().fg(color));
@@ -4713,9 +9270,7 @@ fn render_sun(f: &mut Frame, state: &AnimationState, size: Rect) {
         for r in (radius as u16 + 2)..(radius as u16 + 8) {
             let x = center_x + angle.cos() * r as f32;
             let y = center_y + angle.sin() * r as f32 * 0.5;
-            let px = x as u16;
-            let py = y as u16;
-            if px < size.width && py < size.height {
+            if let Some((px, py)) = cell_at(x, y, size) {
                 let span = Span::styled("│", Style::default().fg(Color::Rgb(255, 200, 100)));
                 let text = Line::from(vec![span]);
                 let paragraph = Paragraph::new(text);
@@ -4740,9 +9295,7 @@ fn render_galaxy(f: &mut Frame, state: &AnimationState, size: Rect) {
             let angle = r as f32 * 0.2 + state.galaxy_angle + arm_offset;
             let x = center_x + angle.cos() * r as f32;
             let y = center_y + angle.sin() * r as f32 * 0.5;
-            let px = x as u16;
-            let py = y as u16;
-            if px < size.width && py < size.height {
+            if let Some((px, py)) = cell_at(x, y, size) {
                 let intensity = (255 - r * 6) as u8;
                 let color = Color::Rgb(intensity, intensity / 2, intensity);
                 let span = Span::styled("•", Style::default().fg(color));
@@ -4767,9 +9320,7 @@ fn render_meteor_shower(f: &mut Frame, state: &AnimationState, size: Rect) {
     f.render_widget(bg_fill, size);
 
     for meteor in &state.meteors {
-        let x = meteor.x as u16;
-        let y = meteor.y as u16;
-        if x < size.width && y < size.height {
+        if let Some((x, y)) = cell_at(meteor.x, meteor.y, size) {
             let intensity = meteor.brightness;
             let color = Color::Rgb(255, 255, intensity);
             let span = Span::styled("☄", Style::default().fg(color));
@@ -4780,9 +9331,11 @@ fn render_meteor_shower(f: &mut Frame, state: &AnimationState, size: Rect) {
 
             // Tail
             for t in 1..meteor.tail_length {
-                let tx = (meteor.x - meteor.vx * t as f32) as u16;
-                let ty = (meteor.y - meteor.vy * t as f32) as u16;
-                if tx < size.width && ty < size.height {
+                if let Some((tx, ty)) = cell_at(
+                    meteor.x - meteor.vx * t as f32,
+                    meteor.y - meteor.vy * t as f32,
+                    size,
+                ) {
                     let tail_intensity = intensity.saturating_sub(t * 20);
                     let tail_color = Color::Rgb(tail_intensity, tail_intensity, tail_intensity / 2);
                     let span = Span::styled("·", Style::default().fg(tail_color));
@@ -4809,9 +9362,7 @@ fn render_satellite(f: &mut Frame, state: &AnimationState, size: Rect) {
         let rad = angle as f32 * std::f32::consts::PI / 180.0;
         let x = center_x + rad.cos() * radius;
         let y = center_y + rad.sin() * radius * 0.5;
-        let px = x as u16;
-        let py = y as u16;
-        if px < size.width && py < size.height {
+        if let Some((px, py)) = cell_at(x, y, size) {
             let span = Span::styled("·", Style::default().fg(Color::Rgb(50, 50, 80)));
             let text = Line::from(vec![span]);
             let paragraph = Paragraph::new(text);
@@ -4821,9 +9372,7 @@ fn render_satellite(f: &mut Frame, state: &AnimationState, size: Rect) {
     }
 
     // Satellite
-    let x = state.satellite.x as u16;
-    let y = state.satellite.y as u16;
-    if x < size.width && y < size.height {
+    if let Some((x, y)) = cell_at(state.satellite.x, state.satellite.y, size) {
         let span = Span::styled("🛰", Style::default().fg(Color::Rgb(200, 200, 220)));
         let text = Line::from(vec![span]);
         let paragraph = Paragraph::new(text);
@@ -4864,9 +9413,7 @@ fn render_pulsar(f: &mut Frame, state: &AnimationState, size: Rect, color: Color
         for r in 0..20 {
             let x = center_x + beam_angle.cos() * r as f32;
             let y = center_y + beam_angle.sin() * r as f32 * 0.5;
-            let px = x as u16;
-            let py = y as u16;
-            if px < size.width && py < size.height {
+            if let Some((px, py)) = cell_at(x, y, size) {
                 let intensity = (pulse * 255.0) as u8;
                 let c = match color {
                     Color::Rgb(r, g, b) => Color::Rgb(
@@ -4921,10 +9468,34 @@ fn render_pong(f: &mut Frame, state: &AnimationState, size: Rect) {
         }
     }
 
+    // Trail, fading out behind the ball
+    let trail_len = state.pong.trail.len();
+    for (i, &(x, y)) in state.pong.trail.iter().enumerate().rev() {
+        if let Some((tx, ty)) = cell_at(x, y, size) {
+            let ch = ["◆", "◇", "·"][i * 3 / trail_len];
+            let fade = 1.0 - (i + 1) as f32 / (trail_len + 1) as f32;
+            let level = (255.0 * fade) as u8;
+            let color = Color::Rgb(level, level, (100.0 * fade) as u8);
+            let span = Span::styled(ch, Style::default().fg(color));
+            let area = Rect::new(tx, ty, 1, 1);
+            f.render_widget(Paragraph::new(Line::from(vec![span])), area);
+        }
+    }
+
+    // Paddle hits
+    for &(x, y, _) in &state.pong.flashes {
+        if x < size.width && y < size.height {
+            let style = Style::default()
+                .fg(Color::Rgb(255, 255, 255))
+                .add_modifier(Modifier::BOLD);
+            let span = Span::styled("◈", style);
+            let area = Rect::new(x, y, 1, 1);
+            f.render_widget(Paragraph::new(Line::from(vec![span])), area);
+        }
+    }
+
     // Ball
-    let bx = state.pong.ball_x as u16;
-    let by = state.pong.ball_y as u16;
-    if bx < size.width && by < size.height {
+    if let Some((bx, by)) = cell_at(state.pong.ball_x, state.pong.ball_y, size) {
         let span = Span::styled("◆", Style::default().fg(Color::Rgb(255, 255, 100)));
         let text = Line::from(vec![span]);
         let paragraph = Paragraph::new(text);
@@ -5026,9 +9597,7 @@ fn render_invaders(f: &mut Frame, state: &AnimationState, size: Rect) {
     ];
 
     for invader in &state.invaders {
-        let x = invader.x as u16;
-        let y = invader.y as u16;
-        if x < size.width && y < size.height {
+        if let Some((x, y)) = cell_at(invader.x, invader.y, size) {
             let color = colors[invader.invader_type as usize % colors.len()];
             let ch = invader_chars[invader.invader_type as usize % invader_chars.len()];
             let span = Span::styled(ch.to_string(), Style::default().fg(color));
@@ -5038,6 +9607,38 @@ fn render_invaders(f: &mut Frame, state: &AnimationState, size: Rect) {
             f.render_widget(paragraph, area);
         }
     }
+
+    let game = &state.invader_game;
+    for bullet in &game.bullets {
+        if let Some((x, y)) = cell_at(bullet.x, bullet.y, size) {
+            let (ch, color) = if bullet.moving_up {
+                ('|', Color::Rgb(255, 255, 255))
+            } else {
+                ('!', Color::Rgb(255, 100, 100))
+            };
+            let span = Span::styled(ch.to_string(), Style::default().fg(color));
+            let area = Rect::new(x, y, 1, 1);
+            f.render_widget(Paragraph::new(Line::from(vec![span])), area);
+        }
+    }
+
+    for explosion in &game.explosions {
+        if explosion.x < size.width && explosion.y < size.height {
+            let style = Style::default()
+                .fg(Color::Rgb(255, 200, 50))
+                .add_modifier(Modifier::BOLD);
+            let span = Span::styled("*", style);
+            let area = Rect::new(explosion.x, explosion.y, 1, 1);
+            f.render_widget(Paragraph::new(Line::from(vec![span])), area);
+        }
+    }
+
+    let player_y = size.height.saturating_sub(2) as f32;
+    if let Some((x, y)) = cell_at(game.player_x, player_y, size) {
+        let span = Span::styled("▲", Style::default().fg(Color::Rgb(100, 255, 100)));
+        let area = Rect::new(x, y, 1, 1);
+        f.render_widget(Paragraph::new(Line::from(vec![span])), area);
+    }
 }
 
 fn render_fibonacci(f: &mut Frame, state: &AnimationState, size: Rect, color: Color) {
@@ -5054,9 +9655,7 @@ fn render_fibonacci(f: &mut Frame, state: &AnimationState, size: Rect, color: Co
         let x = center_x + r * theta.cos();
         let y = center_y + r * theta.sin() * 0.5;
 
-        let px = x as u16;
-        let py = y as u16;
-        if px < size.width && py < size.height {
+        if let Some((px, py)) = cell_at(x, y, size) {
             let intensity = (255 - i / 2) as u8;
             let c = match color {
                 Color::Rgb(r, g, b) => Color::Rgb(
@@ -5079,27 +9678,28 @@ fn render_mandelbrot(f: &mut Frame, state: &AnimationState, size: Rect, color: C
     let bg_fill = Block::default().style(Style::default().bg(Color::Rgb(5, 5, 10)));
     f.render_widget(bg_fill, size);
 
-    let offset_x = state.mandelbrot_offset.0;
-    let offset_y = state.mandelbrot_offset.1;
+    let zoom = state.mandelbrot_zoom;
+    // Deeper views need more iterations to keep the boundary detailed
+    let max_iter = 30 + (zoom.max(1.0).log2() * 10.0) as u32;
 
     for py in 0..size.height {
         for px in 0..size.width {
-            let x0 = (px as f32 / size.width as f32 - 0.5) * 3.0 + offset_x;
-            let y0 = (py as f32 / size.height as f32 - 0.5) * 2.0 + offset_y;
+            let x0 = (px as f32 / size.width as f32 - 0.5) * 3.0 / zoom + state.mandelbrot_real;
+            let y0 = (py as f32 / size.height as f32 - 0.5) * 2.0 / zoom + state.mandelbrot_imag;
 
             let mut x = 0.0;
             let mut y = 0.0;
             let mut iter = 0;
 
-            while x * x + y * y <= 4.0 && iter < 30 {
+            while x * x + y * y <= 4.0 && iter < max_iter {
                 let xtemp = x * x - y * y + x0;
                 y = 2.0 * x * y + y0;
                 x = xtemp;
                 iter += 1;
             }
 
-            if iter < 30 {
-                let intensity = (iter as f32 / 30.0 * 255.0) as u8;
+            if iter < max_iter {
+                let intensity = (iter as f32 / max_iter as f32 * 255.0) as u8;
                 let c = match color {
                     Color::Rgb(r, g, b) => Color::Rgb(
                         (r as u16 * intensity as u16 / 255) as u8,
@@ -5158,9 +9758,7 @@ fn render_rose(f: &mut Frame, state: &AnimationState, size: Rect, color: Color)
         let x = center_x + r * rad.cos();
         let y = center_y + r * rad.sin() * 0.5;
 
-        let px = x as u16;
-        let py = y as u16;
-        if px < size.width && py < size.height {
+        if let Some((px, py)) = cell_at(x, y, size) {
             let c = match color {
                 Color::Rgb(r, g, b) => Color::Rgb(r, g, b),
                 _ => Color::Rgb(255, 100, 150),
@@ -5179,9 +9777,7 @@ fn render_butterflies(f: &mut Frame, state: &AnimationState, size: Rect) {
     f.render_widget(bg_fill, size);
 
     for butterfly in &state.butterflies {
-        let x = butterfly.x as u16;
-        let y = butterfly.y as u16;
-        if x < size.width && y < size.height {
+        if let Some((x, y)) = cell_at(butterfly.x, butterfly.y, size) {
             let hue = butterfly.color as f32 / 255.0;
             let r = ((hue * 6.0).sin() * 0.5 + 0.5) * 255.0;
             let g = ((hue * 6.0 + 2.0).sin() * 0.5 + 0.5) * 255.0;
@@ -5203,33 +9799,61 @@ fn render_spider_web(f: &mut Frame, state: &AnimationState, size: Rect) {
     f.render_widget(bg_fill, size);
 
     for strand in &state.web_strands {
-        let x1 = strand.x1 as u16;
-        let y1 = strand.y1 as u16;
-        let x2 = strand.x2 as u16;
-        let y2 = strand.y2 as u16;
-
-        // Simple line drawing
-        let dx = if x2 > x1 { x2 - x1 } else { x1 - x2 };
-        let dy = if y2 > y1 { y2 - y1 } else { y1 - y2 };
-        let steps = dx.max(dy);
-
-        for step in 0..=steps {
-            let t = if steps == 0 {
-                0.0
-            } else {
-                step as f32 / steps as f32
-            };
-            let x = (strand.x1 + (strand.x2 - strand.x1) * t) as u16;
-            let y = (strand.y1 + (strand.y2 - strand.y1) * t) as u16;
-            if x < size.width && y < size.height {
-                let intensity = (200.0 + strand.vibration * 500.0) as u8;
-                let color = Color::Rgb(intensity, intensity, intensity + 20);
-                let span = Span::styled("·", Style::default().fg(color));
-                let text = Line::from(vec![span]);
-                let paragraph = Paragraph::new(text);
-                let area = Rect::new(x, y, 1, 1);
-                f.render_widget(paragraph, area);
-            }
+        let intensity = (200.0 + strand.vibration * 500.0) as u8;
+        let color = Color::Rgb(intensity, intensity, intensity.saturating_add(20));
+        draw_line(
+            f,
+            strand.x1 as i16,
+            strand.y1 as i16,
+            strand.x2 as i16,
+            strand.y2 as i16,
+            '·',
+            color,
+            size,
+        );
+    }
+}
+
+/// Bresenham line from (x1, y1) to (x2, y2), skipping cells outside `bounds`
+#[allow(clippy::too_many_arguments)]
+fn draw_line(
+    f: &mut Frame,
+    x1: i16,
+    y1: i16,
+    x2: i16,
+    y2: i16,
+    ch: char,
+    color: Color,
+    bounds: Rect,
+) {
+    let (mut x, mut y) = (x1 as i32, y1 as i32);
+    let (x2, y2) = (x2 as i32, y2 as i32);
+    let dx = (x2 - x).abs();
+    let dy = -(y2 - y).abs();
+    let sx = if x < x2 { 1 } else { -1 };
+    let sy = if y < y2 { 1 } else { -1 };
+    let mut err = dx + dy;
+
+    let buffer = f.buffer_mut();
+    loop {
+        let inside = x >= bounds.left() as i32
+            && x < bounds.right() as i32
+            && y >= bounds.top() as i32
+            && y < bounds.bottom() as i32;
+        if inside {
+            buffer[(x as u16, y as u16)].set_char(ch).set_fg(color);
+        }
+        if x == x2 && y == y2 {
+            break;
+        }
+        let e2 = 2 * err;
+        if e2 >= dy {
+            err += dy;
+            x += sx;
+        }
+        if e2 <= dx {
+            err += dx;
+            y += sy;
         }
     }
 }
@@ -5272,7 +9896,7 @@ fn render_moss(f: &mut Frame, state: &AnimationState, size: Rect) {
 
     for cell in &state.moss {
         if cell.x < size.width && cell.y < size.height {
-            let intensity = (100 + cell.age / 2).min(255) as u8;
+            let intensity = 100 + cell.age / 2;
             let color = if cell.spreading {
                 Color::Rgb(intensity / 2, intensity, intensity / 3)
             } else {
@@ -5308,9 +9932,7 @@ fn render_radar(f: &mut Frame, state: &AnimationState, size: Rect, color: Color)
             let rad = angle as f32 * std::f32::consts::PI / 180.0;
             let x = center_x + rad.cos() * r as f32;
             let y = center_y + rad.sin() * r as f32 * 0.6;
-            let px = x as u16;
-            let py = y as u16;
-            if px < size.width && py < size.height {
+            if let Some((px, py)) = cell_at(x, y, size) {
                 let span = Span::styled("·", Style::default().fg(Color::Rgb(50, 100, 50)));
                 let text = Line::from(vec![span]);
                 let paragraph = Paragraph::new(text);
@@ -5326,9 +9948,7 @@ fn render_radar(f: &mut Frame, state: &AnimationState, size: Rect, color: Color)
         let rad = sweep_angle;
         let x = center_x + rad.cos() * r as f32;
         let y = center_y + rad.sin() * r as f32 * 0.6;
-        let px = x as u16;
-        let py = y as u16;
-        if px < size.width && py < size.height {
+        if let Some((px, py)) = cell_at(x, y, size) {
             let span = Span::styled("█", Style::default().fg(color));
             let text = Line::from(vec![span]);
             let paragraph = Paragraph::new(text);
@@ -5339,15 +9959,13 @@ fn render_radar(f: &mut Frame, state: &AnimationState, size: Rect, color: Color)
 
     // Blips
     use rand::Rng;
-    let mut rng = rand::thread_rng();
+    let mut rng = state.frame_rng();
     if rng.gen_bool(0.05) {
         let r = rng.gen_range(5.0..radius);
         let angle = rng.gen_range(0.0..std::f32::consts::TAU);
         let x = center_x + angle.cos() * r;
         let y = center_y + angle.sin() * r * 0.6;
-        let px = x as u16;
-        let py = y as u16;
-        if px < size.width && py < size.height {
+        if let Some((px, py)) = cell_at(x, y, size) {
             let span = Span::styled("●", Style::default().fg(Color::Rgb(255, 50, 50)));
             let text = Line::from(vec![span]);
             let paragraph = Paragraph::new(text);
@@ -5401,10 +10019,11 @@ fn render_signal(f: &mut Frame, state: &AnimationState, size: Rect) {
         // Draw wave at this radius
         for angle in (0..360).step_by(30) {
             let rad = angle as f32 * std::f32::consts::PI / 180.0;
-            let px = (x as f32 + rad.cos() * r as f32) as u16;
-            let py = (y as f32 + rad.sin() * r as f32 * 0.3) as u16;
-
-            if px < size.width && py < size.height {
+            if let Some((px, py)) = cell_at(
+                x as f32 + rad.cos() * r as f32,
+                y as f32 + rad.sin() * r as f32 * 0.3,
+                size,
+            ) {
                 let intensity =
                     (signal.amplitude as f32 * (1.0 - signal.radius / signal.max_radius)) as u8;
                 let color = Color::Rgb(intensity, intensity, intensity + 50);
@@ -5444,9 +10063,7 @@ fn render_wifi(f: &mut Frame, state: &AnimationState, size: Rect) {
             let rad = angle as f32 * std::f32::consts::PI / 180.0;
             let x = center_x + rad.cos() * r as f32;
             let y = center_y + rad.sin() * r as f32 * 0.5;
-            let px = x as u16;
-            let py = y as u16;
-            if px < size.width && py < size.height {
+            if let Some((px, py)) = cell_at(x, y, size) {
                 let span = Span::styled(")", Style::default().fg(color));
                 let text = Line::from(vec![span]);
                 let paragraph = Paragraph::new(text);
@@ -5603,7 +10220,7 @@ fn render_hologram(f: &mut Frame, state: &AnimationState, size: Rect, color: Col
 
     // Holographic content (flickering grid)
     use rand::Rng;
-    let mut rng = rand::thread_rng();
+    let mut rng = state.frame_rng();
     for y in (0..size.height).step_by(3) {
         for x in (0..size.width).step_by(4) {
             if rng.gen_bool(0.3) {
@@ -5628,7 +10245,7 @@ fn render_hologram(f: &mut Frame, state: &AnimationState, size: Rect, color: Col
 
 fn render_glitch(f: &mut Frame, state: &AnimationState, size: Rect) {
     use rand::Rng;
-    let mut rng = rand::thread_rng();
+    let mut rng = state.frame_rng();
 
     // Base background
     let bg_fill = Block::default().style(Style::default().bg(Color::Rgb(10, 10, 10)));
@@ -5686,7 +10303,7 @@ fn render_old_film(f: &mut Frame, state: &AnimationState, size: Rect) {
 
     // Film grain
     use rand::Rng;
-    let mut rng = rand::thread_rng();
+    let mut rng = state.frame_rng();
     for _ in 0..50 {
         let x = rng.gen_range(0..size.width);
         let y = rng.gen_range(0..size.height);
@@ -5706,8 +10323,7 @@ fn render_thermal(f: &mut Frame, state: &AnimationState, size: Rect) {
     for y in 0..size.height {
         for x in 0..size.width {
             let idx = (y * size.width + x) as usize;
-            if let Some(noise) = state.thermal_noise.get(idx) {
-                let temp = *noise as f32 / 255.0;
+            if let Some(&temp) = state.thermal_noise.get(idx) {
                 // Thermal color mapping: black -> blue -> purple -> red -> yellow -> white
                 let color = if temp < 0.2 {
                     Color::Rgb(0, 0, (temp * 5.0 * 255.0) as u8)
@@ -5748,8 +10364,17 @@ const MATRIX_CHARS: &[char; 49] = &[
 fn ui(f: &mut Frame, app: &mut App) {
     let size = f.area();
 
-    // Update and render background animation first (needs mutable borrow)
-    app.update_animation(size);
+    // Below the minimum size only a hint is drawn, until the terminal grows again
+    let responsive = &app.config.responsive;
+    if responsive.enabled
+        && (size.width < responsive.min_terminal_width
+            || size.height < responsive.min_terminal_height)
+    {
+        render_terminal_too_small(f, app, size);
+        return;
+    }
+
+    app.refresh_title();
 
     // Get config reference after mutable borrow is done
     let config = &app.config;
@@ -5763,59 +10388,263 @@ fn ui(f: &mut Frame, app: &mut App) {
 
     // Check if we're in confirmation mode
     match &app.state {
-        AppState::Confirming { action_index } => {
-            render_confirmation_dialog(f, app, *action_index, size);
+        AppState::Confirming {
+            action_ref, focus, ..
+        } => {
+            render_confirmation_dialog(f, app, *action_ref, *focus, size);
         }
         AppState::GracePeriod {
-            action_index,
+            action_ref,
             remaining_secs,
             ..
         } => {
-            render_grace_period(f, app, *action_index, *remaining_secs, size);
+            render_grace_period(f, app, *action_ref, *remaining_secs, size);
         }
         AppState::AnimationMenu => {
             render_animation_menu(f, app, size);
         }
-        AppState::Selecting => {
+        AppState::ThemeMenu { theme_index } => {
+            render_theme_menu(f, app, *theme_index, size);
+        }
+        AppState::Running {
+            action_ref,
+            started,
+            ..
+        } => {
+            render_running_dialog(f, app, *action_ref, *started, size);
+        }
+        AppState::CommandError {
+            action_ref,
+            message,
+            focus,
+            copied,
+        } => {
+            render_error_dialog(f, app, *action_ref, message, *focus, *copied, size);
+        }
+        AppState::Selecting | AppState::HelpOverlay | AppState::SubMenu { .. } => {
             // Render based on layout mode
             match layout_mode.as_str() {
                 "horizontal" => render_horizontal_layout(f, app, size),
                 "grid" => render_grid_layout(f, app, size),
                 "compact" => render_compact_layout(f, app, size),
-                _ => render_vertical_layout(f, app, size, auto_scale),
+                _ => {
+                    app.visible_items = vertical_visible_items(app, size, auto_scale);
+                    app.ensure_selected_visible();
+                    render_vertical_layout(f, app, size, auto_scale)
+                }
             }
 
             // Render help text
             if render_help {
                 render_help_text(f, app, size);
             }
+
+            // The keybinding overlay is drawn on top of the dimmed menu
+            if matches!(app.state, AppState::HelpOverlay) {
+                render_help_overlay(f, app, size);
+            }
+
+            render_submenu(f, app, size);
         }
     }
+
+    if app.config.performance.debug_overlay {
+        render_debug_overlay(f, app, size);
+    }
 }
 
-fn render_vertical_layout(f: &mut Frame, app: &App, size: Rect, auto_scale: bool) {
+/// Replaces the whole UI while the terminal is smaller than the configured minimum
+fn render_terminal_too_small(f: &mut Frame, app: &App, size: Rect) {
+    let responsive = &app.config.responsive;
+    let message = format!(
+        "Terminal too small ({}x{} min)",
+        responsive.min_terminal_width, responsive.min_terminal_height
+    );
+    // Center vertically; on narrow terminals the message wraps onto the rows below
+    let lines = display_width(&message).div_ceil(size.width.max(1));
+    let top = size.height.saturating_sub(lines) / 2;
+    let area = Rect {
+        y: size.y + top,
+        height: size.height - top,
+        ..size
+    };
+    let paragraph = Paragraph::new(message)
+        .alignment(Alignment::Center)
+        .wrap(Wrap { trim: true })
+        .style(Style::default().fg(Color::Red));
+    f.render_widget(paragraph, area);
+}
+
+/// Popup of the sub-menu, placed next to the selected item of the main menu
+fn submenu_area(app: &App, size: Rect) -> Rect {
+    let AppState::SubMenu { sub_actions, .. } = &app.state else {
+        return Rect::default();
+    };
+    let longest = sub_actions
+        .iter()
+        .map(|a| display_width(&a.display_text(&app.config)))
+        .max()
+        .unwrap_or(0);
+    let width = (longest + 4).max(16).min(size.width);
+    let height = (sub_actions.len() as u16 + 2).min(size.height);
+
+    let menu_area = main_menu_area(app, size);
+    let selected = app.selected_index as u16;
+    let (x, y) = match app.config.layout_mode.as_str() {
+        "horizontal" | "compact" => {
+            let item_width = if app.config.layout_mode == "compact" {
+                5
+            } else {
+                15
+            };
+            (
+                menu_area.x + 1 + selected * item_width,
+                menu_area.y + menu_area.height - 1,
+            )
+        }
+        "grid" => {
+            let cols = grid_columns(app) as u16;
+            (
+                menu_area.x + 1 + (selected % cols) * grid_cell_width(app, size),
+                menu_area.y + 2 + (selected / cols) * GRID_CELL_HEIGHT,
+            )
+        }
+        _ => {
+            let row = vertical_rows(app)
+                .iter()
+                .position(|r| matches!(r, ListRow::Action(i) if *i == app.selected_index))
+                .unwrap_or(0);
+            let row = row.saturating_sub(app.scroll_offset) as u16;
+            (menu_area.x + 4, menu_area.y + 2 + row)
+        }
+    };
+
+    // Keep the popup on screen
+    Rect {
+        x: x.min(size.width.saturating_sub(width)),
+        y: y.min(size.height.saturating_sub(height)),
+        width,
+        height,
+    }
+}
+
+/// Nested menu of an action's `submenu` entries, on top of the main menu
+fn render_submenu(f: &mut Frame, app: &App, size: Rect) {
+    let AppState::SubMenu {
+        parent_index,
+        sub_actions,
+        selected,
+    } = &app.state
+    else {
+        return;
+    };
     let config = &app.config;
-    let center_area = if auto_scale {
-        calculate_auto_layout(app, size)
-    } else {
-        calculate_fixed_layout(app, size)
+    let area = submenu_area(app, size);
+
+    let selected_style = Style::default()
+        .fg(parse_color(&config.colors.selected_fg))
+        .bg(parse_color(&config.colors.selected_bg))
+        .add_modifier(parse_modifier(&config.colors.selected_modifier));
+    let fg_color = parse_color(&config.colors.foreground);
+
+    let lines: Vec<Line> = sub_actions
+        .iter()
+        .enumerate()
+        .map(|(i, action)| {
+            let style = if i == *selected {
+                selected_style
+            } else {
+                Style::default().fg(fg_color)
+            };
+            Line::from(Span::styled(action.display_text(config), style))
+        })
+        .collect();
+
+    let title = app
+        .actions
+        .get(*parent_index)
+        .map(|a| format!(" {} ", a.label))
+        .unwrap_or_default();
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(title)
+        .border_style(Style::default().fg(parse_color(&config.colors.border)))
+        .border_set(border_set(&config.border))
+        .style(Style::default().bg(Color::Black));
+
+    f.render_widget(Clear, area);
+    f.render_widget(Paragraph::new(lines).block(block), area);
+}
+
+/// FPS and frame time of the renderer in the top-right corner
+fn render_debug_overlay(f: &mut Frame, app: &App, size: Rect) {
+    let monitor = &app.performance_monitor;
+    let text = format!(
+        " {:.0} fps | {:.1} ms | {} ",
+        monitor.avg_fps(),
+        monitor.last_frame_time().as_secs_f64() * 1000.0,
+        app.term_caps.color_depth()
+    );
+    let width = display_width(&text).min(size.width);
+    let area = Rect {
+        x: size.x + size.width - width,
+        y: size.y,
+        width,
+        height: 1,
     };
+    let paragraph = Paragraph::new(text).style(
+        Style::default()
+            .fg(parse_color(&app.config.colors.help_fg))
+            .bg(Color::Black),
+    );
+    f.render_widget(paragraph, area);
+}
+
+fn render_vertical_layout(f: &mut Frame, app: &App, size: Rect, auto_scale: bool) {
+    let config = &app.config;
+    let center_area = vertical_menu_area(app, size, auto_scale);
 
     // Parse colors
     let fg_color = parse_color(&config.colors.foreground);
     let selected_fg = parse_color(&config.colors.selected_fg);
     let selected_bg = parse_color(&config.colors.selected_bg);
     let selected_modifier = parse_modifier(&config.colors.selected_modifier);
-    let border_color = parse_color(&config.colors.border);
+    let border_color = app.menu_border_color();
+    let icon_color = parse_color(&config.colors.icon_color);
+    let shortcut_color = parse_color(&config.colors.help_key_fg);
+
+    // Column widths are measured once for the whole list
+    let widths = config.align_columns.then(|| column_widths(app));
+
+    // Only the rows that fit in the list are rendered
+    let visible = app.visible_items.max(1);
+    let rows = vertical_rows(app);
+    let count_actions = |rows: &[ListRow]| {
+        rows.iter()
+            .filter(|r| matches!(r, ListRow::Action(_)))
+            .count()
+    };
+    let first_hidden_below = (app.scroll_offset + visible).min(rows.len());
+    let hidden_above = count_actions(&rows[..app.scroll_offset.min(rows.len())]);
+    let hidden_below = count_actions(&rows[first_hidden_below..]);
 
-    // Create list items with shortcut display
-    let items: Vec<ListItem> = app
-        .actions
+    // Create list lines with shortcut display
+    let lines: Vec<Line> = rows
         .iter()
-        .enumerate()
-        .map(|(i, action)| {
-            let content = action.display_text(true);
-            let style = if i == app.selected_index {
+        .skip(app.scroll_offset)
+        .take(visible)
+        .map(|row| {
+            let i = match row {
+                ListRow::Divider(name) => {
+                    return Line::from(Span::styled(
+                        category_divider(name, config),
+                        Style::default().fg(border_color),
+                    ));
+                }
+                ListRow::Action(i) => *i,
+            };
+            let selected = i == app.selected_index;
+            let style = if selected {
                 Style::default()
                     .fg(selected_fg)
                     .bg(selected_bg)
@@ -5823,7 +10652,23 @@ fn render_vertical_layout(f: &mut Frame, app: &App, size: Rect, auto_scale: bool
             } else {
                 Style::default().fg(fg_color)
             };
-            ListItem::new(Line::from(Span::styled(content, style)))
+            let Some(widths) = &widths else {
+                let content = accessible_text(app, i, &app.actions[i].display_text(config));
+                return Line::from(Span::styled(content, style));
+            };
+
+            // One span per column; the selection highlight spans the whole row
+            let mut spans = vec![Span::styled(accessible_text(app, i, ""), style)];
+            for (column, text) in app.actions[i].aligned_columns(config, widths) {
+                let column_style = match column {
+                    _ if selected => style,
+                    Column::Icon => style.fg(icon_color),
+                    Column::Shortcut => style.fg(shortcut_color),
+                    Column::Label => style,
+                };
+                spans.push(Span::styled(text, column_style));
+            }
+            Line::from(spans)
         })
         .collect();
 
@@ -5832,21 +10677,240 @@ fn render_vertical_layout(f: &mut Frame, app: &App, size: Rect, auto_scale: bool
 
     let title_alignment = parse_title_alignment(&config.title_alignment);
 
+    let mut block = Block::default()
+        .borders(if config.border.enabled {
+            border_type
+        } else {
+            Borders::NONE
+        })
+        .title(app.title.as_str())
+        .title_style(app.menu_title_style())
+        .title_alignment(title_alignment)
+        .border_style(Style::default().fg(border_color))
+        .border_set(border_set(&config.border));
+
+    // Scroll indicators live in the top and bottom border
+    if hidden_above > 0 {
+        block = block.title_top(Line::from(format!(" ▲ {} more ", hidden_above)).right_aligned());
+    }
+    if hidden_below > 0 {
+        block =
+            block.title_bottom(Line::from(format!(" ▼ {} more ", hidden_below)).right_aligned());
+    }
+
+    if config.layout.fullscreen {
+        let inner = block.inner(center_area);
+        f.render_widget(block, center_area);
+        render_fullscreen_items(f, config, lines, inner, fg_color);
+        return;
+    }
+
+    let items: Vec<ListItem> = lines.into_iter().map(ListItem::new).collect();
     let list = List::new(items)
-        .block(
-            Block::default()
-                .borders(if config.border.enabled {
-                    border_type
-                } else {
-                    Borders::NONE
-                })
-                .title(config.title.as_str())
-                .title_alignment(title_alignment)
-                .border_style(Style::default().fg(border_color)),
-        )
+        .block(block)
         .style(Style::default().fg(fg_color));
 
     f.render_widget(list, center_area);
+
+    // Page dots below the box while the list scrolls
+    let below = center_area.y + center_area.height;
+    if config.layout.show_scroll_indicator && rows.len() > visible && below < size.y + size.height {
+        let total_pages = rows.len().div_ceil(visible);
+        let current_page = if first_hidden_below == rows.len() {
+            total_pages - 1
+        } else {
+            app.scroll_offset / visible
+        };
+        let area = Rect {
+            y: below,
+            height: 1,
+            ..center_area
+        };
+        render_scroll_indicator(f, area, current_page, total_pages, border_color);
+    }
+}
+
+/// "● ○ ○" centered in `area`, the filled dot marking the current page
+fn render_scroll_indicator(
+    f: &mut Frame,
+    area: Rect,
+    current_page: usize,
+    total_pages: usize,
+    color: Color,
+) {
+    let dots: Vec<&str> = (0..total_pages)
+        .map(|page| if page == current_page { "●" } else { "○" })
+        .collect();
+    let paragraph = Paragraph::new(dots.join(" "))
+        .alignment(Alignment::Center)
+        .style(Style::default().fg(color));
+    f.render_widget(paragraph, area);
+}
+
+/// Terminal columns taken by `s`, counting emoji and CJK characters as two
+fn display_width(s: &str) -> u16 {
+    unicode_width::UnicodeWidthStr::width(s).min(u16::MAX as usize) as u16
+}
+
+/// Pad `s` with spaces to `width` terminal columns
+fn pad_to_width(s: &str, width: u16) -> String {
+    let padding = width.saturating_sub(display_width(s)) as usize;
+    format!("{}{}", s, " ".repeat(padding))
+}
+
+/// A piece of a row of the vertical list with `align_columns`
+#[derive(Clone, Copy, PartialEq)]
+enum Column {
+    Icon,
+    Shortcut,
+    Label,
+}
+
+/// Widest icon, "[key]" and label of the menu, for `align_columns`
+struct ColumnWidths {
+    icon: u16,
+    shortcut: u16,
+    label: u16,
+}
+
+fn column_widths(app: &App) -> ColumnWidths {
+    let widest = |width: fn(&Action) -> u16| app.actions.iter().map(width).max().unwrap_or(0);
+    // "[key]" is two columns wider than the key itself
+    let shortcut = widest(|a| match a.shortcut.as_str() {
+        "" => 0,
+        key => display_width(key) + 2,
+    });
+    let icons = app.actions.iter().map(|a| a.padded_icon(&app.config));
+    ColumnWidths {
+        icon: icons.map(|icon| display_width(&icon)).max().unwrap_or(0),
+        shortcut: shortcut * app.config.show_shortcut as u16,
+        label: widest(|a| display_width(&a.label)),
+    }
+}
+
+/// Text of an action in the vertical list, lined up in columns with `align_columns`
+fn list_text(app: &App, index: usize) -> String {
+    let action = &app.actions[index];
+    if !app.config.align_columns {
+        return action.display_text(&app.config);
+    }
+    let widths = column_widths(app);
+    action
+        .aligned_columns(&app.config, &widths)
+        .into_iter()
+        .map(|(_, text)| text)
+        .collect()
+}
+
+/// Item text with the accessibility number and selection prefix; other items
+/// are padded by the prefix width so labels stay aligned
+fn accessible_text(app: &App, index: usize, text: &str) -> String {
+    let Some(accessibility) = &app.config.accessibility else {
+        return text.to_string();
+    };
+    let mut result = String::new();
+    if accessibility.show_item_numbers {
+        let digits = app.actions.len().to_string().len();
+        result.push_str(&format!("{:>width$}. ", index + 1, width = digits));
+    }
+    if index == app.selected_index {
+        result.push_str(&accessibility.selection_prefix);
+    } else {
+        let width = accessibility.selection_prefix.chars().count();
+        result.push_str(&" ".repeat(width));
+    }
+    result.push_str(text);
+    result
+}
+
+/// A row of the vertical list: a category divider or the index of an action
+enum ListRow<'a> {
+    Divider(&'a str),
+    Action(usize),
+}
+
+/// Rows of the vertical list, with a divider before each category when enabled
+fn vertical_rows(app: &App) -> Vec<ListRow<'_>> {
+    let mut rows = Vec::with_capacity(app.actions.len());
+    let mut current = None;
+    for (i, action) in app.actions.iter().enumerate() {
+        if app.config.show_categories && current != Some(action.category_name()) {
+            current = Some(action.category_name());
+            rows.push(ListRow::Divider(action.category_name()));
+        }
+        rows.push(ListRow::Action(i));
+    }
+    rows
+}
+
+/// "─── Name ───" drawn with the border's horizontal glyph
+fn category_divider(name: &str, config: &Config) -> String {
+    let line = border_set(&config.border).horizontal_top.repeat(3);
+    format!("{} {} {}", line, name, line)
+}
+
+/// Spread the vertical menu's lines evenly over `inner`, one per equal slot
+fn render_fullscreen_items(
+    f: &mut Frame,
+    config: &Config,
+    lines: Vec<Line>,
+    inner: Rect,
+    fg_color: Color,
+) {
+    // A blank fill character leaves the gaps to the background animation
+    let bg_char = config
+        .layout
+        .fullscreen_bg_char
+        .chars()
+        .next()
+        .unwrap_or(' ');
+    if bg_char != ' ' {
+        let row = bg_char.to_string().repeat(inner.width as usize);
+        let fill: Vec<Line> = (0..inner.height).map(|_| Line::from(row.clone())).collect();
+        let fill_paragraph = Paragraph::new(fill).style(Style::default().fg(fg_color));
+        f.render_widget(fill_paragraph, inner);
+    }
+
+    let count = lines.len().max(1) as u32;
+    let slots = Layout::vertical(vec![Constraint::Ratio(1, count); lines.len()]).split(inner);
+    for (line, slot) in lines.into_iter().zip(slots.iter()) {
+        let row = Rect {
+            y: slot.y + slot.height / 2,
+            height: slot.height.min(1),
+            ..*slot
+        };
+        f.render_widget(Paragraph::new(line).alignment(Alignment::Center), row);
+    }
+}
+
+/// Fixed width of each item in the horizontal layout
+const HORIZONTAL_ITEM_WIDTH: u16 = 15;
+
+/// Width of each icon in the compact layout
+const COMPACT_ITEM_WIDTH: u16 = 5;
+
+fn horizontal_menu_area(app: &App, size: Rect) -> Rect {
+    let total_width = HORIZONTAL_ITEM_WIDTH * app.actions.len() as u16 + 4; // +4 for borders
+    let height = 5u16;
+
+    Rect {
+        x: (size.width.saturating_sub(total_width)) / 2,
+        y: menu_y(&app.config.layout, size, height),
+        width: total_width,
+        height,
+    }
+}
+
+fn compact_menu_area(app: &App, size: Rect) -> Rect {
+    let total_width = COMPACT_ITEM_WIDTH * app.actions.len() as u16 + 4;
+    let height = 4u16;
+
+    Rect {
+        x: (size.width.saturating_sub(total_width)) / 2,
+        y: menu_y(&app.config.layout, size, height),
+        width: total_width,
+        height,
+    }
 }
 
 fn render_horizontal_layout(f: &mut Frame, app: &App, size: Rect) {
@@ -5857,23 +10921,11 @@ fn render_horizontal_layout(f: &mut Frame, app: &App, size: Rect) {
     let selected_fg = parse_color(&config.colors.selected_fg);
     let selected_bg = parse_color(&config.colors.selected_bg);
     let selected_modifier = parse_modifier(&config.colors.selected_modifier);
-    let border_color = parse_color(&config.colors.border);
+    let border_color = app.menu_border_color();
 
     // Calculate layout
-    let action_count = app.actions.len() as u16;
-    let item_width = 15u16; // Fixed width for each item
-    let total_width = item_width * action_count + 4; // +4 for borders
-    let height = 5u16;
-
-    let x = (size.width.saturating_sub(total_width)) / 2;
-    let y = (size.height.saturating_sub(height)) / 2;
-
-    let menu_area = Rect {
-        x,
-        y,
-        width: total_width,
-        height,
-    };
+    let item_width = HORIZONTAL_ITEM_WIDTH;
+    let menu_area = horizontal_menu_area(app, size);
 
     // Create border
     let border_type = Borders::ALL;
@@ -5886,9 +10938,11 @@ fn render_horizontal_layout(f: &mut Frame, app: &App, size: Rect) {
         } else {
             Borders::NONE
         })
-        .title(config.title.as_str())
+        .title(app.title.as_str())
+        .title_style(app.menu_title_style())
         .title_alignment(title_alignment)
-        .border_style(Style::default().fg(border_color));
+        .border_style(Style::default().fg(border_color))
+        .border_set(border_set(&config.border));
 
     f.render_widget(block.clone(), menu_area);
 
@@ -5918,7 +10972,18 @@ fn render_horizontal_layout(f: &mut Frame, app: &App, size: Rect) {
             Style::default().fg(fg_color)
         };
 
-        let content = format!("\n  {}\n  {}\n", action.icon, action.label);
+        let label = if config.show_shortcut && !action.shortcut.is_empty() {
+            format!("[{}] {}", action.shortcut, action.label)
+        } else {
+            action.label.clone()
+        };
+        let label = accessible_text(app, i, &label);
+        let icon = action.padded_icon(config);
+        let content = match config.icon_position.as_str() {
+            "right" => format!("\n  {}\n  {}\n", label, icon),
+            "none" => format!("\n\n  {}\n", label),
+            _ => format!("\n  {}\n  {}\n", icon, label),
+        };
         let paragraph = Paragraph::new(content)
             .alignment(Alignment::Center)
             .style(style);
@@ -5932,28 +10997,15 @@ fn render_grid_layout(f: &mut Frame, app: &App, size: Rect) {
 
     // Parse colors
     let fg_color = parse_color(&config.colors.foreground);
-    let selected_fg = parse_color(&config.colors.selected_fg);
-    let selected_bg = parse_color(&config.colors.selected_bg);
-    let selected_modifier = parse_modifier(&config.colors.selected_modifier);
-    let border_color = parse_color(&config.colors.border);
-
-    let cols = 2u16;
-    let rows = (app.actions.len() as u16).div_ceil(cols).max(1);
-
-    let cell_width = 20u16;
-    let cell_height = 4u16;
-    let total_width = cell_width * cols + 4;
-    let total_height = cell_height * rows + 4;
-
-    let x = (size.width.saturating_sub(total_width)) / 2;
-    let y = (size.height.saturating_sub(total_height)) / 2;
+    let selected_fg = parse_color(&config.colors.selected_fg);
+    let selected_bg = parse_color(&config.colors.selected_bg);
+    let selected_modifier = parse_modifier(&config.colors.selected_modifier);
+    let border_color = app.menu_border_color();
 
-    let menu_area = Rect {
-        x,
-        y,
-        width: total_width,
-        height: total_height,
-    };
+    let cols = grid_columns(app) as u16;
+    let cell_width = grid_cell_width(app, size);
+    let cell_height = GRID_CELL_HEIGHT;
+    let menu_area = grid_menu_area(app, size);
 
     // Create border
     let border_type = Borders::ALL;
@@ -5966,9 +11018,11 @@ fn render_grid_layout(f: &mut Frame, app: &App, size: Rect) {
         } else {
             Borders::NONE
         })
-        .title(config.title.as_str())
+        .title(app.title.as_str())
+        .title_style(app.menu_title_style())
         .title_alignment(title_alignment)
-        .border_style(Style::default().fg(border_color));
+        .border_style(Style::default().fg(border_color))
+        .border_set(border_set(&config.border));
 
     f.render_widget(block.clone(), menu_area);
 
@@ -5999,7 +11053,12 @@ fn render_grid_layout(f: &mut Frame, app: &App, size: Rect) {
             Style::default().fg(fg_color)
         };
 
-        let content = format!(" {} {}\n [{}] ", action.icon, action.label, action.shortcut);
+        let label = accessible_text(app, i, &action.with_icon(&action.label, config));
+        let content = if config.show_shortcut && !action.shortcut.is_empty() {
+            format!(" {}\n [{}] ", label, action.shortcut)
+        } else {
+            format!(" {}\n", label)
+        };
         let paragraph = Paragraph::new(content)
             .alignment(Alignment::Center)
             .style(style);
@@ -6008,6 +11067,60 @@ fn render_grid_layout(f: &mut Frame, app: &App, size: Rect) {
     }
 }
 
+const GRID_CELL_HEIGHT: u16 = 4;
+
+/// Columns of the grid layout: `layout.grid_columns`, or picked from the action count
+fn grid_columns(app: &App) -> usize {
+    let count = app.actions.len().max(1);
+    match app.config.layout.grid_columns {
+        Some(cols) => (cols as usize).clamp(1, count),
+        None => match count {
+            1 => 1,
+            2..=4 => 2,
+            5..=9 => 3,
+            _ => 4,
+        },
+    }
+}
+
+/// Width of a grid cell, wide enough for the longest label if the terminal allows
+fn grid_cell_width(app: &App, size: Rect) -> u16 {
+    let config = &app.config;
+    let longest = app
+        .actions
+        .iter()
+        .enumerate()
+        .map(|(i, action)| {
+            let label = display_width(&accessible_text(
+                app,
+                i,
+                &action.with_icon(&action.label, config),
+            ));
+            let shortcut = display_width(&action.shortcut) + 4;
+            label.max(shortcut)
+        })
+        .max()
+        .unwrap_or(0);
+    let cols = grid_columns(app) as u16;
+    let max_width = (size.width.saturating_sub(4) / cols).max(1);
+    (longest + 4).max(12).min(max_width)
+}
+
+/// Outer area of the grid layout, including its border
+fn grid_menu_area(app: &App, size: Rect) -> Rect {
+    let cols = grid_columns(app) as u16;
+    let rows = (app.actions.len() as u16).div_ceil(cols).max(1);
+    let total_width = grid_cell_width(app, size) * cols + 4;
+    let total_height = GRID_CELL_HEIGHT * rows + 4;
+
+    Rect {
+        x: (size.width.saturating_sub(total_width)) / 2,
+        y: menu_y(&app.config.layout, size, total_height),
+        width: total_width,
+        height: total_height,
+    }
+}
+
 fn render_compact_layout(f: &mut Frame, app: &App, size: Rect) {
     let config = &app.config;
 
@@ -6016,23 +11129,11 @@ fn render_compact_layout(f: &mut Frame, app: &App, size: Rect) {
     let selected_fg = parse_color(&config.colors.selected_fg);
     let selected_bg = parse_color(&config.colors.selected_bg);
     let selected_modifier = parse_modifier(&config.colors.selected_modifier);
-    let border_color = parse_color(&config.colors.border);
+    let border_color = app.menu_border_color();
 
     // Compact horizontal layout with just icons
-    let action_count = app.actions.len() as u16;
-    let item_width = 5u16;
-    let total_width = item_width * action_count + 4;
-    let height = 4u16;
-
-    let x = (size.width.saturating_sub(total_width)) / 2;
-    let y = (size.height.saturating_sub(height)) / 2;
-
-    let menu_area = Rect {
-        x,
-        y,
-        width: total_width,
-        height,
-    };
+    let item_width = COMPACT_ITEM_WIDTH;
+    let menu_area = compact_menu_area(app, size);
 
     // Create border
     let border_type = Borders::ALL;
@@ -6045,9 +11146,11 @@ fn render_compact_layout(f: &mut Frame, app: &App, size: Rect) {
         } else {
             Borders::NONE
         })
-        .title(config.title.as_str())
+        .title(app.title.as_str())
+        .title_style(app.menu_title_style())
         .title_alignment(title_alignment)
-        .border_style(Style::default().fg(border_color));
+        .border_style(Style::default().fg(border_color))
+        .border_set(border_set(&config.border));
 
     f.render_widget(block.clone(), menu_area);
 
@@ -6077,7 +11180,17 @@ fn render_compact_layout(f: &mut Frame, app: &App, size: Rect) {
             Style::default().fg(fg_color)
         };
 
-        let content = format!("\n{}\n", action.icon);
+        // Without icons, fall back to the shortcut or the label's first letter
+        let content = if config.icon_position == "none" {
+            let key = if action.shortcut.is_empty() {
+                action.label.chars().take(1).collect()
+            } else {
+                action.shortcut.clone()
+            };
+            format!("\n{}\n", key)
+        } else {
+            format!("\n{}\n", action.icon)
+        };
         let paragraph = Paragraph::new(content)
             .alignment(Alignment::Center)
             .style(style);
@@ -6086,9 +11199,15 @@ fn render_compact_layout(f: &mut Frame, app: &App, size: Rect) {
     }
 }
 
-fn render_confirmation_dialog(f: &mut Frame, app: &App, action_index: usize, size: Rect) {
+fn render_confirmation_dialog(
+    f: &mut Frame,
+    app: &App,
+    action_ref: ActionRef,
+    focus: ConfirmFocus,
+    size: Rect,
+) {
     let config = &app.config;
-    let Some(action) = app.actions.get(action_index) else {
+    let Some(action) = app.action_at(action_ref) else {
         return;
     };
 
@@ -6100,9 +11219,29 @@ fn render_confirmation_dialog(f: &mut Frame, app: &App, action_index: usize, siz
     let border_color = parse_color(&config.colors.border);
 
     // Calculate dialog size
-    let message = format!("Confirm {}?", action.label);
-    let width = (message.chars().count() as u16 + 10).max(30).min(size.width - 4);
-    let height = 7u16;
+    let message_lines: Vec<String> = if app.easter_egg.secret_mode {
+        SKULL.iter().map(|line| line.to_string()).collect()
+    } else {
+        vec![format!("Confirm {}?", action.label)]
+    };
+    let message_rows = message_lines.len() as u16;
+    let message_width = message_lines.iter().map(|l| display_width(l)).max();
+    let subtitle = config
+        .show_host_in_confirm
+        .then(|| format!("on {}@{}", current_user(), current_hostname()));
+    let open_windows = app
+        .open_windows
+        .map(|count| (count, format!("{} applications are open.", count)));
+    let text_width = subtitle
+        .as_ref()
+        .map_or(0, |s| display_width(s))
+        .max(open_windows.as_ref().map_or(0, |(_, s)| display_width(s)))
+        .max(message_width.unwrap_or(0));
+    let width = (text_width + 10).max(30).min(size.width - 4);
+    let remaining_secs = app.confirm_remaining_secs();
+    let info_rows = subtitle.is_some() as u16 + open_windows.is_some() as u16;
+    let extra_rows = info_rows.saturating_sub(1) + remaining_secs.is_some() as u16;
+    let height = 6 + message_rows + extra_rows;
 
     let x = (size.width.saturating_sub(width)) / 2;
     let y = (size.height.saturating_sub(height)) / 2;
@@ -6115,8 +11254,7 @@ fn render_confirmation_dialog(f: &mut Frame, app: &App, action_index: usize, siz
     };
 
     // Clear background under dialog
-    let clear = Block::default().style(Style::default().bg(Color::Black));
-    f.render_widget(clear, dialog_area);
+    mask_area(f, dialog_area, Color::Black);
 
     // Create dialog border
     let border_type = Borders::ALL;
@@ -6125,13 +11263,14 @@ fn render_confirmation_dialog(f: &mut Frame, app: &App, action_index: usize, siz
         .borders(border_type)
         .title(" Confirm ")
         .title_alignment(Alignment::Center)
-        .border_style(Style::default().fg(border_color));
+        .border_style(Style::default().fg(border_color))
+        .border_set(border_set(&config.border));
 
     let inner = block.inner(dialog_area);
     f.render_widget(block, dialog_area);
 
     // Render message
-    let message_paragraph = Paragraph::new(message)
+    let message_paragraph = Paragraph::new(message_lines.join("\n"))
         .alignment(Alignment::Center)
         .style(Style::default().fg(fg_color));
     let message_area = Rect {
@@ -6140,38 +11279,90 @@ fn render_confirmation_dialog(f: &mut Frame, app: &App, action_index: usize, siz
         width: inner.width,
         height: 1,
     };
-    f.render_widget(message_paragraph, message_area);
+    f.render_widget(
+        message_paragraph,
+        Rect {
+            height: message_rows,
+            ..message_area
+        },
+    );
+
+    // Remind which machine is affected, e.g. over SSH
+    if let Some(subtitle) = subtitle {
+        let subtitle_paragraph = Paragraph::new(subtitle)
+            .alignment(Alignment::Center)
+            .style(Style::default().fg(parse_color(&config.colors.help_fg)));
+        let subtitle_area = Rect {
+            y: inner.y + 1 + message_rows,
+            ..message_area
+        };
+        f.render_widget(subtitle_paragraph, subtitle_area);
+    }
+
+    // Warn about applications the action would close
+    if let Some((count, text)) = open_windows {
+        let color = if count > config.warn_open_windows_threshold {
+            Color::Yellow
+        } else {
+            parse_color("gray")
+        };
+        let windows_area = Rect {
+            y: inner.y + message_rows + info_rows,
+            ..message_area
+        };
+        f.render_widget(
+            Paragraph::new(text)
+                .alignment(Alignment::Center)
+                .style(Style::default().fg(color)),
+            windows_area,
+        );
+    }
 
-    // Render Yes/No options - No is default (highlighted)
-    let yes_style = Style::default().fg(fg_color);
-    let no_style = Style::default()
+    // Render Yes/No options - the focused button is highlighted
+    let focused_style = Style::default()
         .fg(selected_fg)
         .bg(selected_bg)
         .add_modifier(selected_modifier);
+    let unfocused_style = Style::default().fg(fg_color);
+    let (yes_style, no_style) = match focus {
+        ConfirmFocus::Yes => (focused_style, unfocused_style),
+        ConfirmFocus::No => (unfocused_style, focused_style),
+    };
 
     let options_text = Line::from(vec![
-        Span::styled("[Y] Yes", yes_style),
+        Span::styled(config.confirm_yes_label.as_str(), yes_style),
         Span::raw("   "),
-        Span::styled("[N] No", no_style),
+        Span::styled(config.confirm_no_label.as_str(), no_style),
     ]);
 
     let options_paragraph = Paragraph::new(options_text).alignment(Alignment::Center);
     let options_area = Rect {
         x: inner.x,
-        y: inner.y + 3,
+        y: inner.y + 1 + message_rows + info_rows.max(1),
         width: inner.width,
         height: 1,
     };
     f.render_widget(options_paragraph, options_area);
 
+    if let Some(remaining_secs) = remaining_secs {
+        let countdown_paragraph = Paragraph::new(format!("(auto-cancel in {}s)", remaining_secs))
+            .alignment(Alignment::Center)
+            .style(Style::default().fg(parse_color(&config.colors.help_fg)));
+        let countdown_area = Rect {
+            y: options_area.y + 1,
+            ..options_area
+        };
+        f.render_widget(countdown_paragraph, countdown_area);
+    }
+
     // Render help text
-    let help_text = "Y to confirm, N/Enter to cancel, Esc to cancel";
+    let help_text = "←/→ switch, Enter to choose, Esc to cancel";
     let help_paragraph = Paragraph::new(help_text)
         .alignment(Alignment::Center)
         .style(Style::default().fg(parse_color("gray")));
     let help_area = Rect {
         x: inner.x,
-        y: inner.y + 5,
+        y: options_area.y + 2,
         width: inner.width,
         height: 1,
     };
@@ -6181,12 +11372,12 @@ fn render_confirmation_dialog(f: &mut Frame, app: &App, action_index: usize, siz
 fn render_grace_period(
     f: &mut Frame,
     app: &App,
-    action_index: usize,
+    action_ref: ActionRef,
     remaining_secs: u64,
     size: Rect,
 ) {
     let config = &app.config;
-    let Some(action) = app.actions.get(action_index) else {
+    let Some(action) = app.action_at(action_ref) else {
         return;
     };
 
@@ -6196,15 +11387,358 @@ fn render_grace_period(
     let icon_color = parse_color(&config.colors.icon_color);
 
     // Build message from template
-    let message = config
-        .grace_period
-        .message_template
-        .replace("{action}", &action.label)
-        .replace("{seconds}", &remaining_secs.to_string());
+    let message = if app.easter_egg.secret_mode {
+        DRAMATIC_QUOTES[app.easter_egg.quote_index % DRAMATIC_QUOTES.len()].to_string()
+    } else {
+        config
+            .grace_period
+            .message_template
+            .replace("{action}", &action.label)
+            .replace("{seconds}", &remaining_secs.to_string())
+    };
+
+    // Rows taken by the countdown visualization
+    let progress_height = match config.grace_period.progress_style.as_str() {
+        "ring" => 4u16,
+        "digits" => 5u16,
+        _ => 1u16,
+    };
+
+    // Calculate dialog size
+    let width = (display_width(&message) + 10).max(40).min(size.width - 4);
+    let height = 8u16 + progress_height;
+
+    let x = (size.width.saturating_sub(width)) / 2;
+    let y = (size.height.saturating_sub(height)) / 2;
+
+    let dialog_area = Rect {
+        x,
+        y,
+        width,
+        height,
+    };
+
+    // Clear background under dialog
+    mask_area(f, dialog_area, Color::Black);
+
+    // Create dialog border
+    let border_type = Borders::ALL;
+
+    let block = Block::default()
+        .borders(border_type)
+        .title(" Grace Period ")
+        .title_alignment(Alignment::Center)
+        .border_style(Style::default().fg(border_color))
+        .border_set(border_set(&config.border));
+
+    let inner = block.inner(dialog_area);
+    f.render_widget(block, dialog_area);
+
+    // Render icon
+    let icon_text = format!("{} ", action.icon);
+    let icon_paragraph = Paragraph::new(icon_text)
+        .alignment(Alignment::Center)
+        .style(Style::default().fg(icon_color));
+    let icon_area = Rect {
+        x: inner.x,
+        y: inner.y + 1,
+        width: inner.width,
+        height: 1,
+    };
+    f.render_widget(icon_paragraph, icon_area);
+
+    // Render message
+    let message_paragraph = Paragraph::new(message)
+        .alignment(Alignment::Center)
+        .style(Style::default().fg(fg_color).add_modifier(Modifier::BOLD));
+    let message_area = Rect {
+        x: inner.x,
+        y: inner.y + 3,
+        width: inner.width,
+        height: 1,
+    };
+    f.render_widget(message_paragraph, message_area);
+
+    // Render countdown
+    let total_secs = config.grace_period.duration_secs as f64;
+    let progress = remaining_secs as f64 / total_secs;
+
+    let bar_color = if progress > 0.6 {
+        Color::Green
+    } else if progress > 0.3 {
+        Color::Yellow
+    } else {
+        Color::Red
+    };
+
+    match config.grace_period.progress_style.as_str() {
+        "ring" => {
+            let ring_area = Rect {
+                x: inner.x + inner.width.saturating_sub(8) / 2,
+                y: inner.y + 5,
+                width: 8.min(inner.width),
+                height: 4,
+            };
+            draw_arc(f, ring_area, progress as f32, bar_color);
+
+            // Seconds left in the middle of the ring
+            let seconds_paragraph = Paragraph::new(remaining_secs.to_string())
+                .alignment(Alignment::Center)
+                .style(Style::default().fg(bar_color).add_modifier(Modifier::BOLD));
+            let seconds_area = Rect {
+                x: ring_area.x,
+                y: ring_area.y + 1,
+                width: ring_area.width,
+                height: 2,
+            };
+            f.render_widget(seconds_paragraph, seconds_area);
+        }
+        "digits" => {
+            let lines: Vec<Line> = big_digit_rows(remaining_secs)
+                .into_iter()
+                .map(Line::from)
+                .collect();
+
+            let digits_paragraph = Paragraph::new(lines)
+                .alignment(Alignment::Center)
+                .style(Style::default().fg(bar_color));
+            let digits_area = Rect {
+                x: inner.x,
+                y: inner.y + 5,
+                width: inner.width,
+                height: 5,
+            };
+            f.render_widget(digits_paragraph, digits_area);
+        }
+        _ => {
+            let bar_width = inner.width.saturating_sub(4) as usize;
+            let filled = (bar_width as f64 * progress) as usize;
+            let empty = bar_width.saturating_sub(filled);
+
+            let filled_char = "█";
+            let empty_char = "░";
+
+            let bar = format!("{}{}", filled_char.repeat(filled), empty_char.repeat(empty));
+
+            let bar_paragraph = Paragraph::new(bar)
+                .alignment(Alignment::Center)
+                .style(Style::default().fg(bar_color));
+            let bar_area = Rect {
+                x: inner.x + 2,
+                y: inner.y + 5,
+                width: inner.width.saturating_sub(4),
+                height: 1,
+            };
+            f.render_widget(bar_paragraph, bar_area);
+        }
+    }
+
+    // Render help text
+    let help_text = "Press any key to cancel";
+    let help_paragraph = Paragraph::new(help_text)
+        .alignment(Alignment::Center)
+        .style(Style::default().fg(parse_color("gray")));
+    let help_area = Rect {
+        x: inner.x,
+        y: inner.y + 6 + progress_height,
+        width: inner.width,
+        height: 1,
+    };
+    f.render_widget(help_paragraph, help_area);
+}
+
+/// 3x5 pixel font for the "digits" countdown, one 3-bit row per line
+const DIGIT_FONT: [[u8; 5]; 10] = [
+    [0b111, 0b101, 0b101, 0b101, 0b111], // 0
+    [0b010, 0b110, 0b010, 0b010, 0b111], // 1
+    [0b111, 0b001, 0b111, 0b100, 0b111], // 2
+    [0b111, 0b001, 0b111, 0b001, 0b111], // 3
+    [0b101, 0b101, 0b111, 0b001, 0b001], // 4
+    [0b111, 0b100, 0b111, 0b001, 0b111], // 5
+    [0b111, 0b100, 0b111, 0b101, 0b111], // 6
+    [0b111, 0b001, 0b010, 0b010, 0b010], // 7
+    [0b111, 0b101, 0b111, 0b101, 0b111], // 8
+    [0b111, 0b101, 0b111, 0b001, 0b111], // 9
+];
+
+/// Render a number with `DIGIT_FONT`, returning its five rows of text
+fn big_digit_rows(value: u64) -> Vec<String> {
+    let digits: Vec<usize> = value
+        .to_string()
+        .chars()
+        .filter_map(|c| c.to_digit(10))
+        .map(|d| d as usize)
+        .collect();
+
+    (0..5)
+        .map(|row| {
+            let glyphs: Vec<String> = digits
+                .iter()
+                .map(|&d| {
+                    (0..3)
+                        .map(|col| match DIGIT_FONT[d][row] & (0b100 >> col) {
+                            0 => ' ',
+                            _ => '█',
+                        })
+                        .collect()
+                })
+                .collect();
+            glyphs.join(" ")
+        })
+        .collect()
+}
+
+/// Braille cells from one dot to full, used to fill the ring gradually
+const ARC_GLYPHS: [char; 8] = ['⠁', '⠃', '⠇', '⡇', '⣇', '⣏', '⣟', '⣿'];
+
+/// Draw a ring of braille cells inside `center`, filled clockwise from the top
+fn draw_arc(f: &mut Frame, center: Rect, fraction: f32, color: Color) {
+    let half_w = center.width as f32 / 2.0;
+    let half_h = center.height as f32 / 2.0;
+    if half_w == 0.0 || half_h == 0.0 {
+        return;
+    }
+
+    // Cells near the ellipse outline, sorted clockwise by angle
+    let mut cells: Vec<(f32, u16, u16)> = Vec::new();
+    for row in 0..center.height {
+        for col in 0..center.width {
+            let dx = (col as f32 + 0.5 - half_w) / half_w;
+            let dy = (row as f32 + 0.5 - half_h) / half_h;
+            let dist = (dx * dx + dy * dy).sqrt();
+            if (0.7..=1.1).contains(&dist) {
+                let angle = dx.atan2(-dy).rem_euclid(std::f32::consts::TAU);
+                cells.push((angle, center.x + col, center.y + row));
+            }
+        }
+    }
+    cells.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+    let filled = fraction.clamp(0.0, 1.0) * cells.len() as f32;
+    for (i, &(_, x, y)) in cells.iter().enumerate() {
+        // Eighths of this cell that are still filled
+        let level = ((filled - i as f32) * ARC_GLYPHS.len() as f32).clamp(0.0, 8.0) as usize;
+        let (ch, style) = if level == 0 {
+            (ARC_GLYPHS[0], Style::default().fg(Color::DarkGray))
+        } else {
+            (ARC_GLYPHS[level - 1], Style::default().fg(color))
+        };
+        let span = Span::styled(ch.to_string(), style);
+        let paragraph = Paragraph::new(Line::from(vec![span]));
+        f.render_widget(paragraph, Rect::new(x, y, 1, 1));
+    }
+}
+
+const SPINNER_FRAMES: [char; 10] = ['⠋', '⠙', '⠹', '⠸', '⠼', '⠴', '⠦', '⠧', '⠇', '⠏'];
+
+fn render_running_dialog(
+    f: &mut Frame,
+    app: &App,
+    action_ref: ActionRef,
+    started: Instant,
+    size: Rect,
+) {
+    let config = &app.config;
+    let Some(action) = app.action_at(action_ref) else {
+        return;
+    };
+
+    // Parse colors
+    let fg_color = parse_color(&config.colors.foreground);
+    let border_color = parse_color(&config.colors.border);
+
+    let elapsed = started.elapsed();
+    let spinner = SPINNER_FRAMES[(elapsed.as_millis() / 100) as usize % SPINNER_FRAMES.len()];
+    let message = format!("{} Running {}...", spinner, action.label);
+    let elapsed_text = format!("{}s", elapsed.as_secs());
+
+    // Calculate dialog size
+    let width = (display_width(&message) + 10).max(30).min(size.width - 4);
+    let height = 6u16;
+
+    let x = (size.width.saturating_sub(width)) / 2;
+    let y = (size.height.saturating_sub(height)) / 2;
+
+    let dialog_area = Rect {
+        x,
+        y,
+        width,
+        height,
+    };
+
+    // Clear background under dialog
+    mask_area(f, dialog_area, Color::Black);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(" Running ")
+        .title_alignment(Alignment::Center)
+        .border_style(Style::default().fg(border_color))
+        .border_set(border_set(&config.border));
+
+    let inner = block.inner(dialog_area);
+    f.render_widget(block, dialog_area);
+
+    let message_paragraph = Paragraph::new(message)
+        .alignment(Alignment::Center)
+        .style(Style::default().fg(fg_color).add_modifier(Modifier::BOLD));
+    let message_area = Rect {
+        x: inner.x,
+        y: inner.y + 1,
+        width: inner.width,
+        height: 1,
+    };
+    f.render_widget(message_paragraph, message_area);
+
+    let elapsed_paragraph = Paragraph::new(elapsed_text)
+        .alignment(Alignment::Center)
+        .style(Style::default().fg(fg_color));
+    let elapsed_area = Rect {
+        x: inner.x,
+        y: inner.y + 2,
+        width: inner.width,
+        height: 1,
+    };
+    f.render_widget(elapsed_paragraph, elapsed_area);
+}
+
+fn render_error_dialog(
+    f: &mut Frame,
+    app: &App,
+    action_ref: ActionRef,
+    message: &str,
+    focus: ConfirmFocus,
+    copied: bool,
+    size: Rect,
+) {
+    let config = &app.config;
+    let command = app
+        .action_at(action_ref)
+        .map(|action| format!("Command: {}", action.command_line()))
+        .unwrap_or_default();
+
+    // Parse colors
+    let fg_color = parse_color(&config.colors.foreground);
+    let help_fg = parse_color(&config.colors.help_fg);
+    let selected_fg = parse_color(&config.colors.selected_fg);
+    let selected_bg = parse_color(&config.colors.selected_bg);
+    let selected_modifier = parse_modifier(&config.colors.selected_modifier);
+    let border_color = parse_color(&config.colors.border);
+
+    let hint = if copied {
+        "Copied to clipboard"
+    } else if clipboard_command().is_some() {
+        "←/→ switch, Enter to choose, c to copy the error"
+    } else {
+        "←/→ switch, Enter to choose"
+    };
 
     // Calculate dialog size
-    let width = (message.chars().count() as u16 + 10).max(40).min(size.width - 4);
-    let height = 9u16;
+    let text_width = display_width(message)
+        .max(display_width(&command))
+        .max(display_width(hint));
+    let width = (text_width + 6).max(30).min(size.width - 4);
+    let height = 8u16;
 
     let x = (size.width.saturating_sub(width)) / 2;
     let y = (size.height.saturating_sub(height)) / 2;
@@ -6217,89 +11751,89 @@ fn render_grace_period(
     };
 
     // Clear background under dialog
-    let clear = Block::default().style(Style::default().bg(Color::Black));
-    f.render_widget(clear, dialog_area);
-
-    // Create dialog border
-    let border_type = Borders::ALL;
+    mask_area(f, dialog_area, Color::Black);
 
     let block = Block::default()
-        .borders(border_type)
-        .title(" Grace Period ")
+        .borders(Borders::ALL)
+        .title(" Error ")
         .title_alignment(Alignment::Center)
-        .border_style(Style::default().fg(border_color));
+        .border_style(Style::default().fg(border_color))
+        .border_set(border_set(&config.border));
 
     let inner = block.inner(dialog_area);
     f.render_widget(block, dialog_area);
 
-    // Render icon
-    let icon_text = format!("{} ", action.icon);
-    let icon_paragraph = Paragraph::new(icon_text)
-        .alignment(Alignment::Center)
-        .style(Style::default().fg(icon_color));
-    let icon_area = Rect {
-        x: inner.x,
-        y: inner.y + 1,
-        width: inner.width,
-        height: 1,
-    };
-    f.render_widget(icon_paragraph, icon_area);
-
-    // Render message
-    let message_paragraph = Paragraph::new(message)
+    let message_paragraph = Paragraph::new(message.to_string())
         .alignment(Alignment::Center)
         .style(Style::default().fg(fg_color).add_modifier(Modifier::BOLD));
     let message_area = Rect {
         x: inner.x,
-        y: inner.y + 3,
+        y: inner.y + 1,
         width: inner.width,
         height: 1,
     };
     f.render_widget(message_paragraph, message_area);
 
-    // Render countdown bar
-    let total_secs = config.grace_period.duration_secs as f64;
-    let progress = remaining_secs as f64 / total_secs;
-    let bar_width = inner.width.saturating_sub(4) as usize;
-    let filled = (bar_width as f64 * progress) as usize;
-    let empty = bar_width.saturating_sub(filled);
-
-    let filled_char = "█";
-    let empty_char = "░";
-
-    let bar = format!("{}{}", filled_char.repeat(filled), empty_char.repeat(empty));
+    let command_paragraph = Paragraph::new(command)
+        .alignment(Alignment::Center)
+        .style(Style::default().fg(parse_color("gray")));
+    let command_area = Rect {
+        y: inner.y + 2,
+        ..message_area
+    };
+    f.render_widget(command_paragraph, command_area);
 
-    let bar_color = if progress > 0.6 {
-        Color::Green
-    } else if progress > 0.3 {
-        Color::Yellow
-    } else {
-        Color::Red
+    // Retry/Cancel buttons, styled like the confirmation dialog
+    let focused_style = Style::default()
+        .fg(selected_fg)
+        .bg(selected_bg)
+        .add_modifier(selected_modifier);
+    let unfocused_style = Style::default().fg(fg_color);
+    let (retry_style, cancel_style) = match focus {
+        ConfirmFocus::Yes => (focused_style, unfocused_style),
+        ConfirmFocus::No => (unfocused_style, focused_style),
     };
+    let options_text = Line::from(vec![
+        Span::styled("[Retry]", retry_style),
+        Span::raw("   "),
+        Span::styled("[Cancel]", cancel_style),
+    ]);
+    let options_area = Rect {
+        y: inner.y + 4,
+        ..message_area
+    };
+    f.render_widget(
+        Paragraph::new(options_text).alignment(Alignment::Center),
+        options_area,
+    );
 
-    let bar_paragraph = Paragraph::new(bar)
+    let hint_paragraph = Paragraph::new(hint)
         .alignment(Alignment::Center)
-        .style(Style::default().fg(bar_color));
-    let bar_area = Rect {
-        x: inner.x + 2,
+        .style(Style::default().fg(help_fg));
+    let hint_area = Rect {
         y: inner.y + 5,
-        width: inner.width.saturating_sub(4),
-        height: 1,
+        ..message_area
     };
-    f.render_widget(bar_paragraph, bar_area);
+    f.render_widget(hint_paragraph, hint_area);
+}
 
-    // Render help text
-    let help_text = "Press any key to cancel";
-    let help_paragraph = Paragraph::new(help_text)
-        .alignment(Alignment::Center)
-        .style(Style::default().fg(parse_color("gray")));
-    let help_area = Rect {
-        x: inner.x,
-        y: inner.y + 7,
-        width: inner.width,
-        height: 1,
-    };
-    f.render_widget(help_paragraph, help_area);
+/// Centered area of the animation menu, shared with mouse handling
+fn animation_menu_area(size: Rect) -> Rect {
+    let max_item_len = ANIMATION_TYPES.iter().map(|s| s.len()).max().unwrap_or(10);
+    let width = (max_item_len as u16 + 10)
+        .max(25)
+        .min(size.width.saturating_sub(4));
+    let height = (ANIMATION_TYPES.len() as u16 + 4).min(size.height.saturating_sub(4));
+
+    let x = (size.width.saturating_sub(width)) / 2;
+    let y = (size.height.saturating_sub(height)) / 2;
+
+    Rect {
+        x,
+        y,
+        width,
+        height,
+    }
 }
 
 fn render_animation_menu(f: &mut Frame, app: &App, size: Rect) {
@@ -6312,24 +11846,10 @@ fn render_animation_menu(f: &mut Frame, app: &App, size: Rect) {
     let selected_modifier = parse_modifier(&config.colors.selected_modifier);
     let border_color = parse_color(&config.colors.border);
 
-    // Calculate menu size
-    let max_item_len = ANIMATION_TYPES.iter().map(|s| s.len()).max().unwrap_or(10);
-    let width = (max_item_len as u16 + 10).max(25).min(size.width - 4);
-    let height = (ANIMATION_TYPES.len() as u16 + 4).min(size.height - 4);
-
-    let x = (size.width.saturating_sub(width)) / 2;
-    let y = (size.height.saturating_sub(height)) / 2;
-
-    let menu_area = Rect {
-        x,
-        y,
-        width,
-        height,
-    };
+    let menu_area = animation_menu_area(size);
 
     // Clear background under menu
-    let clear = Block::default().style(Style::default().bg(Color::Black));
-    f.render_widget(clear, menu_area);
+    mask_area(f, menu_area, Color::Black);
 
     // Create border
     let border_type = Borders::ALL;
@@ -6338,7 +11858,8 @@ fn render_animation_menu(f: &mut Frame, app: &App, size: Rect) {
         .borders(border_type)
         .title(" Select Animation ")
         .title_alignment(Alignment::Center)
-        .border_style(Style::default().fg(border_color));
+        .border_style(Style::default().fg(border_color))
+        .border_set(border_set(&config.border));
 
     let inner = block.inner(menu_area);
     f.render_widget(block, menu_area);
@@ -6397,6 +11918,103 @@ fn render_animation_menu(f: &mut Frame, app: &App, size: Rect) {
     f.render_widget(help_paragraph, help_area);
 }
 
+fn render_theme_menu(f: &mut Frame, app: &App, theme_index: usize, size: Rect) {
+    let config = &app.config;
+
+    // Parse colors (these already reflect the previewed theme)
+    let fg_color = parse_color(&config.colors.foreground);
+    let selected_fg = parse_color(&config.colors.selected_fg);
+    let selected_bg = parse_color(&config.colors.selected_bg);
+    let selected_modifier = parse_modifier(&config.colors.selected_modifier);
+    let border_color = parse_color(&config.colors.border);
+
+    let entries = &app.theme_menu_entries;
+
+    // Calculate menu size
+    let max_item_len = entries.iter().map(|s| display_width(s)).max().unwrap_or(10);
+    let width = (max_item_len + 10)
+        .max(30)
+        .min(size.width.saturating_sub(4));
+    let height = (entries.len() as u16 + 4).min(size.height.saturating_sub(4));
+
+    let x = (size.width.saturating_sub(width)) / 2;
+    let y = (size.height.saturating_sub(height)) / 2;
+
+    let menu_area = Rect {
+        x,
+        y,
+        width,
+        height,
+    };
+
+    // Clear background under menu
+    mask_area(f, menu_area, Color::Black);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(" Select Theme ")
+        .title_alignment(Alignment::Center)
+        .border_style(Style::default().fg(border_color))
+        .border_set(border_set(&config.border));
+
+    let inner = block.inner(menu_area);
+    f.render_widget(block, menu_area);
+
+    // Render theme list
+    let visible_items = (inner.height.saturating_sub(2)) as usize;
+    let start_idx = if theme_index >= visible_items {
+        theme_index.saturating_sub(visible_items.saturating_sub(1))
+    } else {
+        0
+    };
+    let active_theme = app
+        .theme_menu_original
+        .as_ref()
+        .and_then(|c| c.theme.as_deref())
+        .unwrap_or(DEFAULT_THEME_ENTRY);
+
+    for (i, theme) in entries
+        .iter()
+        .enumerate()
+        .skip(start_idx)
+        .take(visible_items)
+    {
+        let prefix = if theme == active_theme { "● " } else { "  " };
+        let text = format!("{}{}", prefix, theme);
+
+        let style = if i == theme_index {
+            Style::default()
+                .fg(selected_fg)
+                .bg(selected_bg)
+                .add_modifier(selected_modifier)
+        } else {
+            Style::default().fg(fg_color)
+        };
+
+        let item_area = Rect {
+            x: inner.x + 1,
+            y: inner.y + 1 + (i - start_idx) as u16,
+            width: inner.width.saturating_sub(2),
+            height: 1,
+        };
+
+        f.render_widget(Paragraph::new(text).style(style), item_area);
+    }
+
+    // Render help text at bottom
+    let help_text = "↑↓ preview | Enter apply | Esc/q cancel";
+    let help_paragraph = Paragraph::new(help_text)
+        .alignment(Alignment::Center)
+        .style(Style::default().fg(parse_color("gray")));
+    let help_area = Rect {
+        x: inner.x,
+        y: inner.y + inner.height.saturating_sub(1),
+        width: inner.width,
+        height: 1,
+    };
+    f.render_widget(help_paragraph, help_area);
+}
+
 fn render_background_animation(f: &mut Frame, app: &App, size: Rect) {
     let config = &app.config;
 
@@ -6404,27 +12022,74 @@ fn render_background_animation(f: &mut Frame, app: &App, size: Rect) {
         return;
     }
 
-    // Use rainbow colors if easter egg is activated
+    // Cycle through the hues if the easter egg is activated
     let animation_color = if app.easter_egg.rainbow_mode {
-        Color::White
+        rainbow_color(app.animation_state.tick, 0.0)
     } else {
         parse_color(&config.animation.color)
     };
     let bg_color = parse_color(&config.colors.background);
 
-    match config.animation.animation_type.as_str() {
-        "matrix" => render_matrix(
+    let primary = &config.animation.animation_type;
+    render_animation_layer(f, app, primary, size, animation_color, bg_color);
+
+    // The blend layer goes on top; its renderer paints the whole screen, so
+    // it's merged onto a copy of the primary layer cell by cell
+    if let Some(blend) = app.blend_animation() {
+        let primary_layer = f.buffer_mut().clone();
+        render_animation_layer(f, app, blend, size, animation_color, bg_color);
+        let opacity = config.animation.blend_opacity;
+        merge_blend_layer(f.buffer_mut(), &primary_layer, size, opacity);
+    }
+
+    // Dialogs draw their own background; only the menu needs clearing
+    let menu_shown = matches!(
+        app.state,
+        AppState::Selecting | AppState::HelpOverlay | AppState::SubMenu { .. }
+    );
+    if config.animation_behind_menu && menu_shown {
+        let center_area = match config.layout_mode.as_str() {
+            "horizontal" => Some(horizontal_menu_area(app, size)),
+            "grid" => Some(grid_menu_area(app, size)),
+            "compact" => Some(compact_menu_area(app, size)),
+            // A fullscreen menu covers the whole screen, masking it would
+            // hide the animation altogether
+            _ if config.layout.fullscreen => None,
+            _ => Some(vertical_menu_area(app, size, config.layout.auto_scale)),
+        };
+        if let Some(center_area) = center_area {
+            mask_area(f, center_area.intersection(size), bg_color);
+        }
+    }
+
+    apply_background_opacity(f, size, config.colors.background_opacity);
+}
+
+/// Draw one animation layer: the primary `animation_type` or the `animation_blend` one
+fn render_animation_layer(
+    f: &mut Frame,
+    app: &App,
+    kind: &str,
+    size: Rect,
+    animation_color: Color,
+    bg_color: Color,
+) {
+    match kind {
+        "matrix" | "matrix_vertical" | "matrix_diagonal" => render_matrix(
             f,
             &app.animation_state,
             size,
             animation_color,
             bg_color,
             app.easter_egg.rainbow_mode,
+            matrix_direction(kind, &app.config.animation),
         ),
         "rain" => render_rain(f, &app.animation_state, size, animation_color, bg_color),
+        "rain_window" => render_rain_window(f, &app.animation_state, size),
         "thunder" => render_thunder(f, &app.animation_state, size, animation_color, bg_color),
         "snow" => render_snow(f, &app.animation_state, size, animation_color, bg_color),
         "stars" => render_stars(f, &app.animation_state, size, animation_color, bg_color),
+        "starfield_3d" => render_starfield_3d(f, &app.animation_state, size),
         "fireflies" => render_fireflies(
             f,
             &app.animation_state,
@@ -6447,6 +12112,7 @@ fn render_background_animation(f: &mut Frame, app: &App, size: Rect) {
         ),
         "heartbeat" => render_heartbeat(f, app, size, bg_color),
         "plasma" => render_plasma(f, &app.animation_state, size),
+        "plasma_pulse" => render_plasma_pulse(f, &app.animation_state, size),
         "scanlines" => render_scanlines(f, &app.animation_state, size, animation_color),
         "aurora" => render_aurora(f, &app.animation_state, size),
         "autumn" => render_autumn(f, &app.animation_state, size),
@@ -6454,7 +12120,16 @@ fn render_background_animation(f: &mut Frame, app: &App, size: Rect) {
         "synthwave" => render_synthwave(f, &app.animation_state, size, animation_color),
         "smoke" => render_smoke(f, &app.animation_state, size),
         "gradient_flow" => render_gradient_flow(f, &app.animation_state, size),
-        "constellation" => render_constellation(f, &app.animation_state, size, animation_color),
+        "constellation" => {
+            let connect_distance = app.config.animation.constellation_connect_distance;
+            render_constellation(
+                f,
+                &app.animation_state,
+                size,
+                animation_color,
+                connect_distance,
+            )
+        }
         "fish_tank" => render_fish_tank(f, &app.animation_state, size),
         "typing_code" => render_typing_code(f, &app.animation_state, size, animation_color),
         "vortex" => render_vortex(f, &app.animation_state, size, animation_color),
@@ -6462,7 +12137,16 @@ fn render_background_animation(f: &mut Frame, app: &App, size: Rect) {
         "flow_field" => render_flow_field(f, &app.animation_state, size),
         "morse" => render_morse(f, &app.animation_state, size, animation_color),
         "lissajous" => render_lissajous(f, &app.animation_state, size),
-        "game_of_life" => render_game_of_life(f, &app.animation_state, size),
+        "game_of_life" => {
+            render_game_of_life(f, &app.animation_state, size, animation_color, bg_color)
+        }
+        "cellular_automata" => {
+            render_cellular_automata(f, &app.animation_state, size, animation_color, bg_color)
+        }
+        "plasma_globe" => {
+            let radius = plasma_globe_radius(size, &app.config.animation);
+            render_plasma_globe(f, &app.animation_state, size, radius)
+        }
         "matrix_cjk" => render_matrix_cjk(
             f,
             &app.animation_state,
@@ -6512,10 +12196,119 @@ fn render_background_animation(f: &mut Frame, app: &App, size: Rect) {
         "glitch" => render_glitch(f, &app.animation_state, size),
         "old_film" => render_old_film(f, &app.animation_state, size),
         "thermal" => render_thermal(f, &app.animation_state, size),
+        "typewriter" => render_typewriter(f, app, size, animation_color),
+        "clock" => render_clock(f, &app.animation_state, size, animation_color),
+        "waveform" => render_waveform(f, app, size, animation_color),
+        "northern_lights" => render_northern_lights(f, &app.animation_state, size),
+        "dvd_logo" => render_dvd_logo(f, &app.animation_state, size),
+        "bouncing_balls" => render_bouncing_balls(f, &app.animation_state, size),
+        "aurora_borealis" => {
+            let glow = app.config.animation.aurora_glow;
+            render_aurora_borealis(f, &app.animation_state, size, glow)
+        }
         _ => {}
     }
 }
 
+/// `color` faded towards black; opacity 255 keeps it, 0 gives black
+fn dim_color(color: Color, opacity: u8) -> Color {
+    let (r, g, b) = alpha_blend(color_rgb(color), BLEND_BASE, opacity);
+    Color::Rgb(r, g, b)
+}
+
+/// RGB value of any terminal color; unknown named colors count as white
+fn color_rgb(color: Color) -> (u8, u8, u8) {
+    match color {
+        Color::Rgb(r, g, b) => (r, g, b),
+        Color::Indexed(index) => xterm_color_to_rgb(index),
+        named => XTERM_SYSTEM_COLORS
+            .iter()
+            .find(|(system, _)| *system == named)
+            .map_or((255, 255, 255), |&(_, rgb)| rgb),
+    }
+}
+
+/// Lay the blend layer just drawn into `buffer` over `primary`: blank cells
+/// show the primary layer, glyphs keep its background and have their color
+/// faded toward it by `opacity`
+fn merge_blend_layer(buffer: &mut Buffer, primary: &Buffer, area: Rect, opacity: u8) {
+    for y in area.top()..area.bottom() {
+        for x in area.left()..area.right() {
+            let under = &primary[(x, y)];
+            let cell = &mut buffer[(x, y)];
+            if cell.symbol().trim().is_empty() {
+                *cell = under.clone();
+                continue;
+            }
+            cell.set_bg(under.bg);
+            if cell.fg != Color::Reset {
+                let behind = match under.bg {
+                    Color::Reset => BLEND_BASE,
+                    bg => color_rgb(bg),
+                };
+                let (r, g, b) = alpha_blend(color_rgb(cell.fg), behind, opacity);
+                cell.set_fg(Color::Rgb(r, g, b));
+            }
+        }
+    }
+}
+
+/// Repaint `exclude` with `bg`, removing the animation and anything else drawn there
+fn mask_area(f: &mut Frame, exclude: Rect, bg: Color) {
+    f.render_widget(Clear, exclude);
+    f.render_widget(Block::default().style(Style::default().bg(bg)), exclude);
+}
+
+/// Cell at animation position (x, y), or None when it lies outside `size`.
+/// A bare `as u16` would draw negative positions in the first row or column.
+fn cell_at(x: f32, y: f32, size: Rect) -> Option<(u16, u16)> {
+    let inside = (0.0..size.width as f32).contains(&x) && (0.0..size.height as f32).contains(&y);
+    inside.then_some((x as u16, y as u16))
+}
+
+/// Fully saturated color whose hue advances one degree per animation tick
+fn rainbow_color(tick: u64, phase_offset: f32) -> Color {
+    let (r, g, b) = hsl_to_rgb((tick % 360) as f32 + phase_offset, 1.0, 0.5);
+    Color::Rgb(r, g, b)
+}
+
+/// Neutral dark color that translucent animation backgrounds are blended against
+const BLEND_BASE: (u8, u8, u8) = (0, 0, 0);
+
+/// Mix `fg` over `bg`; alpha 255 gives `fg`, 0 gives `bg`
+fn alpha_blend(fg: (u8, u8, u8), bg: (u8, u8, u8), alpha: u8) -> (u8, u8, u8) {
+    let mix = |f: u8, b: u8| {
+        ((f as u32 * alpha as u32 + b as u32 * (255 - alpha as u32) + 127) / 255) as u8
+    };
+    (mix(fg.0, bg.0), mix(fg.1, bg.1), mix(fg.2, bg.2))
+}
+
+/// Fade the background the animation painted according to `background_opacity`
+fn apply_background_opacity(f: &mut Frame, area: Rect, opacity: u8) {
+    // Blending needs RGB output; fully transparent works everywhere
+    if opacity == 255 || (opacity > 0 && !term_capabilities().truecolor) {
+        return;
+    }
+    let buffer = f.buffer_mut();
+    for y in area.top()..area.bottom() {
+        for x in area.left()..area.right() {
+            let cell = &mut buffer[(x, y)];
+            if opacity == 0 {
+                cell.set_bg(Color::Reset);
+                continue;
+            }
+            let rgb = match cell.bg {
+                Color::Rgb(r, g, b) => (r, g, b),
+                Color::Indexed(index) => xterm_color_to_rgb(index),
+                Color::Black => (0, 0, 0),
+                _ => continue,
+            };
+            let (r, g, b) = alpha_blend(rgb, BLEND_BASE, opacity);
+            cell.set_bg(Color::Rgb(r, g, b));
+        }
+    }
+}
+
 fn render_matrix(
     f: &mut Frame,
     state: &AnimationState,
@@ -6523,68 +12316,63 @@ fn render_matrix(
     color: Color,
     _bg: Color,
     rainbow: bool,
+    direction: (i8, i8),
 ) {
     // Fill background with black first to avoid gray stripes
     let bg_fill = Block::default().style(Style::default().bg(Color::Black));
     f.render_widget(bg_fill, size);
 
-    // Build each line of the matrix
-    for y in 0..size.height {
-        let mut line_chars: Vec<(char, Color)> = vec![];
-
-        for col in &state.matrix_columns {
-            let head_y = col.y as u16;
-            let trail_length = 8u16;
+    // Place each column's head and trail, then build each line of the matrix
+    let mut lines: Vec<Vec<(char, Color)>> = vec![Vec::new(); size.height as usize];
+    for col in &state.matrix_columns {
+        let trail_length = 8u16;
 
-            // Check if this column has content at this y position
-            if col.x >= size.width {
+        // Calculate trail
+        for i in 0..=trail_length {
+            let Some((x, y)) = matrix_cell(col, i, direction, size) else {
                 continue;
-            }
-
-            // Calculate trail
-            for i in 0..=trail_length {
-                let trail_y = head_y.saturating_sub(i);
-                if trail_y == y {
-                    let fade_factor = if i == 0 {
-                        1.0 // Head is brightest
-                    } else {
-                        (trail_length - i) as f32 / trail_length as f32
-                    };
-
-                    let intensity = (fade_factor * 255.0) as u8;
-
-                    let char_color = if rainbow {
-                        // Rainbow effect based on position and time
-                        let hue = ((col.x as f32 + state.tick as f32) % 360.0) / 360.0;
-                        let r = ((hue * 6.0).sin() * 0.5 + 0.5) * intensity as f32;
-                        let g = ((hue * 6.0 + 2.0).sin() * 0.5 + 0.5) * intensity as f32;
-                        let b = ((hue * 6.0 + 4.0).sin() * 0.5 + 0.5) * intensity as f32;
-                        Color::Rgb(r as u8, g as u8, b as u8)
-                    } else {
-                        match color {
-                            Color::Green => Color::Rgb(0, intensity, 0),
-                            Color::Blue => Color::Rgb(0, 0, intensity),
-                            Color::Cyan => Color::Rgb(0, intensity, intensity),
-                            _ => Color::Rgb(intensity, intensity, intensity),
-                        }
-                    };
+            };
+            let fade_factor = if i == 0 {
+                1.0 // Head is brightest
+            } else {
+                (trail_length - i) as f32 / trail_length as f32
+            };
 
-                    let ch = if i == 0 {
-                        MATRIX_CHARS[col.char_idx]
-                    } else {
-                        // Use different char for trail
-                        MATRIX_CHARS[(col.char_idx + i as usize) % MATRIX_CHARS.len()]
-                    };
+            let intensity = (fade_factor * 255.0) as u8;
 
-                    // Store at correct x position
-                    while line_chars.len() <= col.x as usize {
-                        line_chars.push((' ', Color::Black));
-                    }
-                    line_chars[col.x as usize] = (ch, char_color);
+            let char_color = if rainbow {
+                // Rainbow effect based on position and time
+                let hue = ((col.x as f32 + state.tick as f32) % 360.0) / 360.0;
+                let r = ((hue * 6.0).sin() * 0.5 + 0.5) * intensity as f32;
+                let g = ((hue * 6.0 + 2.0).sin() * 0.5 + 0.5) * intensity as f32;
+                let b = ((hue * 6.0 + 4.0).sin() * 0.5 + 0.5) * intensity as f32;
+                Color::Rgb(r as u8, g as u8, b as u8)
+            } else {
+                match color {
+                    Color::Green => Color::Rgb(0, intensity, 0),
+                    Color::Blue => Color::Rgb(0, 0, intensity),
+                    Color::Cyan => Color::Rgb(0, intensity, intensity),
+                    _ => Color::Rgb(intensity, intensity, intensity),
                 }
+            };
+
+            let ch = if i == 0 {
+                MATRIX_CHARS[col.char_idx]
+            } else {
+                // Use different char for trail
+                MATRIX_CHARS[(col.char_idx + i as usize) % MATRIX_CHARS.len()]
+            };
+
+            // Store at correct x position
+            let line_chars = &mut lines[y as usize];
+            while line_chars.len() <= x as usize {
+                line_chars.push((' ', Color::Black));
             }
+            line_chars[x as usize] = (ch, char_color);
         }
+    }
 
+    for (y, line_chars) in lines.into_iter().enumerate() {
         // Build spans for this line
         let spans: Vec<Span> = line_chars
             .into_iter()
@@ -6594,7 +12382,7 @@ fn render_matrix(
         if !spans.is_empty() {
             let text = Line::from(spans);
             let paragraph = Paragraph::new(text).style(Style::default().bg(Color::Black));
-            let area = Rect::new(0, y, size.width, 1);
+            let area = Rect::new(0, y as u16, size.width, 1);
             f.render_widget(paragraph, area);
         }
     }
@@ -6606,11 +12394,7 @@ fn render_rain(f: &mut Frame, state: &AnimationState, size: Rect, color: Color,
     f.render_widget(bg_fill, size);
 
     for drop in &state.rain_drops {
-        if drop.y < 0.0 {
-            continue;
-        }
-        let y = drop.y as u16;
-        if y < size.height {
+        if let Some((x, y)) = cell_at(drop.x as f32, drop.y, size) {
             let rain_char = if drop.speed > 1.5 { "│" } else { "┆" };
             let intensity = 100 + (drop.speed * 50.0) as u8;
 
@@ -6624,15 +12408,47 @@ fn render_rain(f: &mut Frame, state: &AnimationState, size: Rect, color: Color,
             let span = Span::styled(rain_char, Style::default().fg(rain_color));
             let text = Line::from(vec![span]);
             let paragraph = Paragraph::new(text);
-            let area = Rect::new(drop.x, y, 1, 1);
+            let area = Rect::new(x, y, 1, 1);
             f.render_widget(paragraph, area);
         }
     }
 }
 
+fn render_rain_window(f: &mut Frame, state: &AnimationState, size: Rect) {
+    // Blue-grey tint of a rainy day seen through the glass
+    let bg_fill = Block::default().style(Style::default().bg(Color::Rgb(20, 30, 45)));
+    f.render_widget(bg_fill, size);
+
+    for drop in &state.window_drops {
+        let fast = drop.speed > 0.8;
+        let streak_char = if fast { "│" } else { "┆" };
+        for i in 1..=drop.streak_length {
+            if let Some((x, y)) = cell_at(drop.x, drop.y - i as f32, size) {
+                // Fade the water towards the tint further up the streak
+                let fade = 1.0 - i as f32 / (drop.streak_length as f32 + 1.0);
+                let c = Color::Rgb(
+                    (20.0 + 100.0 * fade) as u8,
+                    (30.0 + 120.0 * fade) as u8,
+                    (45.0 + 140.0 * fade) as u8,
+                );
+                let span = Span::styled(streak_char, Style::default().fg(c));
+                let area = Rect::new(x, y, 1, 1);
+                f.render_widget(Paragraph::new(Line::from(vec![span])), area);
+            }
+        }
+
+        if let Some((x, y)) = cell_at(drop.x, drop.y, size) {
+            let head = if fast { "●" } else { "•" };
+            let span = Span::styled(head, Style::default().fg(Color::Rgb(170, 200, 230)));
+            let area = Rect::new(x, y, 1, 1);
+            f.render_widget(Paragraph::new(Line::from(vec![span])), area);
+        }
+    }
+}
+
 fn render_thunder(f: &mut Frame, state: &AnimationState, size: Rect, _color: Color, bg: Color) {
     use rand::Rng;
-    let mut rng = rand::thread_rng();
+    let mut rng = state.frame_rng();
 
     // Flash effect
     if state.thunder_flash > 0 {
@@ -6693,9 +12509,7 @@ fn render_snow(f: &mut Frame, state: &AnimationState, size: Rect, color: Color,
     f.render_widget(bg_fill, size);
 
     for flake in &state.snow_flakes {
-        let y = flake.y as u16;
-        let x = flake.x as u16;
-        if y < size.height && x < size.width {
+        if let Some((x, y)) = cell_at(flake.x, flake.y, size) {
             let snow_char = match flake.size {
                 1 => "·",
                 2 => "•",
@@ -6742,6 +12556,43 @@ fn render_stars(f: &mut Frame, state: &AnimationState, size: Rect, color: Color,
     }
 }
 
+/// Screen cell of a "starfield_3d" star, `None` once it has left the frame
+fn starfield_project(star: &Star3D, size: Rect) -> Option<(u16, u16)> {
+    let scale = size.width as f32 / 4.0;
+    let cx = size.width as f32 / 2.0;
+    let cy = size.height as f32 / 2.0;
+    // Terminal cells are about twice as tall as wide
+    let sx = star.x / star.z * scale + cx;
+    let sy = star.y / star.z * scale * 0.5 + cy;
+    cell_at(sx, sy, size)
+}
+
+fn render_starfield_3d(f: &mut Frame, state: &AnimationState, size: Rect) {
+    let bg_fill = Block::default().style(Style::default().bg(Color::Black));
+    f.render_widget(bg_fill, size);
+
+    for star in &state.stars_3d {
+        if let Some((x, y)) = starfield_project(star, size) {
+            // Nearer stars are bigger and brighter
+            let star_char = if star.z < 0.1 {
+                "◉"
+            } else if star.z < 0.3 {
+                "●"
+            } else if star.z < 0.6 {
+                "•"
+            } else {
+                "·"
+            };
+            let intensity = (255.0 - star.z.clamp(0.0, 1.0) * 200.0) as u8;
+
+            let c = Color::Rgb(intensity, intensity, intensity);
+            let span = Span::styled(star_char, Style::default().fg(c));
+            let area = Rect::new(x, y, 1, 1);
+            f.render_widget(Paragraph::new(Line::from(vec![span])), area);
+        }
+    }
+}
+
 fn render_fireflies(
     f: &mut Frame,
     state: &AnimationState,
@@ -6755,9 +12606,7 @@ fn render_fireflies(
     f.render_widget(bg_fill, size);
 
     for (i, firefly) in state.fireflies.iter().enumerate() {
-        let y = firefly.y as u16;
-        let x = firefly.x as u16;
-        if y < size.height && x < size.width {
+        if let Some((x, y)) = cell_at(firefly.x, firefly.y, size) {
             let intensity = firefly.brightness;
             let firefly_color = if rainbow {
                 let hue = ((i as f32 * 30.0 + state.tick as f32) % 360.0) / 360.0;
@@ -6787,9 +12636,7 @@ fn render_bubbles(f: &mut Frame, state: &AnimationState, size: Rect, color: Colo
     f.render_widget(bg_fill, size);
 
     for bubble in &state.bubbles {
-        let y = bubble.y as u16;
-        let x = bubble.x as u16;
-        if y < size.height && x < size.width {
+        if let Some((x, y)) = cell_at(bubble.x, bubble.y, size) {
             let bubble_char = match bubble.size {
                 1 => "○",
                 2 => "◎",
@@ -6818,9 +12665,7 @@ fn render_confetti(f: &mut Frame, state: &AnimationState, size: Rect, _bg: Color
     f.render_widget(bg_fill, size);
 
     for conf in &state.confetti {
-        let y = conf.y as u16;
-        let x = conf.x as u16;
-        if y < size.height && x < size.width {
+        if let Some((x, y)) = cell_at(conf.x, conf.y, size) {
             // HSL to RGB conversion for rainbow colors
             let hue = conf.color as f32 / 255.0;
             let r = ((hue * 6.0).sin() * 0.5 + 0.5) * 255.0;
@@ -6883,9 +12728,7 @@ fn render_particles(f: &mut Frame, state: &AnimationState, size: Rect, _bg: Colo
     f.render_widget(bg_fill, size);
 
     for particle in &state.particles {
-        let y = particle.y as u16;
-        let x = particle.x as u16;
-        if y < size.height && x < size.width {
+        if let Some((x, y)) = cell_at(particle.x, particle.y, size) {
             let alpha = (particle.life as f32 / particle.max_life as f32 * 255.0) as u8;
             let color = match particle.color {
                 Color::Rgb(r, g, b) => Color::Rgb(
@@ -6920,6 +12763,9 @@ fn render_digital_rain(
     let hex_chars = "0123456789ABCDEF";
 
     for col in &state.matrix_columns {
+        if col.x >= size.width {
+            continue;
+        }
         let head_y = col.y as u16;
         let trail_length = 6u16;
 
@@ -7025,6 +12871,33 @@ fn render_plasma(f: &mut Frame, state: &AnimationState, size: Rect) {
     }
 }
 
+fn render_plasma_pulse(f: &mut Frame, state: &AnimationState, size: Rect) {
+    let bg_fill = Block::default().style(Style::default().bg(Color::Black));
+    f.render_widget(bg_fill, size);
+
+    let pulse = state.heartbeat_phase.sin() * 0.3 + 0.7;
+    for cell in &state.plasma {
+        if cell.x >= size.width || cell.y >= size.height {
+            continue;
+        }
+
+        // The combined value picks the hue, the pulse dims the whole field
+        let value = cell.value * pulse;
+        let (r, g, b) = hsl_to_rgb((value + 1.0) * 180.0, 1.0, 0.5 * pulse);
+        let ch = if value > 0.5 {
+            "█"
+        } else if value > 0.0 {
+            "▓"
+        } else {
+            "▒"
+        };
+
+        let span = Span::styled(ch, Style::default().fg(Color::Rgb(r, g, b)));
+        let area = Rect::new(cell.x, cell.y, 1, 1);
+        f.render_widget(Paragraph::new(Line::from(vec![span])), area);
+    }
+}
+
 fn render_scanlines(f: &mut Frame, state: &AnimationState, size: Rect, color: Color) {
     // Dark background
     let bg_fill = Block::default().style(Style::default().bg(Color::Rgb(5, 5, 5)));
@@ -7032,7 +12905,7 @@ fn render_scanlines(f: &mut Frame, state: &AnimationState, size: Rect, color: Co
 
     // Render scanlines - fill entire lines
     for y in 0..size.height {
-        let is_scanline = (y + state.scanline_pos) % 4 == 0;
+        let is_scanline = (y + state.scanline_pos).is_multiple_of(4);
         let line_color = if is_scanline {
             color
         } else {
@@ -7051,7 +12924,7 @@ fn render_scanlines(f: &mut Frame, state: &AnimationState, size: Rect, color: Co
 
     // Occasional glitch effect
     use rand::Rng;
-    let mut rng = rand::thread_rng();
+    let mut rng = state.frame_rng();
     if rng.gen_bool(0.02) {
         let glitch_y = rng.gen_range(0..size.height);
         let glitch_color = Color::Rgb(
@@ -7106,6 +12979,274 @@ fn render_aurora(f: &mut Frame, state: &AnimationState, size: Rect) {
     }
 }
 
+fn render_northern_lights(f: &mut Frame, state: &AnimationState, size: Rect) {
+    const SHADES: [char; 4] = ['░', '▒', '▓', '█'];
+
+    let bg_fill = Block::default().style(Style::default().bg(Color::Rgb(5, 5, 15)));
+    f.render_widget(bg_fill, size);
+
+    let lights = &state.northern_lights;
+    let phase = lights.phase;
+    let height = size.height.max(1) as f32;
+
+    for y in 0..size.height {
+        // Sway of each curtain on this row, from a sine and the noise field
+        let centers: Vec<f32> = lights
+            .curtains
+            .iter()
+            .map(|c| {
+                let row = y as f32;
+                c.x + (row * 0.12 + phase + c.offset).sin() * 3.0
+                    + (noise(row * 0.07, phase * 0.5 + c.offset) - 0.5) * 4.0
+            })
+            .collect();
+
+        for x in 0..size.width {
+            let mut brightest: Option<(f32, f32)> = None;
+            for (curtain, center) in lights.curtains.iter().zip(&centers) {
+                // Light hangs from the curtain's top and fades towards the ground
+                let below_top = y as f32 - curtain.offset;
+                if below_top < 0.0 {
+                    continue;
+                }
+                let vertical = 1.0 - below_top / (height * 0.9);
+                let horizontal = 1.0 - (x as f32 - center).abs() / (curtain.width / 2.0);
+                let intensity = (horizontal * vertical).clamp(0.0, 1.0);
+                if brightest.is_none_or(|(best, _)| intensity > best) {
+                    brightest = Some((intensity, curtain.hue));
+                }
+            }
+
+            let Some((intensity, hue)) = brightest else {
+                continue;
+            };
+            if intensity < 0.1 {
+                continue;
+            }
+            let ch = SHADES[((intensity * SHADES.len() as f32) as usize).min(SHADES.len() - 1)];
+            let (r, g, b) = hsl_to_rgb(hue, 0.8, 0.2 + 0.4 * intensity);
+            let span = Span::styled(ch.to_string(), Style::default().fg(Color::Rgb(r, g, b)));
+            f.render_widget(Paragraph::new(Line::from(span)), Rect::new(x, y, 1, 1));
+        }
+    }
+}
+
+/// Character for a line piece going `dx` columns right and `dy` rows down
+fn line_char(dx: f32, dy: f32) -> char {
+    let slope = dy.abs() / dx.abs().max(0.001);
+    if slope < 0.4 {
+        '─'
+    } else if slope > 2.5 {
+        '|'
+    } else if (dx > 0.0) == (dy > 0.0) {
+        '\\'
+    } else {
+        '/'
+    }
+}
+
+fn render_plasma_globe(f: &mut Frame, state: &AnimationState, size: Rect, radius: f32) {
+    let bg_fill = Block::default().style(Style::default().bg(Color::Rgb(5, 0, 10)));
+    f.render_widget(bg_fill, size);
+
+    let center_x = size.width as f32 / 2.0;
+    let center_y = size.height as f32 / 2.0;
+    // Columns are about half as wide as rows are tall
+    let to_cell = |(x, y): (f32, f32)| (center_x + x * 2.0, center_y + y);
+
+    // Glass, pulsing slowly
+    let pulse = 0.6 + 0.4 * (state.tick as f32 * 0.08).sin();
+    let glass = (90.0 * pulse) as u8;
+    let buffer = f.buffer_mut();
+    let steps = (radius * 12.0) as usize;
+    for i in 0..steps {
+        let theta = i as f32 / steps as f32 * std::f32::consts::TAU;
+        let (x, y) = to_cell((theta.cos() * radius, theta.sin() * radius));
+        if let Some((x, y)) = cell_at(x, y, size) {
+            let color = Color::Rgb(glass, glass / 2, (glass as f32 * 1.6) as u8);
+            buffer[(x, y)].set_char('·').set_fg(color);
+        }
+    }
+
+    for arc in &state.plasma_arcs {
+        let (r, g, b) = arc.color;
+        let color = Color::Rgb(r, g, b);
+        for pair in arc.segments.windows(2) {
+            let (x1, y1) = to_cell(pair[0]);
+            let (x2, y2) = to_cell(pair[1]);
+            let ch = line_char(x2 - x1, y2 - y1);
+            let (x1, y1, x2, y2) = (x1 as i16, y1 as i16, x2 as i16, y2 as i16);
+            draw_line(f, x1, y1, x2, y2, ch, color, size);
+        }
+        // Where the arc touches the glass it glows
+        if arc.length >= 1.0 {
+            if let Some(&end) = arc.segments.last() {
+                let (x, y) = to_cell(end);
+                if let Some((x, y)) = cell_at(x, y, size) {
+                    f.buffer_mut()[(x, y)].set_char('*').set_fg(Color::White);
+                }
+            }
+        }
+    }
+
+    // Electrode in the middle
+    if let Some((x, y)) = cell_at(center_x, center_y, size) {
+        let color = Color::Rgb(255, 230, 255);
+        f.buffer_mut()[(x, y)].set_char('◉').set_fg(color);
+    }
+}
+
+fn render_cellular_automata(
+    f: &mut Frame,
+    state: &AnimationState,
+    size: Rect,
+    color: Color,
+    bg: Color,
+) {
+    let bg_fill = Block::default().style(Style::default().bg(bg));
+    f.render_widget(bg_fill, size);
+
+    let buffer = f.buffer_mut();
+    for (y, row) in (size.y..size.bottom()).zip(&state.ca_history) {
+        for (x, &alive) in (size.x..size.right()).zip(row) {
+            if alive {
+                buffer[(x, y)].set_char('█').set_fg(color);
+            }
+        }
+    }
+}
+
+fn render_aurora_borealis(f: &mut Frame, state: &AnimationState, size: Rect, glow: bool) {
+    const SHADES: [char; 4] = ['│', '░', '▒', '▓'];
+    const BACKGROUND: (u8, u8, u8) = (5, 5, 15);
+
+    let bg_fill = Block::default().style(Style::default().bg(Color::Rgb(5, 5, 15)));
+    f.render_widget(bg_fill, size);
+
+    // Brightest light per cell, so overlapping curtains layer instead of mixing
+    let width = size.width as usize;
+    let mut light = vec![(0.0_f32, 0.0_f32); width * size.height as usize];
+    let t = state.tick as f32;
+    for curtain in &state.aurora_curtains {
+        let hue = aurora_borealis_hue(curtain.color_hue);
+        let top = 1.0 + (t * 0.01 + curtain.phase_offset).sin();
+        for row in 0..curtain.height {
+            let y = top as u16 + row;
+            if y >= size.height {
+                break;
+            }
+            // The sway travels down the curtain like a wave through cloth
+            let wave = t * curtain.sway_speed + curtain.phase_offset + row as f32 * 0.15;
+            let center = curtain.base_x + wave.sin() * 4.0;
+            // Brightest at the top, fading into the night towards the bottom
+            let vertical = 1.0 - row as f32 / curtain.height as f32;
+            let reach: i32 = if glow { 3 } else { 1 };
+            for dx in -reach..=reach {
+                let x = (center + dx as f32).round();
+                if x < 0.0 || x >= size.width as f32 {
+                    continue;
+                }
+                let intensity = match dx.abs() {
+                    0 => vertical,
+                    1 => vertical * 0.7,
+                    d => vertical * 0.25 / (d - 1) as f32,
+                };
+                let cell = &mut light[y as usize * width + x as usize];
+                if intensity > cell.0 {
+                    *cell = (intensity, hue);
+                }
+            }
+        }
+    }
+
+    let buffer = f.buffer_mut();
+    for (i, &(intensity, hue)) in light.iter().enumerate() {
+        if intensity < 0.05 {
+            continue;
+        }
+        let ch = SHADES[((intensity * SHADES.len() as f32) as usize).min(SHADES.len() - 1)];
+        let rgb = hsl_to_rgb(hue, 0.8, 0.55);
+        let (r, g, b) = alpha_blend(rgb, BACKGROUND, (intensity * 255.0) as u8);
+        let x = size.x + (i % width) as u16;
+        let y = size.y + (i / width) as u16;
+        buffer[(x, y)].set_char(ch).set_fg(Color::Rgb(r, g, b));
+    }
+}
+
+fn render_bouncing_balls(f: &mut Frame, state: &AnimationState, size: Rect) {
+    let bg_fill = Block::default().style(Style::default().bg(Color::Black));
+    f.render_widget(bg_fill, size);
+
+    for ball in &state.balls {
+        let (r, g, b) = ball.color;
+        if ball.radius < 1.5 {
+            if let Some((x, y)) = cell_at(ball.x, ball.y, size) {
+                let span = Span::styled("●", Style::default().fg(Color::Rgb(r, g, b)));
+                let area = Rect::new(x, y, 1, 1);
+                f.render_widget(Paragraph::new(Line::from(vec![span])), area);
+            }
+            continue;
+        }
+
+        // Concentric rings, darker towards the edge
+        let reach = ball.radius.ceil() as i32;
+        for dy in -reach..=reach {
+            for dx in -reach..=reach {
+                let dist = ((dx * dx + dy * dy * 4) as f32).sqrt() / ball.radius;
+                if dist > 1.0 {
+                    continue;
+                }
+                let Some((x, y)) = cell_at(ball.x + dx as f32, ball.y + dy as f32, size) else {
+                    continue;
+                };
+                let ch = if dx == 0 && dy == 0 {
+                    "◉"
+                } else if dist < 0.7 {
+                    "●"
+                } else {
+                    "○"
+                };
+                let shade = 1.0 - dist * 0.5;
+                let c = Color::Rgb(
+                    (r as f32 * shade) as u8,
+                    (g as f32 * shade) as u8,
+                    (b as f32 * shade) as u8,
+                );
+                let span = Span::styled(ch, Style::default().fg(c));
+                let area = Rect::new(x, y, 1, 1);
+                f.render_widget(Paragraph::new(Line::from(vec![span])), area);
+            }
+        }
+    }
+}
+
+fn render_dvd_logo(f: &mut Frame, state: &AnimationState, size: Rect) {
+    let bg_fill = Block::default().style(Style::default().bg(Color::Black));
+    f.render_widget(bg_fill, size);
+
+    let color = DVD_COLORS[state.dvd_color_idx % DVD_COLORS.len()];
+    let text_width = state.dvd_text.chars().count() as u16;
+    let mut draw = |x: f32, y: f32, (r, g, b): (u8, u8, u8)| {
+        let (x, y) = (x as u16, y as u16);
+        if x < size.width && y < size.height {
+            let span = Span::styled(
+                state.dvd_text.as_str(),
+                Style::default().fg(Color::Rgb(r, g, b)),
+            );
+            let width = text_width.min(size.width - x);
+            f.render_widget(Paragraph::new(Line::from(span)), Rect::new(x, y, width, 1));
+        }
+    };
+
+    // Oldest ghosts first so newer ones are drawn on top
+    let trail_len = state.dvd_trail.len();
+    for (i, &(x, y)) in state.dvd_trail.iter().enumerate().rev() {
+        let alpha = (255 * (trail_len - i) / (trail_len + 1)) as u8 / 2;
+        draw(x, y, alpha_blend(color, BLEND_BASE, alpha));
+    }
+    draw(state.dvd_x, state.dvd_y, color);
+}
+
 fn render_autumn(f: &mut Frame, state: &AnimationState, size: Rect) {
     let bg_fill = Block::default().style(Style::default().bg(Color::Rgb(20, 15, 10)));
     f.render_widget(bg_fill, size);
@@ -7121,10 +13262,7 @@ fn render_autumn(f: &mut Frame, state: &AnimationState, size: Rect) {
         if leaf.y < 0.0 {
             continue;
         }
-        let y = leaf.y as u16;
-        let x = leaf.x as u16;
-
-        if y < size.height && x < size.width {
+        if let Some((x, y)) = cell_at(leaf.x, leaf.y, size) {
             let leaf_chars = ["🍂", "🍁", "•", "◦"];
             let leaf_char = leaf_chars[leaf.color as usize % leaf_chars.len()];
             let color = autumn_colors[leaf.color as usize % autumn_colors.len()];
@@ -7143,7 +13281,7 @@ fn render_dna(f: &mut Frame, state: &AnimationState, size: Rect, color: Color) {
     f.render_widget(bg_fill, size);
 
     // Define multiple helix center positions based on terminal width
-    let num_helixes = ((size.width as usize) / 25).max(1).min(4);
+    let num_helixes = ((size.width as usize) / 25).clamp(1, 4);
     let spacing = size.width / (num_helixes as u16 + 1);
 
     for helix_idx in 0..num_helixes {
@@ -7295,7 +13433,7 @@ fn render_synthwave(f: &mut Frame, state: &AnimationState, size: Rect, _color: C
     for y in (grid_start..size.height).step_by(2) {
         let distance = (y - grid_start) as f32;
         let perspective_gap = (1.0 + distance * 0.1) as u16;
-        if (y + offset) % perspective_gap == 0 {
+        if (y + offset).is_multiple_of(perspective_gap) {
             let line_color = Color::Rgb(
                 100 + (distance * 2.0) as u8,
                 0,
@@ -7341,10 +13479,7 @@ fn render_smoke(f: &mut Frame, state: &AnimationState, size: Rect) {
     f.render_widget(bg_fill, size);
 
     for particle in &state.smoke {
-        let y = particle.y as u16;
-        let x = particle.x as u16;
-
-        if y < size.height && x < size.width {
+        if let Some((x, y)) = cell_at(particle.x, particle.y, size) {
             let alpha = (particle.life as f32 / particle.max_life as f32 * 100.0) as u8 + 50;
             let smoke_color = Color::Rgb(alpha, alpha, alpha);
 
@@ -7380,42 +13515,56 @@ fn render_gradient_flow(f: &mut Frame, state: &AnimationState, size: Rect) {
     }
 }
 
-fn render_constellation(f: &mut Frame, state: &AnimationState, size: Rect, color: Color) {
+fn render_constellation(
+    f: &mut Frame,
+    state: &AnimationState,
+    size: Rect,
+    color: Color,
+    connect_distance: f32,
+) {
     let bg_fill = Block::default().style(Style::default().bg(Color::Rgb(5, 5, 10)));
     f.render_widget(bg_fill, size);
 
-    // Draw connections
+    // Close pairs, nearest first, so each star keeps its nearest neighbours
+    let max_dist = connect_distance;
+    let mut pairs = Vec::new();
     for (i, node1) in state.nodes.iter().enumerate() {
-        for node2 in state.nodes.iter().skip(i + 1) {
-            let dx = node1.x - node2.x;
-            let dy = node1.y - node2.y;
-            let dist_sq = dx * dx + dy * dy;
-
-            if dist_sq < 400.0 {
-                // Draw line between close nodes
-                let mid_x = ((node1.x + node2.x) / 2.0) as u16;
-                let mid_y = ((node1.y + node2.y) / 2.0) as u16;
-
-                if mid_x < size.width && mid_y < size.height {
-                    let alpha = (1.0 - dist_sq / 400.0) * 150.0;
-                    let line_color =
-                        Color::Rgb((alpha * 0.5) as u8, (alpha * 0.7) as u8, alpha as u8);
-                    let span = Span::styled("·", Style::default().fg(line_color));
-                    let text = Line::from(vec![span]);
-                    let paragraph = Paragraph::new(text);
-                    let area = Rect::new(mid_x, mid_y, 1, 1);
-                    f.render_widget(paragraph, area);
-                }
+        for (j, node2) in state.nodes.iter().enumerate().skip(i + 1) {
+            let dist = (node1.x - node2.x).hypot(node1.y - node2.y);
+            if dist < max_dist {
+                pairs.push((dist, i, j));
             }
         }
     }
+    pairs.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+    // Draw connections, at most three per star to keep the sky readable
+    let mut connections = vec![0u8; state.nodes.len()];
+    for (dist, i, j) in pairs {
+        if connections[i] >= 3 || connections[j] >= 3 {
+            continue;
+        }
+        connections[i] += 1;
+        connections[j] += 1;
+
+        let alpha = (1.0 - dist / max_dist) * 150.0;
+        let line_color = Color::Rgb((alpha * 0.5) as u8, (alpha * 0.7) as u8, alpha as u8);
+        let (node1, node2) = (&state.nodes[i], &state.nodes[j]);
+        draw_line(
+            f,
+            node1.x as i16,
+            node1.y as i16,
+            node2.x as i16,
+            node2.y as i16,
+            '·',
+            line_color,
+            size,
+        );
+    }
 
     // Draw nodes
     for node in &state.nodes {
-        let x = node.x as u16;
-        let y = node.y as u16;
-
-        if x < size.width && y < size.height {
+        if let Some((x, y)) = cell_at(node.x, node.y, size) {
             let span = Span::styled("●", Style::default().fg(color));
             let text = Line::from(vec![span]);
             let paragraph = Paragraph::new(text);
@@ -7431,10 +13580,7 @@ fn render_fish_tank(f: &mut Frame, state: &AnimationState, size: Rect) {
 
     // Draw bubbles
     for bubble in &state.bubbles {
-        let y = bubble.y as u16;
-        let x = bubble.x as u16;
-
-        if y < size.height && x < size.width {
+        if let Some((x, y)) = cell_at(bubble.x, bubble.y, size) {
             let span = Span::styled("○", Style::default().fg(Color::Rgb(200, 200, 255)));
             let text = Line::from(vec![span]);
             let paragraph = Paragraph::new(text);
@@ -7461,53 +13607,196 @@ fn render_fish_tank(f: &mut Frame, state: &AnimationState, size: Rect) {
             let fish_char = if fish.direction { "><>" } else { "<><" };
             let color = fish_colors[fish.color as usize % fish_colors.len()];
 
-            let span = Span::styled(fish_char, Style::default().fg(color));
-            let text = Line::from(vec![span]);
-            let paragraph = Paragraph::new(text);
-            let area = Rect::new(x, y, 3, 1);
-            f.render_widget(paragraph, area);
-        }
+            let span = Span::styled(fish_char, Style::default().fg(color));
+            let text = Line::from(vec![span]);
+            let paragraph = Paragraph::new(text);
+            let area = Rect::new(x, y, 3, 1);
+            f.render_widget(paragraph, area);
+        }
+    }
+}
+
+fn render_typing_code(f: &mut Frame, state: &AnimationState, size: Rect, color: Color) {
+    let bg_fill = Block::default().style(Style::default().bg(Color::Rgb(10, 10, 15)));
+    f.render_widget(bg_fill, size);
+
+    // Render typed code
+    for (i, line) in state.code_lines.iter().enumerate() {
+        let y = i as u16 + 1;
+        if y >= size.height - 1 {
+            break;
+        }
+
+        let display_line = if i < state.code_line_idx {
+            line.clone()
+        } else if i == state.code_line_idx {
+            line.chars().take(state.code_char_idx).collect()
+        } else {
+            String::new()
+        };
+
+        if !display_line.is_empty() {
+            let span = Span::styled(display_line, Style::default().fg(color));
+            let text = Line::from(vec![span]);
+            let paragraph = Paragraph::new(text);
+            let area = Rect::new(1, y, size.width - 2, 1);
+            f.render_widget(paragraph, area);
+        }
+    }
+
+    // Draw cursor
+    let cursor_y = (state.code_line_idx + 1) as u16;
+    if cursor_y < size.height - 1 {
+        let span = Span::styled("█", Style::default().fg(Color::White));
+        let text = Line::from(vec![span]);
+        let paragraph = Paragraph::new(text);
+        let area = Rect::new(1, cursor_y, 1, 1);
+        f.render_widget(paragraph, area);
+    }
+}
+
+fn render_typewriter(f: &mut Frame, app: &App, size: Rect, color: Color) {
+    let bg_fill = Block::default().style(Style::default().bg(Color::Black));
+    f.render_widget(bg_fill, size);
+
+    let animation = &app.config.animation;
+    let tw = &app.animation_state.typewriter;
+    let x = size.x + animation.typewriter_x.min(size.width.saturating_sub(1));
+    let top = size.y + animation.typewriter_y.min(size.height);
+    let width = size.right().saturating_sub(x);
+
+    for (i, line) in animation.typewriter_text.iter().enumerate() {
+        let y = top.saturating_add(i as u16);
+        if y >= size.bottom() {
+            break;
+        }
+        let shown: String = match i.cmp(&tw.line) {
+            std::cmp::Ordering::Less => line.clone(),
+            std::cmp::Ordering::Equal => line.chars().take(tw.col).collect(),
+            std::cmp::Ordering::Greater => break,
+        };
+        f.render_widget(
+            Paragraph::new(shown).style(Style::default().fg(color)),
+            Rect::new(x, y, width, 1),
+        );
+    }
+
+    // Blinking cursor at the insertion point
+    let cursor_x = x.saturating_add(tw.col as u16);
+    let cursor_y = top.saturating_add(tw.line as u16);
+    let blink_on = (app.animation_state.tick / 8).is_multiple_of(2);
+    if blink_on && cursor_x < size.right() && cursor_y < size.bottom() {
+        f.render_widget(
+            Paragraph::new("_").style(Style::default().fg(Color::White)),
+            Rect::new(cursor_x, cursor_y, 1, 1),
+        );
+    }
+}
+
+fn render_clock(f: &mut Frame, state: &AnimationState, size: Rect, color: Color) {
+    let bg_fill = Block::default().style(Style::default().bg(Color::Black));
+    f.render_widget(bg_fill, size);
+
+    let clock = &state.clock;
+    let x = size.x + clock.x.min(size.width.saturating_sub(1));
+    for (i, row) in clock_rows(&clock.text).into_iter().enumerate() {
+        let y = size.y + clock.y + i as u16;
+        if y >= size.bottom() {
+            break;
+        }
+        f.render_widget(
+            Paragraph::new(row).style(Style::default().fg(color)),
+            Rect::new(x, y, size.right().saturating_sub(x), 1),
+        );
+    }
+}
+
+/// Blend from `base` at low levels through red to white at the top
+fn gradient_color(fraction: f32, base: Color) -> Color {
+    let base_rgb = match base {
+        Color::Rgb(r, g, b) => (r, g, b),
+        Color::Indexed(index) => xterm_color_to_rgb(index),
+        named => XTERM_SYSTEM_COLORS
+            .iter()
+            .find(|(color, _)| *color == named)
+            .map_or((0, 255, 0), |(_, rgb)| *rgb),
+    };
+    let lerp = |from: (u8, u8, u8), to: (u8, u8, u8), amount: f32| {
+        let mix = |a: u8, b: u8| (a as f32 + (b as f32 - a as f32) * amount) as u8;
+        Color::Rgb(mix(from.0, to.0), mix(from.1, to.1), mix(from.2, to.2))
+    };
+
+    let fraction = fraction.clamp(0.0, 1.0);
+    if fraction < 0.6 {
+        Color::Rgb(base_rgb.0, base_rgb.1, base_rgb.2)
+    } else if fraction < 0.85 {
+        lerp(base_rgb, (255, 0, 0), (fraction - 0.6) / 0.25)
+    } else {
+        lerp((255, 0, 0), (255, 255, 255), (fraction - 0.85) / 0.15)
     }
 }
 
-fn render_typing_code(f: &mut Frame, state: &AnimationState, size: Rect, color: Color) {
-    let bg_fill = Block::default().style(Style::default().bg(Color::Rgb(10, 10, 15)));
+fn render_waveform(f: &mut Frame, app: &App, size: Rect, color: Color) {
+    const LEVELS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+    let bg_fill = Block::default().style(Style::default().bg(Color::Black));
     f.render_widget(bg_fill, size);
 
-    // Render typed code
-    let mut y = 1u16;
-    for (i, line) in state.code_lines.iter().enumerate() {
-        if y >= size.height - 1 {
+    let state = &app.animation_state;
+    let count = state.waveform_bars.len() as u16;
+    if count == 0 || size.height == 0 {
+        return;
+    }
+    let slot = (size.width / count).max(1);
+    // Leave a gap between bars once they are wide enough
+    let bar_width = if slot >= 3 { slot - 1 } else { slot };
+    let left = size.x + size.width.saturating_sub(slot * count) / 2;
+    let rows = size.height as f32;
+
+    for (i, (&bar, &peak)) in state
+        .waveform_bars
+        .iter()
+        .zip(state.waveform_peaks.iter())
+        .enumerate()
+    {
+        let x = left + i as u16 * slot;
+        if x + bar_width > size.right() {
             break;
         }
 
-        let display_line = if i < state.code_line_idx {
-            line.clone()
-        } else if i == state.code_line_idx {
-            line.chars().take(state.code_char_idx).collect()
-        } else {
-            String::new()
-        };
-
-        if !display_line.is_empty() {
-            let span = Span::styled(display_line, Style::default().fg(color));
-            let text = Line::from(vec![span]);
-            let paragraph = Paragraph::new(text);
-            let area = Rect::new(1, y, size.width - 2, 1);
-            f.render_widget(paragraph, area);
+        // Height in eighths of a cell, drawn from the bottom row up
+        let eighths = (bar * rows * 8.0) as u16;
+        for row in 0..size.height {
+            let filled = eighths.saturating_sub(row * 8).min(8);
+            if filled == 0 {
+                break;
+            }
+            let ch = LEVELS[filled as usize - 1];
+            let y = size.bottom() - 1 - row;
+            let span = Span::styled(
+                ch.to_string().repeat(bar_width as usize),
+                Style::default().fg(gradient_color(row as f32 / rows, color)),
+            );
+            f.render_widget(
+                Paragraph::new(Line::from(span)),
+                Rect::new(x, y, bar_width, 1),
+            );
         }
 
-        y += 1;
-    }
-
-    // Draw cursor
-    let cursor_y = (state.code_line_idx + 1) as u16;
-    if cursor_y < size.height - 1 {
-        let span = Span::styled("█", Style::default().fg(Color::White));
-        let text = Line::from(vec![span]);
-        let paragraph = Paragraph::new(text);
-        let area = Rect::new(1, cursor_y, 1, 1);
-        f.render_widget(paragraph, area);
+        if app.config.animation.waveform_peak_hold {
+            let peak_row = ((peak * rows) as u16).min(size.height - 1);
+            if peak_row * 8 >= eighths {
+                let y = size.bottom() - 1 - peak_row;
+                let span = Span::styled(
+                    "─".repeat(bar_width as usize),
+                    Style::default().fg(gradient_color(peak, color)),
+                );
+                f.render_widget(
+                    Paragraph::new(Line::from(span)),
+                    Rect::new(x, y, bar_width, 1),
+                );
+            }
+        }
     }
 }
 
@@ -7527,10 +13816,7 @@ fn render_vortex(f: &mut Frame, state: &AnimationState, size: Rect, color: Color
             let x = center_x + (angle + arm_offset + rad * 0.2).cos() * rad;
             let y = center_y + (angle + arm_offset + rad * 0.2).sin() * rad;
 
-            let px = x as u16;
-            let py = y as u16;
-
-            if px < size.width && py < size.height {
+            if let Some((px, py)) = cell_at(x, y, size) {
                 let intensity = (255 - r * 4).max(50) as u8;
                 let vortex_color = match color {
                     Color::Rgb(r, g, b) => Color::Rgb(
@@ -7555,24 +13841,31 @@ fn render_circuit(f: &mut Frame, state: &AnimationState, size: Rect, color: Colo
     let bg_fill = Block::default().style(Style::default().bg(Color::Rgb(5, 10, 5)));
     f.render_widget(bg_fill, size);
 
-    for trace in &state.traces {
-        let x = trace.x;
-        let y = trace.y;
-
-        if x < size.width && y < size.height {
-            let intensity = (trace.life as f32 / 150.0 * 255.0) as u8;
-            let trace_color = Color::Rgb(0, intensity, intensity / 2);
-
-            let ch = match trace.direction {
-                0 | 2 => "│",
-                _ => "─",
+    for (idx, trace) in state.traces.iter().enumerate() {
+        // Traces carrying an electron glow brighter
+        let powered = state.electrons.iter().any(|e| e.trace_idx == Some(idx));
+        let life = trace.life as f32 / 150.0;
+        let intensity = if powered {
+            (150.0 + life * 105.0) as u8
+        } else {
+            (life * 180.0) as u8
+        };
+        let trace_color = Color::Rgb(0, intensity, intensity / 2);
+
+        let buffer = f.buffer_mut();
+        let mut previous = None;
+        for &(x, y) in &trace.path {
+            // Each cell points the way the wire came from
+            let ch = match previous {
+                Some((px, _)) if px != x => '─',
+                Some(_) => '│',
+                None if trace.direction % 2 == 0 => '│',
+                None => '─',
             };
-
-            let span = Span::styled(ch, Style::default().fg(trace_color));
-            let text = Line::from(vec![span]);
-            let paragraph = Paragraph::new(text);
-            let area = Rect::new(x, y, 1, 1);
-            f.render_widget(paragraph, area);
+            previous = Some((x, y));
+            if x < size.right() && y < size.bottom() {
+                buffer[(x, y)].set_char(ch).set_fg(trace_color);
+            }
         }
     }
 
@@ -7586,6 +13879,24 @@ fn render_circuit(f: &mut Frame, state: &AnimationState, size: Rect, color: Colo
             f.render_widget(paragraph, area);
         }
     }
+
+    for electron in &state.electrons {
+        let Some(trace) = electron.trace_idx.and_then(|idx| state.traces.get(idx)) else {
+            continue;
+        };
+        let Some(&(x, y)) = trace.path.get(electron.position as usize) else {
+            continue;
+        };
+        if x < size.width && y < size.height {
+            let (r, g, b) = electron.color;
+            let style = Style::default()
+                .fg(Color::Rgb(r, g, b))
+                .add_modifier(Modifier::BOLD);
+            let span = Span::styled("●", style);
+            let area = Rect::new(x, y, 1, 1);
+            f.render_widget(Paragraph::new(Line::from(vec![span])), area);
+        }
+    }
 }
 
 fn render_flow_field(f: &mut Frame, state: &AnimationState, size: Rect) {
@@ -7593,10 +13904,7 @@ fn render_flow_field(f: &mut Frame, state: &AnimationState, size: Rect) {
     f.render_widget(bg_fill, size);
 
     for particle in &state.flow_particles {
-        let x = particle.x as u16;
-        let y = particle.y as u16;
-
-        if x < size.width && y < size.height {
+        if let Some((x, y)) = cell_at(particle.x, particle.y, size) {
             let hue = particle.color as f32 / 255.0;
             let r = ((hue * 6.0).sin() * 0.5 + 0.5) * 255.0;
             let g = ((hue * 6.0 + 2.0).sin() * 0.5 + 0.5) * 255.0;
@@ -7648,19 +13956,31 @@ fn render_lissajous(f: &mut Frame, state: &AnimationState, size: Rect) {
     let center_y = size.height as f32 / 2.0;
 
     for curve in &state.lissajous {
-        let x = center_x + (curve.a * curve.t + curve.delta).sin() * center_x * 0.8;
-        let y = center_y + (curve.b * curve.t).sin() * center_y * 0.8;
-
-        let px = x as u16;
-        let py = y as u16;
-
-        if px < size.width && py < size.height {
-            let hue = curve.color as f32 / 255.0;
-            let r = ((hue * 6.0).sin() * 0.5 + 0.5) * 255.0;
-            let g = ((hue * 6.0 + 2.0).sin() * 0.5 + 0.5) * 255.0;
-            let b = ((hue * 6.0 + 4.0).sin() * 0.5 + 0.5) * 255.0;
+        let hue = curve.color as f32 / 255.0;
+        let r = ((hue * 6.0).sin() * 0.5 + 0.5) * 255.0;
+        let g = ((hue * 6.0 + 2.0).sin() * 0.5 + 0.5) * 255.0;
+        let b = ((hue * 6.0 + 4.0).sin() * 0.5 + 0.5) * 255.0;
+        let rgb = (r as u8, g as u8, b as u8);
+
+        // Trail, oldest first so newer points are drawn on top
+        let trail_len = curve.history.len();
+        for (i, &(sx, sy)) in curve.history.iter().enumerate() {
+            let x = center_x + sx * center_x * 0.8;
+            let y = center_y + sy * center_y * 0.8;
+            if let Some((px, py)) = cell_at(x, y, size) {
+                let brightness = ((i + 1) * 255 / (trail_len + 1)) as u8;
+                let (r, g, b) = alpha_blend(rgb, (0, 0, 0), brightness);
+                let span = Span::styled("•", Style::default().fg(Color::Rgb(r, g, b)));
+                let area = Rect::new(px, py, 1, 1);
+                f.render_widget(Paragraph::new(Line::from(vec![span])), area);
+            }
+        }
 
-            let color = Color::Rgb(r as u8, g as u8, b as u8);
+        let (sx, sy) = curve.point(curve.t);
+        let x = center_x + sx * center_x * 0.8;
+        let y = center_y + sy * center_y * 0.8;
+        if let Some((px, py)) = cell_at(x, y, size) {
+            let color = Color::Rgb(rgb.0, rgb.1, rgb.2);
             let span = Span::styled("●", Style::default().fg(color));
             let text = Line::from(vec![span]);
             let paragraph = Paragraph::new(text);
@@ -7670,8 +13990,23 @@ fn render_lissajous(f: &mut Frame, state: &AnimationState, size: Rect) {
     }
 }
 
-fn render_game_of_life(f: &mut Frame, state: &AnimationState, size: Rect) {
-    let bg_fill = Block::default().style(Style::default().bg(Color::Black));
+/// Color of a live "game_of_life" cell: newborn cells flash white, settle into
+/// the animation color and fade to a dim green as they keep surviving
+fn gol_cell_color(age: u8, color: (u8, u8, u8)) -> Color {
+    const NEWBORN: (u8, u8, u8) = (255, 255, 255);
+    const OLD: (u8, u8, u8) = (0, 70, 30);
+
+    let (r, g, b) = match age {
+        0..=2 => NEWBORN,
+        3..=20 => alpha_blend(color, NEWBORN, ((age - 2) as u32 * 255 / 18) as u8),
+        21..=60 => alpha_blend(OLD, color, ((age - 20) as u32 * 255 / 40) as u8),
+        _ => OLD,
+    };
+    Color::Rgb(r, g, b)
+}
+
+fn render_game_of_life(f: &mut Frame, state: &AnimationState, size: Rect, color: Color, bg: Color) {
+    let bg_fill = Block::default().style(Style::default().bg(bg));
     f.render_widget(bg_fill, size);
 
     let width = state.gol_width;
@@ -7679,19 +14014,14 @@ fn render_game_of_life(f: &mut Frame, state: &AnimationState, size: Rect) {
         return;
     }
 
+    let base = color_rgb(color);
     for cell in &state.gol_grid {
         if cell.x >= size.width as usize || cell.y >= size.height as usize {
             continue;
         }
 
         if cell.alive {
-            let age_factor = (cell.age as f32 / 50.0).min(1.0);
-            let color = Color::Rgb(
-                (50.0 + age_factor * 150.0) as u8,
-                (100.0 + age_factor * 100.0) as u8,
-                200,
-            );
-
+            let color = gol_cell_color(cell.age, base);
             let span = Span::styled("█", Style::default().fg(color));
             let text = Line::from(vec![span]);
             let paragraph = Paragraph::new(text);
@@ -7802,10 +14132,21 @@ fn render_fireworks(f: &mut Frame, state: &AnimationState, size: Rect, _bg: Colo
                 && firework.y >= 0.0
                 && firework.y < size.height as f32
             {
-                let x = firework.x as u16;
-                let y = firework.y as u16;
-                if x < size.width && y < size.height {
-                    let color = Color::Rgb(firework.color.0, firework.color.1, firework.color.2);
+                let (r, g, b) = firework.color;
+                let color = Color::Rgb(r, g, b);
+
+                // Trail of sparks where the rocket just was
+                for step in 1..=2 {
+                    let trail_x = firework.x - firework.vx * step as f32;
+                    let trail_y = firework.y - firework.vy * step as f32;
+                    if let Some((x, y)) = cell_at(trail_x, trail_y, size) {
+                        let style = Style::default().fg(dim_color(color, 200 / step));
+                        let span = Span::styled("|", style);
+                        let area = Rect::new(x, y, 1, 1);
+                        f.render_widget(Paragraph::new(Line::from(vec![span])), area);
+                    }
+                }
+                if let Some((x, y)) = cell_at(firework.x, firework.y, size) {
                     let span = Span::styled("▲", Style::default().fg(color));
                     let line = Line::from(vec![span]);
                     let text = Paragraph::new(line);
@@ -7821,19 +14162,14 @@ fn render_fireworks(f: &mut Frame, state: &AnimationState, size: Rect, _bg: Colo
                     && particle.y >= 0.0
                     && particle.y < size.height as f32
                 {
-                    let x = particle.x as u16;
-                    let y = particle.y as u16;
-                    if x < size.width && y < size.height {
+                    if let Some((x, y)) = cell_at(particle.x, particle.y, size) {
                         let fade = particle.life as f32 / particle.max_life as f32;
                         let r = (firework.color.0 as f32 * fade) as u8;
                         let g = (firework.color.1 as f32 * fade) as u8;
                         let b = (firework.color.2 as f32 * fade) as u8;
                         let color = Color::Rgb(r, g, b);
 
-                        let chars = ['•', '∙', '·'];
-                        let char_idx = ((1.0 - fade) * 2.0) as usize % chars.len();
-                        let span =
-                            Span::styled(chars[char_idx].to_string(), Style::default().fg(color));
+                        let span = Span::styled("✦", Style::default().fg(color));
                         let line = Line::from(vec![span]);
                         let text = Paragraph::new(line);
                         let area = Rect::new(x, y, 1, 1);
@@ -8026,32 +14362,14 @@ fn render_cube_3d(f: &mut Frame, state: &AnimationState, size: Rect, color: Colo
     for (i, j) in &edges {
         let (x1, y1) = transformed[*i];
         let (x2, y2) = transformed[*j];
-
-        // Simple line drawing
-        let dx = (x2 - x1).abs();
-        let dy = (y2 - y1).abs();
-        let steps = (dx.max(dy) as usize).max(1);
-
-        for step in 0..=steps {
-            let t = step as f32 / steps as f32;
-            let px = (x1 + (x2 - x1) * t) as u16;
-            let py = (y1 + (y2 - y1) * t) as u16;
-
-            if px < size.width && py < size.height {
-                let span = Span::styled("█", Style::default().fg(color));
-                let line = Line::from(vec![span]);
-                let text = Paragraph::new(line);
-                let area = Rect::new(px, py, 1, 1);
-                f.render_widget(text, area);
-            }
-        }
+        draw_line(
+            f, x1 as i16, y1 as i16, x2 as i16, y2 as i16, '█', color, size,
+        );
     }
 
     // Draw vertices
     for (px, py) in &transformed {
-        let x = *px as u16;
-        let y = *py as u16;
-        if x < size.width && y < size.height {
+        if let Some((x, y)) = cell_at(*px, *py, size) {
             let span = Span::styled("◆", Style::default().fg(Color::White));
             let line = Line::from(vec![span]);
             let text = Paragraph::new(line);
@@ -8120,17 +14438,19 @@ fn calculate_auto_layout(app: &App, size: Rect) -> Rect {
     let config = &app.config;
 
     // Calculate content dimensions
-    let max_label_width = app
-        .actions
+    let max_label_width = vertical_rows(app)
         .iter()
-        .map(|action| action.display_text(true).chars().count())
+        .map(|row| match row {
+            ListRow::Divider(name) => display_width(&category_divider(name, config)),
+            ListRow::Action(i) => display_width(&accessible_text(app, *i, &list_text(app, *i))),
+        })
         .max()
-        .unwrap_or(0) as u16;
+        .unwrap_or(0);
 
     // Calculate menu dimensions
     let padding = config.layout.padding;
     let border_width = if config.border.enabled { 2 } else { 0 };
-    let title_width = config.title.chars().count() as u16;
+    let title_width = display_width(&app.title);
 
     // Content width + padding on both sides + borders
     let content_width = max_label_width.max(title_width.saturating_sub(2));
@@ -8146,14 +14466,14 @@ fn calculate_auto_layout(app: &App, size: Rect) -> Rect {
     // Ensure minimum width
     let final_width = final_width.max(config.layout.min_width);
 
-    // Calculate height based on number of actions + borders + padding
-    let action_count = app.actions.len() as u16;
+    // Calculate height based on number of visible actions + borders + padding
+    let action_count = visible_action_rows(app);
     let menu_height = action_count + (padding * 2) + border_width;
-    let final_height = menu_height.max(config.layout.min_height);
+    let final_height = menu_height.max(config.layout.min_height).min(size.height);
 
-    // Center the menu
+    // Center the menu horizontally, place it vertically per `layout.position`
     let x = (size.width.saturating_sub(final_width)) / 2;
-    let y = (size.height.saturating_sub(final_height)) / 2;
+    let y = menu_y(&config.layout, size, final_height);
 
     Rect {
         x,
@@ -8163,6 +14483,70 @@ fn calculate_auto_layout(app: &App, size: Rect) -> Rect {
     }
 }
 
+/// Number of list rows the vertical layout asks for, honoring `max_visible_items`
+fn visible_action_rows(app: &App) -> u16 {
+    let count = vertical_rows(app).len();
+    let count = app
+        .config
+        .layout
+        .max_visible_items
+        .map_or(count, |max| count.min(max.max(1)));
+    count as u16
+}
+
+/// Area of the vertical menu: the whole terminal above the help line in
+/// fullscreen mode, otherwise the auto-scaled or margin-based box
+fn vertical_menu_area(app: &App, size: Rect, auto_scale: bool) -> Rect {
+    if app.config.layout.fullscreen {
+        let help_rows = if app.config.help_text.enabled { 1 } else { 0 };
+        Rect {
+            height: size.height.saturating_sub(help_rows),
+            ..size
+        }
+    } else if auto_scale {
+        calculate_auto_layout(app, size)
+    } else {
+        calculate_fixed_layout(app, size)
+    }
+}
+
+/// How many actions fit in the vertical list for the given terminal size
+fn vertical_visible_items(app: &App, size: Rect, auto_scale: bool) -> usize {
+    let area = vertical_menu_area(app, size, auto_scale);
+    // Top and bottom rows hold the border, or the title and scroll indicator without one
+    let rows = area.height.saturating_sub(2) as usize;
+    let rows = app
+        .config
+        .layout
+        .max_visible_items
+        .map_or(rows, |max| rows.min(max));
+    rows.max(1)
+}
+
+/// Top row of a menu of `height` rows according to `layout.position`
+fn menu_y(layout: &LayoutConfig, size: Rect, height: u16) -> u16 {
+    let free = size.height.saturating_sub(height);
+    let y = match layout.position.as_str() {
+        "top" => layout.vertical_margin.min(free),
+        "bottom" => free.saturating_sub(layout.vertical_margin),
+        "custom" => (free as u32 * layout.vertical_margin.min(100) as u32 / 100) as u16,
+        _ => free / 2,
+    };
+    size.y + y
+}
+
+/// Move a percentage-margin layout away from the center when requested
+fn position_fixed_layout(config: &Config, size: Rect, area: Rect) -> Rect {
+    match config.layout.position.as_str() {
+        "top" | "bottom" | "custom" => Rect {
+            y: menu_y(&config.layout, size, area.height),
+            ..area
+        },
+        // The symmetric margins already center it
+        _ => area,
+    }
+}
+
 fn calculate_fixed_layout(app: &App, size: Rect) -> Rect {
     let config = &app.config;
 
@@ -8189,7 +14573,7 @@ fn calculate_fixed_layout(app: &App, size: Rect) -> Rect {
         .constraints(horizontal_constraints)
         .split(vertical_chunks[1]);
 
-    horizontal_chunks[1]
+    position_fixed_layout(config, size, horizontal_chunks[1])
 }
 
 fn render_help_text(f: &mut Frame, app: &App, size: Rect) {
@@ -8206,7 +14590,22 @@ fn render_help_text(f: &mut Frame, app: &App, size: Rect) {
     let select_keys = config.keys.select.join("/");
     let quit_keys = config.keys.quit.join("/");
 
-    let help_spans = vec![
+    let mut help_spans = Vec::new();
+
+    // Still usable, but the menu or help may not fit completely
+    let responsive = &config.responsive;
+    if responsive.enabled
+        && (size.width < responsive.warn_terminal_width
+            || size.height < responsive.warn_terminal_height)
+    {
+        help_spans.push(Span::styled(
+            "⚠ Terminal small, some elements may be cut",
+            Style::default().fg(Color::Yellow),
+        ));
+        help_spans.push(Span::raw(&help_config.separator));
+    }
+
+    help_spans.extend([
         Span::styled(
             format!("{}/{}", up_keys, down_keys),
             Style::default()
@@ -8230,20 +14629,412 @@ fn render_help_text(f: &mut Frame, app: &App, size: Rect) {
                 .add_modifier(help_key_modifier),
         ),
         Span::styled(" Quit", Style::default().fg(help_fg)),
+    ]);
+    if let Some(remaining) = app.idle_remaining_secs() {
+        if remaining < config.auto_trigger.idle_warning_secs {
+            help_spans.push(Span::raw(&help_config.separator));
+            help_spans.push(Span::styled(
+                format!("Idle: {}s", remaining),
+                Style::default()
+                    .fg(help_key_fg)
+                    .add_modifier(help_key_modifier),
+            ));
+        }
+    }
+    if let Some(remaining) = app.auto_quit_remaining_secs().filter(|&r| r < 10) {
+        help_spans.push(Span::raw(&help_config.separator));
+        help_spans.push(Span::styled(
+            format!("(closing in {}s)", remaining),
+            Style::default().fg(help_fg),
+        ));
+    }
+    if help_config.show_help_overlay_hint {
+        help_spans.push(Span::raw(&help_config.separator));
+        help_spans.push(Span::styled(
+            "?",
+            Style::default()
+                .fg(help_key_fg)
+                .add_modifier(help_key_modifier),
+        ));
+        help_spans.push(Span::styled(" for help", Style::default().fg(help_fg)));
+    }
+
+    let help_area = Rect {
+        x: 0,
+        y: size.height.saturating_sub(1),
+        width: size.width,
+        height: 1,
+    };
+
+    let help_text = Paragraph::new(Line::from(help_spans))
+        .alignment(Alignment::Center)
+        .style(Style::default().fg(help_fg));
+
+    f.render_widget(help_text, help_area);
+}
+
+/// Full-screen list of every keybinding and action shortcut
+fn render_help_overlay(f: &mut Frame, app: &App, size: Rect) {
+    let config = &app.config;
+    let fg_color = parse_color(&config.colors.foreground);
+    let border_color = parse_color(&config.colors.border);
+    let help_key_fg = parse_color(&config.colors.help_key_fg);
+    let help_key_modifier = parse_modifier(&config.colors.help_key_modifier);
+
+    // Dim everything behind the table: empty cells get a dark shade pattern,
+    // the menu and animation stay visible in a muted color
+    let buffer = f.buffer_mut();
+    for y in size.top()..size.bottom() {
+        for x in size.left()..size.right() {
+            let cell = &mut buffer[(x, y)];
+            if cell.symbol() == " " {
+                cell.set_symbol("▒");
+                cell.set_fg(Color::Rgb(40, 40, 40));
+            } else {
+                cell.set_fg(Color::Rgb(90, 90, 90));
+            }
+        }
+    }
+
+    let keys = &config.keys;
+    let mut entries: Vec<(String, String)> = vec![
+        (keys.up.join("/"), "Move up".to_string()),
+        (keys.down.join("/"), "Move down".to_string()),
+        (keys.select.join("/"), "Select".to_string()),
+        (keys.quit.join("/"), "Quit".to_string()),
+        (keys.confirm_yes_keys.join("/"), "Dialog: yes".to_string()),
+        (keys.confirm_no_keys.join("/"), "Dialog: no".to_string()),
+        (keys.animation_menu.join("/"), "Animation menu".to_string()),
+        (keys.theme_menu.join("/"), "Theme menu".to_string()),
+        (keys.help.join("/"), "Toggle this help".to_string()),
     ];
+    entries.extend(
+        app.actions
+            .iter()
+            .filter(|a| !a.shortcut.is_empty())
+            .map(|a| (a.shortcut.clone(), a.label.clone())),
+    );
+    entries.push(("↑↑↓↓←→←→ b a".to_string(), "Rainbow mode".to_string()));
+
+    let key_width = entries
+        .iter()
+        .map(|(k, _)| display_width(k))
+        .max()
+        .unwrap_or(3)
+        .max(3);
+    let action_width = entries
+        .iter()
+        .map(|(_, a)| display_width(a))
+        .max()
+        .unwrap_or(6)
+        .max(6);
+
+    // Columns plus spacing and borders; header row plus borders
+    let width = (key_width + action_width + 5).min(size.width);
+    let height = (entries.len() as u16 + 3).min(size.height);
+    let area = Rect {
+        x: (size.width.saturating_sub(width)) / 2,
+        y: (size.height.saturating_sub(height)) / 2,
+        width,
+        height,
+    };
+
+    // Clear background under the table
+    f.render_widget(Clear, area);
+    let clear = Block::default().style(Style::default().bg(Color::Black));
+    f.render_widget(clear, area);
+
+    let key_style = Style::default()
+        .fg(help_key_fg)
+        .add_modifier(help_key_modifier);
+    let rows: Vec<Row> = entries
+        .into_iter()
+        .map(|(key, action)| {
+            Row::new(vec![
+                Cell::from(key).style(key_style),
+                Cell::from(action).style(Style::default().fg(fg_color)),
+            ])
+        })
+        .collect();
+
+    let header = Row::new(vec!["Key", "Action"])
+        .style(Style::default().fg(fg_color).add_modifier(Modifier::BOLD));
+
+    let table = Table::new(
+        rows,
+        [Constraint::Length(key_width), Constraint::Min(action_width)],
+    )
+    .header(header)
+    .column_spacing(1)
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(" Help ")
+            .title_alignment(Alignment::Center)
+            .border_style(Style::default().fg(border_color))
+            .border_set(border_set(&config.border)),
+    );
+    f.render_widget(table, area);
+}
+
+// ============================================================================
+// IPC
+// ============================================================================
+
+/// A single newline-delimited JSON command received on the IPC socket
+#[derive(Debug, Default, Deserialize)]
+struct IpcRequest {
+    /// Trigger the action with this id (or label) as if the user selected it
+    action: Option<String>,
+    /// Exit rexit
+    #[serde(default)]
+    quit: bool,
+    /// Reply with the current state and actions
+    #[serde(default)]
+    status: bool,
+}
+
+/// A request forwarded from the socket thread to the event loop
+struct IpcMessage {
+    request: IpcRequest,
+    /// Channel for the JSON reply (only used by status requests)
+    reply: mpsc::Sender<String>,
+}
+
+/// Removes the socket file when rexit exits cleanly
+struct IpcSocketGuard {
+    path: PathBuf,
+}
+
+impl Drop for IpcSocketGuard {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.path);
+    }
+}
+
+fn get_ipc_socket_path() -> Option<PathBuf> {
+    std::env::var_os("XDG_RUNTIME_DIR")
+        .filter(|dir| !dir.is_empty())
+        .map(|dir| PathBuf::from(dir).join("rexit.sock"))
+}
+
+/// Bind the IPC socket and spawn the listener thread
+fn start_ipc_server() -> Result<(IpcSocketGuard, mpsc::Receiver<IpcMessage>)> {
+    use std::os::unix::net::{UnixListener, UnixStream};
+
+    let path = get_ipc_socket_path().context("XDG_RUNTIME_DIR is not set")?;
+
+    // A stale socket from a crashed instance would make bind() fail, but one
+    // that still accepts connections belongs to a running rexit
+    match UnixStream::connect(&path) {
+        Ok(_) => anyhow::bail!("another rexit instance is listening on {}", path.display()),
+        Err(e) if e.kind() == io::ErrorKind::ConnectionRefused => {
+            fs::remove_file(&path)
+                .with_context(|| format!("Failed to remove stale socket: {}", path.display()))?;
+        }
+        Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+        Err(e) => {
+            return Err(e)
+                .with_context(|| format!("Failed to check IPC socket: {}", path.display()))
+        }
+    }
+
+    let listener = UnixListener::bind(&path)
+        .with_context(|| format!("Failed to bind IPC socket: {}", path.display()))?;
+    let (tx, rx) = mpsc::channel();
+
+    std::thread::spawn(move || {
+        for stream in listener.incoming().flatten() {
+            let tx = tx.clone();
+            std::thread::spawn(move || handle_ipc_connection(stream, tx));
+        }
+    });
+
+    Ok((IpcSocketGuard { path }, rx))
+}
+
+fn handle_ipc_connection(stream: std::os::unix::net::UnixStream, tx: mpsc::Sender<IpcMessage>) {
+    use std::io::{BufRead, BufReader};
+
+    let Ok(mut writer) = stream.try_clone() else {
+        return;
+    };
+
+    for line in BufReader::new(stream).lines() {
+        let Ok(line) = line else {
+            break;
+        };
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let response = match serde_json::from_str::<IpcRequest>(&line) {
+            Ok(request) => {
+                let (reply_tx, reply_rx) = mpsc::channel();
+                if tx
+                    .send(IpcMessage {
+                        request,
+                        reply: reply_tx,
+                    })
+                    .is_err()
+                {
+                    break;
+                }
+                reply_rx
+                    .recv_timeout(std::time::Duration::from_secs(2))
+                    .unwrap_or_else(|_| serde_json::json!({ "ok": true }).to_string())
+            }
+            Err(e) => serde_json::json!({ "ok": false, "error": e.to_string() }).to_string(),
+        };
+
+        if writeln!(writer, "{}", response).is_err() {
+            break;
+        }
+    }
+}
+
+impl std::fmt::Display for AppState {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            AppState::Selecting => write!(f, "Selecting"),
+            AppState::Confirming {
+                action_ref, focus, ..
+            } => write!(
+                f,
+                "Confirming {{ action: {:?}, focus: {:?} }}",
+                action_ref, focus
+            ),
+            AppState::GracePeriod {
+                action_ref,
+                remaining_secs,
+                ..
+            } => write!(
+                f,
+                "GracePeriod {{ action: {:?}, remaining_secs: {} }}",
+                action_ref, remaining_secs
+            ),
+            AppState::AnimationMenu => write!(f, "AnimationMenu"),
+            AppState::ThemeMenu { theme_index } => {
+                write!(f, "ThemeMenu {{ theme_index: {} }}", theme_index)
+            }
+            AppState::Running {
+                action_ref,
+                child_pid,
+                ..
+            } => write!(
+                f,
+                "Running {{ action: {:?}, child_pid: {} }}",
+                action_ref, child_pid
+            ),
+            AppState::CommandError {
+                action_ref,
+                message,
+                ..
+            } => write!(
+                f,
+                "CommandError {{ action: {:?}, message: {:?} }}",
+                action_ref, message
+            ),
+            AppState::HelpOverlay => write!(f, "HelpOverlay"),
+            AppState::SubMenu {
+                parent_index,
+                selected,
+                ..
+            } => write!(
+                f,
+                "SubMenu {{ parent_index: {}, selected: {} }}",
+                parent_index, selected
+            ),
+        }
+    }
+}
+
+/// Append "[HH:MM:SS.mmm] from → to" to the debug log
+fn log_state_transition(log: &mut impl Write, from: &AppState, to: &AppState) {
+    let time = chrono::Local::now().format("%H:%M:%S%.3f");
+    // A failing debug log must not take the menu down
+    let _ = writeln!(log, "[{}] {} → {}", time, from, to);
+}
+
+fn app_state_name(state: &AppState) -> &'static str {
+    match state {
+        AppState::Selecting => "selecting",
+        AppState::Confirming { .. } => "confirming",
+        AppState::GracePeriod { .. } => "grace_period",
+        AppState::AnimationMenu => "animation_menu",
+        AppState::ThemeMenu { .. } => "theme_menu",
+        AppState::Running { .. } => "running",
+        AppState::CommandError { .. } => "error",
+        AppState::HelpOverlay => "help",
+        AppState::SubMenu { .. } => "submenu",
+    }
+}
+
+fn handle_ipc_message(app: &mut App, message: IpcMessage) -> Result<()> {
+    let IpcMessage { request, reply } = message;
+
+    if request.status {
+        let actions: Vec<serde_json::Value> = app
+            .actions
+            .iter()
+            .enumerate()
+            .map(|(i, action)| {
+                serde_json::json!({
+                    "id": action.id,
+                    "label": action.label,
+                    "shortcut": action.shortcut,
+                    "selected": i == app.selected_index,
+                })
+            })
+            .collect();
+        let mut status = serde_json::json!({
+            "ok": true,
+            "state": app_state_name(&app.state),
+            "actions": actions,
+        });
+        if let AppState::Running { child_pid, .. } = app.state {
+            status["pid"] = serde_json::json!(child_pid);
+        }
+        let _ = reply.send(status.to_string());
+        return Ok(());
+    }
 
-    let help_area = Rect {
-        x: 0,
-        y: size.height.saturating_sub(1),
-        width: size.width,
-        height: 1,
-    };
+    if request.quit {
+        let _ = reply.send(serde_json::json!({ "ok": true }).to_string());
+        // An explicit quit request ends watch mode as well
+        app.should_quit = true;
+        return Ok(());
+    }
 
-    let help_text = Paragraph::new(Line::from(help_spans))
-        .alignment(Alignment::Center)
-        .style(Style::default().fg(help_fg));
+    if let Some(name) = request.action {
+        let index = app
+            .actions
+            .iter()
+            .position(|a| a.id.eq_ignore_ascii_case(&name) || a.label.eq_ignore_ascii_case(&name));
+        let Some(index) = index else {
+            let _ = reply.send(
+                serde_json::json!({ "ok": false, "error": format!("Unknown action: {}", name) })
+                    .to_string(),
+            );
+            return Ok(());
+        };
 
-    f.render_widget(help_text, help_area);
+        if matches!(app.state, AppState::Running { .. }) {
+            let _ = reply.send(
+                serde_json::json!({ "ok": false, "error": "An action is already running" })
+                    .to_string(),
+            );
+            return Ok(());
+        }
+
+        let _ = reply.send(serde_json::json!({ "ok": true }).to_string());
+        // Behave exactly like a selection from the main menu
+        app.state = AppState::Selecting;
+        return app.select_at_index(index);
+    }
+
+    let _ = reply.send(serde_json::json!({ "ok": false, "error": "Unknown command" }).to_string());
+    Ok(())
 }
 
 // ============================================================================
@@ -8255,6 +15046,12 @@ fn render_help_text(f: &mut Frame, app: &App, size: Rect) {
 #[command(author = "Ninso112")]
 #[command(version = "1.1.6")]
 #[command(about = "A rice-ready TUI power menu for Linux with multi-WM support", long_about = None)]
+#[command(group(clap::ArgGroup::new("import").args(["import_wlogout", "import_nwg_bar"])))]
+#[command(group(
+    clap::ArgGroup::new("writes_file")
+        .args(["generate_config", "output"])
+        .multiple(true)
+))]
 struct Cli {
     /// Generate default configuration file
     #[arg(short, long)]
@@ -8264,6 +15061,14 @@ struct Cli {
     #[arg(short, long, value_name = "PATH")]
     config: Option<PathBuf>,
 
+    /// Print the default configuration, or write it to PATH
+    #[arg(long, value_name = "PATH", num_args = 0..=1, default_missing_value = "-")]
+    generate_config: Option<Option<PathBuf>>,
+
+    /// Overwrite an existing file with --generate-config or --output
+    #[arg(long, requires = "writes_file")]
+    force: bool,
+
     /// Specify theme to use (loads from ~/.config/rexit/themes/<name>.toml)
     #[arg(short, long, value_name = "NAME")]
     theme: Option<String>,
@@ -8272,6 +15077,14 @@ struct Cli {
     #[arg(long)]
     list_themes: bool,
 
+    /// List all animation types, one per line
+    #[arg(long)]
+    list_animations: bool,
+
+    /// List enabled actions as tab-separated id, label and shortcut
+    #[arg(long)]
+    list_actions: bool,
+
     /// Validate configuration file and exit
     #[arg(long)]
     check_config: bool,
@@ -8279,6 +15092,201 @@ struct Cli {
     /// Use emoji icons instead of Nerd Fonts
     #[arg(long)]
     emoji: bool,
+
+    /// Listen for JSON commands on $XDG_RUNTIME_DIR/rexit.sock
+    #[arg(long)]
+    ipc: bool,
+
+    /// Disable the background animation
+    #[arg(long)]
+    no_animation: bool,
+
+    /// Seed the background animation for reproducible screenshots (0 = random)
+    #[arg(long, value_name = "SEED")]
+    animation_seed: Option<u64>,
+
+    /// List the REXIT_* environment variables and exit
+    #[arg(long)]
+    help_env: bool,
+
+    /// Save the current colors, border and animation as a theme
+    #[arg(long, value_name = "NAME")]
+    export_theme: Option<String>,
+
+    /// Copy a theme file into ~/.config/rexit/themes/
+    #[arg(long, value_name = "PATH")]
+    import_theme: Option<PathBuf>,
+
+    /// Convert a wlogout layout file into a rexit config
+    #[arg(long, value_name = "PATH")]
+    import_wlogout: Option<PathBuf>,
+
+    /// Convert an nwg-bar JSON file into a rexit config
+    #[arg(long, value_name = "PATH")]
+    import_nwg_bar: Option<PathBuf>,
+
+    /// With --import-wlogout or --import-nwg-bar, write the config to PATH instead of stdout
+    #[arg(long, value_name = "PATH", requires = "import")]
+    output: Option<PathBuf>,
+
+    /// Print actions and settings as JSON instead of launching the TUI
+    #[arg(long)]
+    json: bool,
+
+    /// With --json, read {"execute": "<id>"} from stdin and run that action
+    #[arg(long, requires = "json")]
+    stdin: bool,
+
+    /// Append timestamped state transitions to PATH (overrides debug_log)
+    #[arg(long, value_name = "PATH")]
+    debug_log: Option<PathBuf>,
+
+    /// Also write key presses to the debug log
+    #[arg(long)]
+    debug_keys: bool,
+
+    /// Keep the menu open after Esc or Cancel; only a successful action or Ctrl-C exits
+    #[arg(short, long)]
+    watch: bool,
+
+    /// Close the menu after SECS seconds without a key press (overrides auto_quit_secs)
+    #[arg(long, value_name = "SECS")]
+    timeout: Option<u64>,
+
+    /// Vertical placement of the menu (overrides layout.position)
+    #[arg(long, value_name = "POSITION", value_parser = ["top", "center", "bottom", "custom"])]
+    position: Option<String>,
+
+    /// Change the label of an action; may be given several times
+    #[arg(long, value_names = ["ID", "LABEL"], num_args = 2)]
+    rename_action: Vec<String>,
+
+    /// Hide an action from the menu; may be given several times
+    #[arg(long, value_name = "ID")]
+    disable_action: Vec<String>,
+
+    /// Show an action that the config disables; may be given several times
+    #[arg(long, value_name = "ID")]
+    enable_action: Vec<String>,
+}
+
+/// Print every environment variable rexit reads
+fn print_env_vars() {
+    println!("Environment variables:");
+    println!("  REXIT_USE_EMOJI=1        Use emoji icons instead of Nerd Fonts");
+    println!("  REXIT_NO_ANIMATION=1     Disable the background animation (like --no-animation)");
+    println!("  REXIT_ANIMATION=<type>   Override the animation type (see --list-animations)");
+    println!("  REXIT_ICON_WIDTH=1|2     Cells every icon takes up (overrides force_icon_width)");
+}
+
+/// Change the label of the action `id` in the loaded config
+fn rename_action(config: &mut Config, id: &str, new_label: &str) -> Result<()> {
+    let action = config
+        .actions
+        .get_mut(id)
+        .with_context(|| format!("Unknown action: {}", id))?;
+    action.label = new_label.to_string();
+    Ok(())
+}
+
+/// Enable or disable the action `id` in the loaded config
+fn set_action_enabled(config: &mut Config, id: &str, enabled: bool) -> Result<()> {
+    let action = config
+        .actions
+        .get_mut(id)
+        .with_context(|| format!("Unknown action: {}", id))?;
+    action.enabled = enabled;
+    Ok(())
+}
+
+/// Configured actions in menu order: favorites first, then by label
+fn menu_ordered_actions(config: &Config) -> Vec<(&String, &ActionConfig)> {
+    let mut actions: Vec<(&String, &ActionConfig)> = config.actions.iter().collect();
+    actions.sort_by(|(_, a), (_, b)| {
+        b.favorite
+            .cmp(&a.favorite)
+            .then_with(|| a.label.cmp(&b.label))
+    });
+    actions
+}
+
+/// Command accepted on stdin by `--json --stdin`
+#[derive(Debug, Deserialize)]
+struct JsonCommand {
+    execute: String,
+}
+
+/// Print actions and settings as JSON for status bars and scripts
+fn print_json_status(config: &Config, config_path: Option<&PathBuf>) -> Result<()> {
+    let use_emoji = config.use_emoji_icons.unwrap_or_else(|| !has_nerd_fonts());
+
+    let actions: Vec<serde_json::Value> = menu_ordered_actions(config)
+        .into_iter()
+        .map(|(id, action)| {
+            serde_json::json!({
+                "id": id,
+                "label": action.label,
+                "icon": resolve_icon(action, use_emoji),
+                "shortcut": action.shortcut,
+                "enabled": action.enabled,
+                "confirm": action.confirm,
+                "description": action.description,
+            })
+        })
+        .collect();
+
+    let wm = if config.wm_type == "auto" {
+        detect_wm()
+    } else {
+        config.wm_type.clone()
+    };
+
+    let status = serde_json::json!({
+        "actions": actions,
+        "config_path": config_path.map(|path| path.display().to_string()),
+        "wm": wm,
+        "animation": config.animation.animation_type,
+        "last_action": load_last_executed(),
+    });
+    println!(
+        "{}",
+        serde_json::to_string_pretty(&status).context("Failed to serialize status")?
+    );
+
+    Ok(())
+}
+
+/// Run the action named by a `{"execute": "<id>"}` object read from stdin
+fn execute_json_command(config: Config) -> Result<()> {
+    use std::io::Read;
+
+    let mut input = String::new();
+    io::stdin()
+        .read_to_string(&mut input)
+        .context("Failed to read stdin")?;
+    let command: JsonCommand =
+        serde_json::from_str(&input).context("Failed to parse JSON from stdin")?;
+
+    // Build the app so WM-specific logout and lock fallbacks apply
+    let mut app = App::new(config);
+    let action = app
+        .actions
+        .iter()
+        .find(|a| {
+            a.id.eq_ignore_ascii_case(&command.execute)
+                || a.label.eq_ignore_ascii_case(&command.execute)
+        })
+        .cloned()
+        .with_context(|| format!("Unknown action: {}", command.execute))?;
+
+    action.execute()?;
+    app.remember_executed(&action);
+    println!(
+        "{}",
+        serde_json::json!({ "ok": true, "executed": action.id })
+    );
+
+    Ok(())
 }
 
 fn main() -> Result<()> {
@@ -8289,6 +15297,11 @@ fn main() -> Result<()> {
         return generate_config_file();
     }
 
+    // Handle --generate-config flag
+    if let Some(ref path) = cli.generate_config {
+        return write_generated_config(path.as_ref(), cli.force);
+    }
+
     // Handle --list-themes flag
     if cli.list_themes {
         println!("Available themes:");
@@ -8304,12 +15317,68 @@ fn main() -> Result<()> {
         return Ok(());
     }
 
+    // Handle --help-env flag
+    if cli.help_env {
+        print_env_vars();
+        return Ok(());
+    }
+
+    // Handle --import-theme flag
+    if let Some(ref path) = cli.import_theme {
+        return import_theme_file(path);
+    }
+
+    // Handle --import-wlogout and --import-nwg-bar flags
+    if let Some(ref path) = cli.import_wlogout {
+        let buttons = read_wlogout_layout(path)?;
+        return write_imported_config(path, &buttons, cli.output.as_ref(), cli.force);
+    }
+    if let Some(ref path) = cli.import_nwg_bar {
+        let buttons = read_nwg_bar_config(path)?;
+        return write_imported_config(path, &buttons, cli.output.as_ref(), cli.force);
+    }
+
+    // Handle --list-animations flag
+    if cli.list_animations {
+        for animation in ANIMATION_TYPES {
+            println!("{}", animation);
+        }
+        return Ok(());
+    }
+
     // Load configuration
+    let config_path = cli
+        .config
+        .clone()
+        .or_else(|| get_config_path().filter(|path| path.exists()));
     let mut config = if let Some(config_path) = cli.config {
         load_config_from_path(&config_path)?
     } else {
         load_config()
     };
+    merge_desktop_actions(&mut config);
+
+    // Handle --rename-action, --disable-action and --enable-action
+    for pair in cli.rename_action.chunks_exact(2) {
+        rename_action(&mut config, &pair[0], &pair[1])?;
+    }
+    for id in &cli.disable_action {
+        set_action_enabled(&mut config, id, false)?;
+    }
+    for id in &cli.enable_action {
+        set_action_enabled(&mut config, id, true)?;
+    }
+
+    // Handle --list-actions flag
+    if cli.list_actions {
+        for (id, action) in menu_ordered_actions(&config)
+            .into_iter()
+            .filter(|(_, a)| a.enabled)
+        {
+            println!("{}\t{}\t{}", id, action.label, action.shortcut);
+        }
+        return Ok(());
+    }
 
     // Handle --theme flag
     if let Some(theme_name) = cli.theme {
@@ -8323,11 +15392,70 @@ fn main() -> Result<()> {
         }
     }
 
+    // Handle --export-theme flag
+    if let Some(ref theme_name) = cli.export_theme {
+        return export_theme_file(&config, theme_name);
+    }
+
+    // Handle --position flag
+    if let Some(position) = cli.position {
+        config.layout.position = position;
+    }
+
+    // Handle --debug-log flag
+    if let Some(path) = cli.debug_log {
+        config.debug_log = Some(path);
+    }
+
+    // Handle --timeout flag
+    if let Some(secs) = cli.timeout {
+        config.auto_quit_secs = Some(secs);
+    }
+
     // Handle --emoji flag
     if cli.emoji {
         config.use_emoji_icons = Some(true);
     }
 
+    // Handle REXIT_ANIMATION env var
+    if let Ok(animation) = std::env::var("REXIT_ANIMATION") {
+        if animation == "random" || ANIMATION_TYPES.contains(&animation.as_str()) {
+            config.animation.animation_type = animation;
+        } else {
+            eprintln!(
+                "Warning: Unknown animation '{}' in REXIT_ANIMATION",
+                animation
+            );
+        }
+    }
+
+    // Handle REXIT_ICON_WIDTH env var
+    if let Ok(width) = std::env::var("REXIT_ICON_WIDTH") {
+        match width.as_str() {
+            "1" | "2" => config.force_icon_width = width.parse().ok(),
+            _ => eprintln!("Warning: REXIT_ICON_WIDTH must be 1 or 2, not '{}'", width),
+        }
+    }
+
+    // Handle --animation-seed flag
+    if let Some(seed) = cli.animation_seed {
+        config.animation.seed = Some(seed);
+    }
+
+    // Handle --no-animation flag and REXIT_NO_ANIMATION env var
+    if cli.no_animation || std::env::var("REXIT_NO_ANIMATION").is_ok_and(|v| v == "1") {
+        config.animation.enabled = false;
+        config.animation.animation_type = "none".to_string();
+    }
+
+    // Handle --json flag, optionally executing a command from stdin
+    if cli.json {
+        if cli.stdin {
+            return execute_json_command(config);
+        }
+        return print_json_status(&config, config_path.as_ref());
+    }
+
     // Handle --check-config flag
     if cli.check_config {
         println!("Configuration is valid!");
@@ -8336,13 +15464,58 @@ fn main() -> Result<()> {
         }
         println!("Layout mode: {}", config.layout_mode);
         println!("Animation: {}", config.animation.animation_type);
+        let caps = term_capabilities();
+        println!(
+            "Terminal: {}, Nerd Fonts {}",
+            caps.color_depth(),
+            if has_nerd_fonts() { "yes" } else { "no" }
+        );
         println!(
             "Actions enabled: {}",
             config.actions.values().filter(|a| a.enabled).count()
         );
+        let keys = &config.keys;
+        let menu_keys = keys.help.iter().chain(&keys.animation_menu);
+        for key in menu_keys.chain(&keys.theme_menu) {
+            if let Some((id, _)) = config
+                .actions
+                .iter()
+                .find(|(_, a)| a.enabled && a.shortcut.eq_ignore_ascii_case(key))
+            {
+                println!(
+                    "Warning: key \"{}\" opens a menu and is also the shortcut of \"{}\"",
+                    key, id
+                );
+            }
+        }
+        if config.auto_trigger.enabled {
+            let action_id = &config.auto_trigger.action_id;
+            if config
+                .actions
+                .iter()
+                .any(|(id, a)| a.enabled && id.eq_ignore_ascii_case(action_id))
+            {
+                println!(
+                    "Auto trigger: {} after {}s idle",
+                    action_id, config.auto_trigger.idle_secs
+                );
+            } else {
+                println!(
+                    "Warning: auto_trigger.action_id \"{}\" is not an enabled action",
+                    action_id
+                );
+            }
+        }
         return Ok(());
     }
 
+    // Handle --ipc flag (bind before touching the terminal so errors print cleanly)
+    let ipc = if cli.ipc {
+        Some(start_ipc_server()?)
+    } else {
+        None
+    };
+
     // Setup terminal
     enable_raw_mode().context("Failed to enable raw mode")?;
     let mut stdout = io::stdout();
@@ -8353,7 +15526,26 @@ fn main() -> Result<()> {
 
     // Run the app
     let mut app = App::new(config);
+    app.watch = cli.watch;
+    if let Some(path) = &app.config.debug_log {
+        let file = fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .with_context(|| format!("Failed to open debug log {}", path.display()))?;
+        app.debug_log = Some(BufWriter::new(file));
+        app.debug_keys = cli.debug_keys;
+    }
+    app.title = app.expanded_title();
+    let _ipc_guard = ipc.map(|(guard, receiver)| {
+        app.ipc_receiver = Some(receiver);
+        guard
+    });
     let res = run_app(&mut terminal, &mut app);
+    app.release_inhibitor();
+    if let Some(log) = app.debug_log.as_mut() {
+        let _ = log.flush();
+    }
 
     // Restore terminal
     disable_raw_mode().context("Failed to disable raw mode")?;
@@ -8384,7 +15576,7 @@ fn generate_config_file() -> Result<()> {
         )
     })?;
 
-    let default_config = generate_default_config();
+    let default_config = generated_config_with_header();
 
     fs::write(&config_path, default_config)
         .with_context(|| format!("Failed to write config file: {}", config_path.display()))?;
@@ -8395,15 +15587,315 @@ fn generate_config_file() -> Result<()> {
     );
     println!("Edit this file to customize rexit's appearance and behavior.");
 
-    Ok(())
+    Ok(())
+}
+
+/// The default configuration, prefixed with a comment recording when it was generated
+fn generated_config_with_header() -> String {
+    format!(
+        "## Generated by rexit {} on {}\n{}",
+        env!("CARGO_PKG_VERSION"),
+        chrono::Local::now().format("%Y-%m-%d"),
+        generate_default_config()
+    )
+}
+
+/// Write the default configuration to `path`, or to stdout when no path (or "-") is given
+fn write_generated_config(path: Option<&PathBuf>, force: bool) -> Result<()> {
+    write_config_text(path, &generated_config_with_header(), force)
+}
+
+/// Print a config, or write it to `path` and print the file's absolute path
+fn write_config_text(path: Option<&PathBuf>, content: &str, force: bool) -> Result<()> {
+    let path = match path {
+        Some(path) if path.as_os_str() != "-" => path,
+        _ => {
+            print!("{}", content);
+            return Ok(());
+        }
+    };
+
+    if path.exists() && !force {
+        anyhow::bail!(
+            "{} already exists (use --force to overwrite it)",
+            path.display()
+        );
+    }
+
+    if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+        fs::create_dir_all(dir)
+            .with_context(|| format!("Failed to create directory: {}", dir.display()))?;
+    }
+
+    fs::write(path, content)
+        .with_context(|| format!("Failed to write config file: {}", path.display()))?;
+
+    let absolute = fs::canonicalize(path).unwrap_or_else(|_| path.clone());
+    println!("{}", absolute.display());
+
+    Ok(())
+}
+
+fn export_theme_file(config: &Config, theme_name: &str) -> Result<()> {
+    let theme_path = get_theme_path(theme_name).context("Could not determine themes directory")?;
+    let themes_dir = theme_path.parent().context("Invalid theme path")?;
+
+    fs::create_dir_all(themes_dir).with_context(|| {
+        format!(
+            "Failed to create themes directory: {}",
+            themes_dir.display()
+        )
+    })?;
+
+    let theme = theme_from_config(theme_name, config);
+    let content = toml::to_string_pretty(&theme).context("Failed to serialize theme")?;
+
+    fs::write(&theme_path, content)
+        .with_context(|| format!("Failed to write theme file: {}", theme_path.display()))?;
+
+    println!("Theme exported to: {}", theme_path.display());
+
+    Ok(())
+}
+
+fn import_theme_file(path: &PathBuf) -> Result<()> {
+    let content = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read theme file: {}", path.display()))?;
+
+    toml::from_str::<ThemeConfig>(&content)
+        .with_context(|| format!("Failed to parse theme file: {}", path.display()))?;
+
+    let theme_name = path
+        .file_stem()
+        .and_then(|stem| stem.to_str())
+        .context("Invalid theme file name")?;
+    let theme_path = get_theme_path(theme_name).context("Could not determine themes directory")?;
+    let themes_dir = theme_path.parent().context("Invalid theme path")?;
+
+    fs::create_dir_all(themes_dir).with_context(|| {
+        format!(
+            "Failed to create themes directory: {}",
+            themes_dir.display()
+        )
+    })?;
+
+    fs::write(&theme_path, content)
+        .with_context(|| format!("Failed to write theme file: {}", theme_path.display()))?;
+
+    println!(
+        "Theme '{}' imported to: {}",
+        theme_name,
+        theme_path.display()
+    );
+
+    Ok(())
+}
+
+/// A button of another power menu, before it becomes an action
+struct ImportedButton {
+    id: String,
+    label: String,
+    command: String,
+    shortcut: String,
+}
+
+/// Words in a label or command that identify a well-known power action
+const IMPORT_KINDS: &[(&str, &[&str])] = &[
+    ("shutdown", &["shutdown", "poweroff", "power off"]),
+    ("reboot", &["reboot", "restart"]),
+    ("hibernate", &["hibernate"]),
+    ("suspend", &["suspend", "sleep"]),
+    ("lock", &["lock"]),
+    ("logout", &["logout", "log out", "exit", "terminate-"]),
+];
+
+/// Icons for the kinds in IMPORT_KINDS, used for both Nerd Font and emoji mode
+const IMPORT_ICONS: &[(&str, &str)] = &[
+    ("shutdown", "⏻"),
+    ("reboot", "🔄"),
+    ("hibernate", "💤"),
+    ("suspend", "🌙"),
+    ("lock", "🔒"),
+    ("logout", "🚪"),
+];
+
+/// Read a wlogout layout: JSON objects with `label`, `action`, `text` and `keybind`
+///
+/// wlogout writes the objects one after another; a JSON array is accepted as well.
+fn read_wlogout_layout(path: &PathBuf) -> Result<Vec<ImportedButton>> {
+    let content = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read wlogout layout: {}", path.display()))?;
+
+    let mut buttons = Vec::new();
+    for value in serde_json::Deserializer::from_str(&content).into_iter::<serde_json::Value>() {
+        let value =
+            value.with_context(|| format!("Failed to parse wlogout layout: {}", path.display()))?;
+        let entries = match value {
+            serde_json::Value::Array(entries) => entries,
+            entry => vec![entry],
+        };
+        for entry in entries {
+            let field = |name: &str| entry[name].as_str().unwrap_or_default().to_string();
+            let id = import_id(&field("label"));
+            let label = Some(field("text"))
+                .filter(|text| !text.is_empty())
+                .unwrap_or_else(|| field("label"));
+            buttons.push(ImportedButton {
+                id,
+                label,
+                command: field("action"),
+                shortcut: field("keybind"),
+            });
+        }
+    }
+    Ok(buttons)
+}
+
+/// Read an nwg-bar config: a JSON array of objects with `label`, `exec` and `icon`
+fn read_nwg_bar_config(path: &PathBuf) -> Result<Vec<ImportedButton>> {
+    let content = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read nwg-bar config: {}", path.display()))?;
+    let entries: Vec<serde_json::Value> = serde_json::from_str(&content)
+        .with_context(|| format!("Failed to parse nwg-bar config: {}", path.display()))?;
+
+    Ok(entries
+        .iter()
+        .map(|entry| {
+            let label = entry["label"].as_str().unwrap_or_default().to_string();
+            ImportedButton {
+                id: import_id(&label),
+                label,
+                command: entry["exec"].as_str().unwrap_or_default().to_string(),
+                shortcut: String::new(),
+            }
+        })
+        .collect())
+}
+
+/// Turn a label into an action id: lowercase, with `_` for anything but letters and digits
+fn import_id(label: &str) -> String {
+    label
+        .trim()
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() {
+                c.to_ascii_lowercase()
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+/// Guess which power action a button performs from its label, then its command
+fn import_kind(label: &str, command: &str) -> Option<&'static str> {
+    [label, command].iter().find_map(|text| {
+        let text = text.to_lowercase();
+        IMPORT_KINDS
+            .iter()
+            .find(|(_, words)| words.iter().any(|word| text.contains(word)))
+            .map(|(kind, _)| *kind)
+    })
+}
+
+/// Convert a button into an action
+///
+/// Plain commands such as `systemctl poweroff` or `loginctl terminate-user $USER` are
+/// split into command and args; anything using shell syntax runs through `sh -c`.
+fn imported_action(button: &ImportedButton) -> ActionConfig {
+    let kind = import_kind(&button.label, &button.command);
+    let icon = kind
+        .and_then(|kind| IMPORT_ICONS.iter().find(|(k, _)| *k == kind))
+        .map_or("•", |(_, icon)| icon)
+        .to_string();
+
+    let shell = button
+        .command
+        .contains(['|', '&', ';', '>', '<', '(', '`', '"', '\'']);
+    let mut words = button.command.split_whitespace().map(str::to_string);
+    let (command, args) = if shell {
+        (button.command.clone(), Vec::new())
+    } else {
+        (words.next().unwrap_or_default(), words.collect())
+    };
+
+    ActionConfig {
+        icon_fallback: Some(icon.clone()),
+        icon,
+        label: button.label.clone(),
+        command,
+        args,
+        enabled: true,
+        confirm: matches!(kind, Some("shutdown" | "reboot" | "logout")),
+        favorite: false,
+        shortcut: button.shortcut.clone(),
+        expand_env: true,
+        shell,
+        category: None,
+        description: None,
+        submenu: None,
+        icon_cell_width: None,
+        max_retries: 0,
+    }
+}
+
+/// Write a default config whose actions are the imported buttons
+fn write_imported_config(
+    source: &Path,
+    buttons: &[ImportedButton],
+    output: Option<&PathBuf>,
+    force: bool,
+) -> Result<()> {
+    let mut actions = HashMap::new();
+    for button in buttons {
+        // Labels made only of symbols turn into ids like "___"
+        if button.id.trim_matches('_').is_empty() {
+            eprintln!(
+                "Warning: Skipping a button whose label gives no usable id (command: {:?})",
+                button.command
+            );
+            continue;
+        }
+        // Buttons sharing a label get "_2", "_3", ... instead of replacing each other
+        let mut id = button.id.clone();
+        let mut suffix = 2;
+        while actions.contains_key(&id) {
+            id = format!("{}_{}", button.id, suffix);
+            suffix += 1;
+        }
+        actions.insert(id, imported_action(button));
+    }
+    if actions.is_empty() {
+        anyhow::bail!("No buttons found in {}", source.display());
+    }
+
+    let config = Config {
+        actions,
+        ..Config::default()
+    };
+
+    let content = format!(
+        "# rexit configuration imported from {}\n\n{}",
+        source.display(),
+        toml::to_string_pretty(&config).context("Failed to serialize config")?
+    );
+    write_config_text(output, &content, force)
 }
 
 fn load_config_from_path(path: &PathBuf) -> Result<Config> {
     let content = fs::read_to_string(path)
         .with_context(|| format!("Failed to read config file: {}", path.display()))?;
 
-    let config = toml::from_str(&content)
-        .with_context(|| format!("Failed to parse config file: {}", path.display()))?;
+    let mut config = parse_config(&content).map_err(|e| {
+        let mut message = format!("Failed to parse config file: {}", path.display());
+        for issue in config_issues(&content) {
+            message.push_str("\n  ");
+            message.push_str(&issue);
+        }
+        anyhow::Error::new(e).context(message)
+    })?;
+    apply_high_contrast_animation(&mut config, &content);
+    validate_config(&mut config);
 
     Ok(config)
 }
@@ -8413,20 +15905,8 @@ fn run_app<B: ratatui::backend::Backend>(terminal: &mut Terminal<B>, app: &mut A
     let (cols, rows) = terminal::size().unwrap_or((80, 24));
     let size = Rect::new(0, 0, cols, rows);
 
-    // Check if terminal is too small
+    // A terminal below the minimum size gets a hint from ui() until it is enlarged
     if app.config.responsive.enabled {
-        if cols < app.config.responsive.min_terminal_width
-            || rows < app.config.responsive.min_terminal_height
-        {
-            return Err(anyhow::anyhow!(
-                "Terminal too small: {}x{}. Minimum required: {}x{}",
-                cols,
-                rows,
-                app.config.responsive.min_terminal_width,
-                app.config.responsive.min_terminal_height
-            ));
-        }
-
         // Auto-switch to compact layout if terminal is narrow
         if app.config.layout_mode == "vertical" && cols < app.config.responsive.compact_threshold {
             app.config.layout_mode = "compact".to_string();
@@ -8441,59 +15921,161 @@ fn run_app<B: ratatui::backend::Backend>(terminal: &mut Terminal<B>, app: &mut A
         if app.config.responsive.hide_border_when_small && (cols < 60 || rows < 15) {
             app.config.border.enabled = false;
         }
+
+        // A compact bar of only a few icons looks odd, so list them instead
+        if app.config.layout_mode == "compact"
+            && app.actions.len() < app.config.responsive.min_actions_for_compact
+        {
+            app.config.layout_mode = "vertical".to_string();
+        }
     }
 
     // Initialize animation with actual terminal size
     app.animation_state.init(&app.config, size);
 
+    const MAX_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+    let mut next_frame = Instant::now();
     loop {
+        app.log_state_change();
+
+        // Advance the animation on a fixed schedule, however many events arrive
+        if Instant::now() >= next_frame {
+            let (cols, rows) = terminal::size().unwrap_or((80, 24));
+            app.update_animation(Rect::new(0, 0, cols, rows));
+            next_frame = Instant::now() + app.frame_interval();
+        }
+
+        app.performance_monitor.begin_frame();
         terminal.draw(|f| ui(f, app))?;
+        app.performance_monitor.end_frame();
 
         if app.should_quit {
             break;
         }
 
         // Update grace period countdown
-        if matches!(app.state, AppState::GracePeriod { .. }) {
-            if app.update_grace_period()? {
-                break; // Grace period expired and action executed
+        if matches!(app.state, AppState::GracePeriod { .. }) && app.update_grace_period()? {
+            break; // Grace period expired and action executed
+        }
+
+        // Cancel a confirmation dialog left unanswered for confirm_timeout_secs
+        app.update_confirm();
+
+        // Trigger the idle action once no input arrived for auto_trigger.idle_secs
+        app.update_auto_trigger()?;
+
+        // Close the menu once no key was pressed for auto_quit_secs
+        app.update_auto_quit();
+        if app.should_quit {
+            break;
+        }
+
+        // Check whether a running action has finished or timed out
+        if matches!(app.state, AppState::Running { .. }) {
+            app.update_running()?;
+            if app.should_quit {
+                break;
             }
         }
 
-        if event::poll(std::time::Duration::from_millis(100))? {
+        // Process commands received on the IPC socket
+        let ipc_messages: Vec<IpcMessage> = app
+            .ipc_receiver
+            .as_ref()
+            .map(|rx| rx.try_iter().collect())
+            .unwrap_or_default();
+        for message in ipc_messages {
+            handle_ipc_message(app, message)?;
+        }
+        if app.should_quit {
+            break;
+        }
+
+        // Wake up for the next frame, but often enough that countdowns,
+        // timeouts, running actions and IPC requests never wait on a slow animation
+        let until_frame = next_frame.saturating_duration_since(Instant::now());
+        if event::poll(until_frame.min(MAX_POLL_INTERVAL))? {
             match event::read()? {
-                Event::Key(key) => {
-                    if key.kind == KeyEventKind::Press {
-                        // Handle different states
-                        match &app.state {
-                            AppState::Confirming { .. } => {
-                                handle_confirmation_input(app, &key)?;
-                            }
-                            AppState::GracePeriod { .. } => {
-                                handle_grace_period_input(app, &key)?;
-                            }
-                            AppState::AnimationMenu => {
-                                handle_animation_menu_input(app, &key)?;
-                            }
-                            AppState::Selecting => {
-                                handle_selecting_input(app, &key)?;
-                            }
+                Event::Key(key) if key.kind == KeyEventKind::Press => {
+                    app.last_input = Instant::now();
+                    app.menu_opened_at = Instant::now();
+                    app.log_key(&key);
+
+                    // Ctrl-C always exits, even in watch mode
+                    if key.code == KeyCode::Char('c')
+                        && key
+                            .modifiers
+                            .contains(crossterm::event::KeyModifiers::CONTROL)
+                    {
+                        app.should_quit = true;
+                        continue;
+                    }
+
+                    // The secret word sees every letter before shortcuts act on it
+                    if app.easter_egg.check_secret_word(key.code) {
+                        continue;
+                    }
+
+                    if app.config.global_shortcuts && handle_global_shortcut(app, &key)? {
+                        continue;
+                    }
+
+                    // Handle different states
+                    match &app.state {
+                        AppState::Confirming { .. } => {
+                            handle_confirmation_input(app, &key)?;
+                        }
+                        AppState::GracePeriod { .. } => {
+                            handle_grace_period_input(app, &key)?;
+                        }
+                        AppState::AnimationMenu => {
+                            handle_animation_menu_input(app, &key)?;
+                        }
+                        AppState::ThemeMenu { .. } => {
+                            handle_theme_menu_input(app, &key)?;
+                        }
+                        // Keys are ignored until the command exits
+                        AppState::Running { .. } => {}
+                        AppState::CommandError { .. } => {
+                            handle_error_input(app, &key)?;
+                        }
+                        AppState::HelpOverlay => {
+                            handle_help_overlay_input(app, &key);
+                        }
+                        AppState::SubMenu { .. } => {
+                            handle_submenu_input(app, &key)?;
+                        }
+                        AppState::Selecting => {
+                            handle_selecting_input(app, &key)?;
                         }
                     }
                 }
                 Event::Mouse(mouse) => {
+                    app.last_input = Instant::now();
                     handle_mouse_input(app, mouse)?;
                 }
                 _ => {}
             }
         }
     }
+    app.log_state_change();
     Ok(())
 }
 
 fn handle_animation_menu_input(app: &mut App, key: &crossterm::event::KeyEvent) -> Result<()> {
     use crossterm::event::KeyCode;
 
+    // Configured navigation keys (including Tab/Shift-Tab) also move through the list
+    if app.config.keys.up.iter().any(|k| app.check_key(k, key)) {
+        app.previous_animation();
+        return Ok(());
+    }
+    if app.config.keys.down.iter().any(|k| app.check_key(k, key)) {
+        app.next_animation();
+        return Ok(());
+    }
+
     match key.code {
         KeyCode::Up => {
             app.previous_animation();
@@ -8516,19 +16098,63 @@ fn handle_animation_menu_input(app: &mut App, key: &crossterm::event::KeyEvent)
     Ok(())
 }
 
-fn handle_confirmation_input(app: &mut App, key: &crossterm::event::KeyEvent) -> Result<()> {
+fn handle_theme_menu_input(app: &mut App, key: &crossterm::event::KeyEvent) -> Result<()> {
     use crossterm::event::KeyCode;
 
+    let (cols, rows) = terminal::size().unwrap_or((80, 24));
+    let size = Rect::new(0, 0, cols, rows);
+
+    if app.config.keys.up.iter().any(|k| app.check_key(k, key)) {
+        app.move_theme_selection(false, size);
+        return Ok(());
+    }
+    if app.config.keys.down.iter().any(|k| app.check_key(k, key)) {
+        app.move_theme_selection(true, size);
+        return Ok(());
+    }
+
     match key.code {
-        KeyCode::Char('y') | KeyCode::Char('Y') => {
-            app.confirm_yes()?;
+        KeyCode::Up => app.move_theme_selection(false, size),
+        KeyCode::Down => app.move_theme_selection(true, size),
+        KeyCode::Enter => app.select_theme(),
+        KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('Q') => app.close_theme_menu(size),
+        _ => {}
+    }
+
+    Ok(())
+}
+
+fn handle_confirmation_input(app: &mut App, key: &crossterm::event::KeyEvent) -> Result<()> {
+    use crossterm::event::KeyCode;
+
+    for key_str in &app.config.keys.confirm_yes_keys {
+        if app.check_key(key_str, key) {
+            return app.confirm_yes();
         }
-        KeyCode::Char('n') | KeyCode::Char('N') => {
+    }
+
+    for key_str in &app.config.keys.confirm_no_keys {
+        if app.check_key(key_str, key) {
             app.confirm_no();
+            return Ok(());
+        }
+    }
+
+    match key.code {
+        KeyCode::Left | KeyCode::Right => {
+            app.toggle_confirm_focus();
         }
         KeyCode::Enter => {
-            // Enter defaults to No (cancel)
-            app.confirm_no();
+            // Enter activates the focused button
+            if let AppState::Confirming {
+                focus: ConfirmFocus::Yes,
+                ..
+            } = app.state
+            {
+                app.confirm_yes()?;
+            } else {
+                app.confirm_no();
+            }
         }
         KeyCode::Esc => {
             app.confirm_no();
@@ -8538,6 +16164,31 @@ fn handle_confirmation_input(app: &mut App, key: &crossterm::event::KeyEvent) ->
     Ok(())
 }
 
+fn handle_error_input(app: &mut App, key: &crossterm::event::KeyEvent) -> Result<()> {
+    use crossterm::event::KeyCode;
+
+    match key.code {
+        KeyCode::Left | KeyCode::Right | KeyCode::Tab => {
+            if let AppState::CommandError { focus, .. } = &mut app.state {
+                *focus = focus.toggle();
+            }
+        }
+        KeyCode::Enter => app.error_choose()?,
+        KeyCode::Char('r') | KeyCode::Char('y') => {
+            if let AppState::CommandError { focus, .. } = &mut app.state {
+                *focus = ConfirmFocus::Yes;
+            }
+            app.error_choose()?;
+        }
+        KeyCode::Char('n') | KeyCode::Char('q') | KeyCode::Esc => {
+            app.state = AppState::Selecting;
+        }
+        KeyCode::Char('c') => app.copy_last_error(),
+        _ => {}
+    }
+    Ok(())
+}
+
 fn handle_grace_period_input(app: &mut App, _key: &crossterm::event::KeyEvent) -> Result<()> {
     // Any key press cancels the grace period
     app.cancel_grace_period();
@@ -8550,11 +16201,28 @@ fn handle_selecting_input(app: &mut App, key: &crossterm::event::KeyEvent) -> Re
         // Konami code activated! Rainbow mode toggled
         return Ok(());
     }
+    // Check animation menu keys
+    for key_str in &app.config.keys.animation_menu {
+        if app.check_key(key_str, key) {
+            app.open_animation_menu();
+            return Ok(());
+        }
+    }
 
-    // Check for animation menu hotkey (hidden feature - 'a' key)
-    if let KeyCode::Char('a') = key.code {
-        app.open_animation_menu();
-        return Ok(());
+    // Check theme menu keys
+    for key_str in &app.config.keys.theme_menu {
+        if app.check_key(key_str, key) {
+            app.open_theme_menu();
+            return Ok(());
+        }
+    }
+
+    // Check keybinding overlay keys
+    for key_str in &app.config.keys.help {
+        if app.check_key(key_str, key) {
+            app.state = AppState::HelpOverlay;
+            return Ok(());
+        }
     }
 
     // Check quit keys
@@ -8570,7 +16238,7 @@ fn handle_selecting_input(app: &mut App, key: &crossterm::event::KeyEvent) -> Re
         if app.check_key(key_str, key) {
             match app.config.layout_mode.as_str() {
                 "horizontal" | "compact" => app.previous_horizontal(),
-                "grid" => app.previous_grid(2),
+                "grid" => app.previous_grid(grid_columns(app)),
                 _ => app.previous(),
             }
             return Ok(());
@@ -8582,7 +16250,7 @@ fn handle_selecting_input(app: &mut App, key: &crossterm::event::KeyEvent) -> Re
         if app.check_key(key_str, key) {
             match app.config.layout_mode.as_str() {
                 "horizontal" | "compact" => app.next_horizontal(),
-                "grid" => app.next_grid(2),
+                "grid" => app.next_grid(grid_columns(app)),
                 _ => app.next(),
             }
             return Ok(());
@@ -8598,23 +16266,119 @@ fn handle_selecting_input(app: &mut App, key: &crossterm::event::KeyEvent) -> Re
     }
 
     // Check action shortcuts
+    if let Some(index) = find_shortcut_action(app, key) {
+        app.select_at_index(index)?;
+        return Ok(());
+    }
+
+    Ok(())
+}
+
+fn handle_submenu_input(app: &mut App, key: &crossterm::event::KeyEvent) -> Result<()> {
+    let AppState::SubMenu {
+        sub_actions,
+        selected,
+        ..
+    } = &app.state
+    else {
+        return Ok(());
+    };
+    let selected = *selected;
+
+    if key.code == KeyCode::Esc || app.config.keys.quit.iter().any(|k| app.check_key(k, key)) {
+        app.state = AppState::Selecting;
+        return Ok(());
+    }
+    if app.config.keys.up.iter().any(|k| app.check_key(k, key)) {
+        app.move_submenu_selection(false);
+        return Ok(());
+    }
+    if app.config.keys.down.iter().any(|k| app.check_key(k, key)) {
+        app.move_submenu_selection(true);
+        return Ok(());
+    }
+    if app.config.keys.select.iter().any(|k| app.check_key(k, key)) {
+        return app.select_submenu_item(selected);
+    }
+
+    // Shortcuts of the sub-menu entries
     if let KeyCode::Char(c) = key.code {
-        if let Some(index) = app
-            .actions
+        let pressed = c.to_lowercase().to_string();
+        if let Some(index) = sub_actions
             .iter()
-            .position(|a| a.shortcut.to_lowercase() == c.to_lowercase().to_string())
+            .position(|a| !a.shortcut.is_empty() && a.shortcut.to_lowercase() == pressed)
         {
-            app.select_at_index(index)?;
-            return Ok(());
+            return app.select_submenu_item(index);
         }
     }
 
     Ok(())
 }
 
+fn handle_help_overlay_input(app: &mut App, key: &crossterm::event::KeyEvent) {
+    if matches!(key.code, KeyCode::Esc | KeyCode::Enter | KeyCode::Char('q'))
+        || app.config.keys.help.iter().any(|k| app.check_key(k, key))
+    {
+        app.state = AppState::Selecting;
+    }
+}
+
+/// Find the action whose shortcut matches the pressed character (case-insensitive)
+fn find_shortcut_action(app: &App, key: &crossterm::event::KeyEvent) -> Option<usize> {
+    let KeyCode::Char(c) = key.code else {
+        return None;
+    };
+    let pressed = c.to_lowercase().to_string();
+    app.actions
+        .iter()
+        .position(|a| !a.shortcut.is_empty() && a.shortcut.to_lowercase() == pressed)
+}
+
+/// Jump to an action by shortcut from states other than `Selecting`.
+/// Returns true if the key was consumed.
+fn handle_global_shortcut(app: &mut App, key: &crossterm::event::KeyEvent) -> Result<bool> {
+    let Some(index) = find_shortcut_action(app, key) else {
+        return Ok(false);
+    };
+
+    match app.state {
+        AppState::AnimationMenu => app.close_animation_menu(),
+        AppState::ThemeMenu { .. } => {
+            let (cols, rows) = terminal::size().unwrap_or((80, 24));
+            app.close_theme_menu(Rect::new(0, 0, cols, rows));
+        }
+        AppState::Confirming { .. } => {
+            // The dialog's own keys take precedence over shortcuts
+            let keys = &app.config.keys;
+            if keys
+                .confirm_yes_keys
+                .iter()
+                .chain(&keys.confirm_no_keys)
+                .any(|key_str| app.check_key(key_str, key))
+            {
+                return Ok(false);
+            }
+            app.confirm_no();
+        }
+        // Selecting handles shortcuts itself, and any key cancels a grace period
+        AppState::Selecting | AppState::GracePeriod { .. } => return Ok(false),
+        // Nothing else may start while a command runs or its error is shown
+        AppState::Running { .. } | AppState::CommandError { .. } => return Ok(false),
+        // The overlay only lists the shortcuts, so its keys just close it
+        AppState::HelpOverlay => return Ok(false),
+        // Shortcuts pick entries of the sub-menu instead
+        AppState::SubMenu { .. } => return Ok(false),
+    }
+
+    app.select_at_index(index)?;
+    Ok(true)
+}
+
 fn handle_mouse_input(app: &mut App, mouse: MouseEvent) -> Result<()> {
     use crossterm::event::MouseEventKind;
 
+    let scroll_steps = app.config.mouse_scroll_speed.clamp(1, 5);
+
     match app.state {
         AppState::Selecting => {
             match mouse.kind {
@@ -8623,12 +16387,7 @@ fn handle_mouse_input(app: &mut App, mouse: MouseEvent) -> Result<()> {
                     let (cols, rows) = terminal::size().unwrap_or((80, 24));
                     let size = Rect::new(0, 0, cols, rows);
 
-                    // Calculate menu area based on layout mode
-                    let menu_area = if app.config.layout.auto_scale {
-                        calculate_auto_layout_menu_area(app, size)
-                    } else {
-                        calculate_fixed_layout_menu_area(app, size)
-                    };
+                    let menu_area = main_menu_area(app, size);
 
                     // Check if click is inside menu area
                     if mouse.column >= menu_area.x
@@ -8655,9 +16414,9 @@ fn handle_mouse_input(app: &mut App, mouse: MouseEvent) -> Result<()> {
                                     .min(app.actions.len().saturating_sub(1))
                             }
                             "grid" => {
-                                let grid_cols = 2usize;
-                                let cell_width = 20u16;
-                                let cell_height = 4u16;
+                                let grid_cols = grid_columns(app);
+                                let cell_width = grid_cell_width(app, size);
+                                let cell_height = GRID_CELL_HEIGHT;
                                 let inner = if app.config.border.enabled {
                                     Rect::new(
                                         menu_area.x + 1,
@@ -8680,8 +16439,27 @@ fn handle_mouse_input(app: &mut App, mouse: MouseEvent) -> Result<()> {
                                 // vertical layout (default)
                                 let relative_y = mouse.row.saturating_sub(menu_area.y);
                                 let border_offset = if app.config.border.enabled { 1 } else { 0 };
-                                let padding = app.config.layout.padding;
-                                relative_y.saturating_sub(border_offset + padding) as usize
+                                let rows = vertical_rows(app);
+                                let row = if app.config.layout.fullscreen {
+                                    // Items sit in equal slots spanning the inner height
+                                    let inner_height =
+                                        menu_area.height.saturating_sub(2).max(1) as usize;
+                                    let shown = app
+                                        .visible_items
+                                        .min(rows.len().saturating_sub(app.scroll_offset))
+                                        .max(1);
+                                    let row = relative_y.saturating_sub(1) as usize;
+                                    row * shown / inner_height + app.scroll_offset
+                                } else {
+                                    let padding = app.config.layout.padding;
+                                    relative_y.saturating_sub(border_offset + padding) as usize
+                                        + app.scroll_offset
+                                };
+                                // Clicks on a category divider select nothing
+                                match rows.get(row) {
+                                    Some(ListRow::Action(i)) => *i,
+                                    _ => usize::MAX,
+                                }
                             }
                         };
 
@@ -8692,61 +16470,132 @@ fn handle_mouse_input(app: &mut App, mouse: MouseEvent) -> Result<()> {
                     }
                 }
                 MouseEventKind::ScrollUp => {
-                    app.previous();
+                    for _ in 0..scroll_steps {
+                        app.previous();
+                    }
                 }
                 MouseEventKind::ScrollDown => {
-                    app.next();
+                    for _ in 0..scroll_steps {
+                        app.next();
+                    }
                 }
                 _ => {}
             }
         }
-        AppState::Confirming { action_index: _ } => {
-            match mouse.kind {
-                MouseEventKind::Down(_) => {
-                    // Simple click anywhere cancels confirmation
-                    app.confirm_no();
-                }
-                _ => {}
+        AppState::Confirming { .. } => {
+            if let MouseEventKind::Down(_) = mouse.kind {
+                // Simple click anywhere cancels confirmation
+                app.confirm_no();
             }
         }
         AppState::GracePeriod { .. } => {
+            if let MouseEventKind::Down(_) = mouse.kind {
+                // Any click cancels grace period
+                app.cancel_grace_period();
+            }
+        }
+        AppState::SubMenu { .. } => {
+            let (cols, rows) = terminal::size().unwrap_or((80, 24));
+            let area = submenu_area(app, Rect::new(0, 0, cols, rows));
             match mouse.kind {
+                // Clicking an entry runs it, clicking elsewhere closes the sub-menu
                 MouseEventKind::Down(_) => {
-                    // Any click cancels grace period
-                    app.cancel_grace_period();
+                    let inside = mouse.column > area.x
+                        && mouse.column + 1 < area.x + area.width
+                        && mouse.row > area.y
+                        && mouse.row + 1 < area.y + area.height;
+                    if inside {
+                        app.select_submenu_item((mouse.row - area.y - 1) as usize)?;
+                    } else {
+                        app.state = AppState::Selecting;
+                    }
                 }
+                MouseEventKind::ScrollUp => app.move_submenu_selection(false),
+                MouseEventKind::ScrollDown => app.move_submenu_selection(true),
                 _ => {}
             }
         }
-        AppState::AnimationMenu => match mouse.kind {
-            MouseEventKind::ScrollUp => {
-                app.previous_animation();
+        AppState::Running { .. } => {}
+        AppState::CommandError { .. } | AppState::HelpOverlay => {
+            if let MouseEventKind::Down(_) = mouse.kind {
+                app.state = AppState::Selecting;
+            }
+        }
+        AppState::AnimationMenu => {
+            let (cols, rows) = terminal::size().unwrap_or((80, 24));
+            let menu_area = animation_menu_area(Rect::new(0, 0, cols, rows));
+
+            // Only scroll the menu when the pointer is over it
+            if mouse.column >= menu_area.x
+                && mouse.column < menu_area.x + menu_area.width
+                && mouse.row >= menu_area.y
+                && mouse.row < menu_area.y + menu_area.height
+            {
+                match mouse.kind {
+                    MouseEventKind::ScrollUp => {
+                        for _ in 0..scroll_steps {
+                            app.previous_animation();
+                        }
+                    }
+                    MouseEventKind::ScrollDown => {
+                        for _ in 0..scroll_steps {
+                            app.next_animation();
+                        }
+                    }
+                    _ => {}
+                }
             }
-            MouseEventKind::ScrollDown => {
-                app.next_animation();
+        }
+        AppState::ThemeMenu { .. } => {
+            let (cols, rows) = terminal::size().unwrap_or((80, 24));
+            let size = Rect::new(0, 0, cols, rows);
+            for _ in 0..scroll_steps {
+                match mouse.kind {
+                    MouseEventKind::ScrollUp => app.move_theme_selection(false, size),
+                    MouseEventKind::ScrollDown => app.move_theme_selection(true, size),
+                    _ => {}
+                }
             }
-            _ => {}
-        },
+        }
     }
 
     Ok(())
 }
 
+/// Area of the main menu as seen by the mouse handler and the sub-menu popup
+fn main_menu_area(app: &App, size: Rect) -> Rect {
+    let vertical = !matches!(
+        app.config.layout_mode.as_str(),
+        "horizontal" | "grid" | "compact"
+    );
+    if vertical && app.config.layout.fullscreen {
+        vertical_menu_area(app, size, app.config.layout.auto_scale)
+    } else if app.config.layout_mode == "grid" {
+        grid_menu_area(app, size)
+    } else if app.config.layout.auto_scale {
+        calculate_auto_layout_menu_area(app, size)
+    } else {
+        calculate_fixed_layout_menu_area(app, size)
+    }
+}
+
 // Helper function to calculate menu area for mouse input (auto layout)
 fn calculate_auto_layout_menu_area(app: &App, size: Rect) -> Rect {
     let config = &app.config;
 
     // Calculate content dimensions
-    let max_label_width = app
-        .actions
+    let max_label_width = vertical_rows(app)
         .iter()
-        .map(|action| action.display_text(true).chars().count())
+        .map(|row| match row {
+            ListRow::Divider(name) => display_width(&category_divider(name, config)),
+            ListRow::Action(i) => display_width(&accessible_text(app, *i, &list_text(app, *i))),
+        })
         .max()
-        .unwrap_or(0) as u16;
+        .unwrap_or(0);
 
     let padding = config.layout.padding;
     let border_width = if config.border.enabled { 2 } else { 0 };
-    let title_width = config.title.chars().count() as u16;
+    let title_width = display_width(&app.title);
 
     let content_width = max_label_width.max(title_width.saturating_sub(2));
     let menu_width = content_width + (padding * 2) + border_width;
@@ -8758,12 +16607,12 @@ fn calculate_auto_layout_menu_area(app: &App, size: Rect) -> Rect {
     };
     let final_width = final_width.max(config.layout.min_width);
 
-    let action_count = app.actions.len() as u16;
+    let action_count = visible_action_rows(app);
     let menu_height = action_count + (padding * 2) + border_width;
-    let final_height = menu_height.max(config.layout.min_height);
+    let final_height = menu_height.max(config.layout.min_height).min(size.height);
 
     let x = (size.width.saturating_sub(final_width)) / 2;
-    let y = (size.height.saturating_sub(final_height)) / 2;
+    let y = menu_y(&config.layout, size, final_height);
 
     Rect {
         x,
@@ -8799,5 +16648,273 @@ fn calculate_fixed_layout_menu_area(app: &App, size: Rect) -> Rect {
         .constraints(horizontal_constraints)
         .split(vertical_chunks[1]);
 
-    horizontal_chunks[1]
+    position_fixed_layout(config, size, horizontal_chunks[1])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn konami_code_still_works_next_to_the_secret_word() {
+        let mut egg = EasterEggState::new();
+        // Feed keys the way run_app and handle_selecting_input do
+        let press = |egg: &mut EasterEggState, key: KeyCode| {
+            egg.check_secret_word(key) || egg.check_konami(key)
+        };
+
+        for key in egg.konami_code.clone() {
+            press(&mut egg, key);
+        }
+        assert!(egg.rainbow_mode);
+        assert!(!egg.secret_mode);
+
+        for c in SECRET_WORD.chars() {
+            press(&mut egg, KeyCode::Char(c));
+        }
+        assert!(egg.secret_mode);
+        assert!(egg.rainbow_mode);
+
+        for key in egg.konami_code.clone() {
+            press(&mut egg, key);
+        }
+        assert!(!egg.rainbow_mode);
+        assert!(egg.secret_mode);
+    }
+
+    #[test]
+    fn shift_tab_parses_as_backtab() {
+        use crossterm::event::{KeyEvent, KeyModifiers};
+
+        let binding = parse_key("Shift-Tab").unwrap();
+        assert_eq!(binding.key, KeyCode::BackTab);
+
+        // Terminals report Shift+Tab as BackTab with or without the Shift flag
+        let event = KeyEvent::new(KeyCode::BackTab, KeyModifiers::SHIFT);
+        assert!(matches_key(&binding, &event));
+        let event = KeyEvent::new(KeyCode::BackTab, KeyModifiers::NONE);
+        assert!(matches_key(&binding, &event));
+        assert_eq!(parse_key("Tab").unwrap().key, KeyCode::Tab);
+    }
+
+    #[test]
+    fn parses_css_and_shorthand_colors() {
+        assert_eq!(parse_color_rgb("rgb(255, 128, 0)"), Color::Rgb(255, 128, 0));
+        assert_eq!(parse_color_rgb("rgb(0,0,0)"), Color::Rgb(0, 0, 0));
+        assert_eq!(parse_color_rgb("RGB( 1 , 2 , 3 )"), Color::Rgb(1, 2, 3));
+        assert_eq!(parse_color_rgb("rgb(256, 0, 0)"), Color::White);
+
+        assert_eq!(
+            parse_color_rgb("hsl(120, 100%, 50%)"),
+            Color::Rgb(0, 255, 0)
+        );
+        assert_eq!(parse_color_rgb("hsl(360, 0%, 0%)"), Color::Rgb(0, 0, 0));
+        assert_eq!(parse_color_rgb("hsl(0, 100%, 50%)"), Color::Rgb(255, 0, 0));
+        assert_eq!(
+            parse_color_rgb("hsl(0, 0%, 100%)"),
+            Color::Rgb(255, 255, 255)
+        );
+        assert_eq!(parse_color_rgb("hsl(0, 150%, 50%)"), Color::White);
+
+        assert_eq!(parse_color_rgb("#f80"), Color::Rgb(255, 136, 0));
+        assert_eq!(parse_color_rgb("#FFF"), Color::Rgb(255, 255, 255));
+        assert_eq!(parse_color_rgb("#ff8800"), Color::Rgb(255, 136, 0));
+        assert_eq!(parse_color_rgb("#ggg"), Color::White);
+
+        assert_eq!(parse_color_rgb("color_196"), Color::Rgb(255, 0, 0));
+        assert_eq!(parse_color_rgb("not a color"), Color::White);
+    }
+
+    #[test]
+    fn expands_env_vars_with_defaults() {
+        let home = std::env::var("HOME").unwrap_or_default();
+        assert_eq!(expand_env_vars("$HOME/bin"), format!("{}/bin", home));
+        assert_eq!(expand_env_vars("${HOME}"), home);
+        assert_eq!(expand_env_vars("${NONEXISTENT:-default}"), "default");
+        assert_eq!(expand_env_vars("${NONEXISTENT}x"), "x");
+        assert_eq!(expand_env_vars("costs $5"), "costs $5");
+        assert_eq!(expand_env_vars("${unterminated"), "${unterminated");
+    }
+
+    #[test]
+    fn shell_quote_expanding_only_expands_variables() {
+        assert_eq!(shell_quote_expanding("$HOME/bin"), "\"$HOME/bin\"");
+        assert_eq!(shell_quote_expanding("${HOME}"), "\"${HOME}\"");
+        assert_eq!(shell_quote_expanding("costs $5"), "\"costs \\$5\"");
+
+        // A command substitution reaches the program as typed
+        let quoted = shell_quote_expanding("$(echo x)");
+        assert_eq!(quoted, "\"\\$(echo x)\"");
+        let output = Command::new("sh")
+            .arg("-c")
+            .arg(format!("printf %s {}", quoted))
+            .output()
+            .unwrap();
+        assert_eq!(String::from_utf8_lossy(&output.stdout), "$(echo x)");
+    }
+
+    #[test]
+    fn alpha_blend_mixes_colors() {
+        let white = (255, 255, 255);
+        let black = (0, 0, 0);
+        assert_eq!(alpha_blend(white, black, 255), white);
+        assert_eq!(alpha_blend(white, black, 0), black);
+        assert_eq!(alpha_blend(black, white, 255), black);
+        assert_eq!(alpha_blend(black, black, 128), black);
+        assert_eq!(alpha_blend(white, white, 128), white);
+        // 128/255 is just over half, which rounds up
+        assert_eq!(alpha_blend(white, black, 128), (128, 128, 128));
+        assert_eq!(
+            alpha_blend((200, 100, 0), (0, 100, 200), 128),
+            (100, 100, 100)
+        );
+    }
+
+    #[test]
+    fn display_width_counts_terminal_columns() {
+        // "⏻" is a single-width Unicode symbol, like the Nerd Font glyphs
+        assert_eq!(display_width("⏻ [s] Shutdown"), 14);
+        assert_eq!(display_width("\u{f011} [s] Shutdown"), 14);
+        // Emoji fallbacks take two columns, though they are more than two bytes
+        assert_eq!(display_width("🔌 [s] Shutdown"), 15);
+        assert_eq!(display_width("[s] Shutdown"), 12);
+        assert_eq!(display_width("关机"), 4);
+        assert_eq!(display_width(""), 0);
+    }
+
+    /// `detect_wm_from` with only `vars` set
+    fn detect_wm_with(vars: &[(&str, &str)]) -> Option<String> {
+        let vars: HashMap<String, String> = vars
+            .iter()
+            .map(|(var, value)| (var.to_string(), value.to_string()))
+            .collect();
+        detect_wm_from(|var| vars.get(var).cloned())
+    }
+
+    #[test]
+    fn detects_wayland_compositors_by_their_variables() {
+        for (var, wm) in WAYLAND_COMPOSITOR_MARKERS {
+            let detected = detect_wm_with(&[("WAYLAND_DISPLAY", "wayland-1"), (var, "x")]);
+            assert_eq!(detected.as_deref(), Some(*wm), "{}", var);
+        }
+        // Only trusted inside a Wayland session
+        assert_eq!(detect_wm_with(&[("SWAYSOCK", "/run/sway.sock")]), None);
+        let empty = detect_wm_with(&[("WAYLAND_DISPLAY", "wayland-1"), ("SWAYSOCK", "")]);
+        assert_eq!(empty, None);
+    }
+
+    #[test]
+    fn detects_window_managers_by_their_variables() {
+        for (var, wm) in WM_ENV_MARKERS {
+            assert_eq!(
+                detect_wm_with(&[(var, "1")]).as_deref(),
+                Some(*wm),
+                "{}",
+                var
+            );
+        }
+    }
+
+    #[test]
+    fn detects_window_managers_by_desktop_name() {
+        for (desktop, wm) in [
+            ("i3", "i3"),
+            ("bspwm", "bspwm"),
+            ("awesome", "awesome"),
+            ("Hyprland", "hyprland"),
+            ("sway", "sway"),
+            ("KDE", "kwin"),
+            ("plasmawayland", "kwin"),
+            ("GNOME", "mutter"),
+            ("openbox", "openbox"),
+            ("fluxbox", "fluxbox"),
+            ("fvwm3", "fvwm"),
+            ("niri", "niri"),
+            ("river", "river"),
+            ("labwc:wlroots", "labwc"),
+        ] {
+            let session = detect_wm_with(&[("XDG_SESSION_DESKTOP", desktop)]);
+            assert_eq!(session.as_deref(), Some(wm), "{}", desktop);
+            let current = detect_wm_with(&[("XDG_CURRENT_DESKTOP", desktop)]);
+            assert_eq!(current.as_deref(), Some(wm), "{}", desktop);
+        }
+
+        let both = [
+            ("XDG_SESSION_DESKTOP", "i3"),
+            ("XDG_CURRENT_DESKTOP", "GNOME"),
+        ];
+        assert_eq!(detect_wm_with(&both).as_deref(), Some("i3"));
+        assert_eq!(detect_wm_with(&[("XDG_SESSION_DESKTOP", "xfce")]), None);
+        assert_eq!(detect_wm_with(&[]), None);
+    }
+
+    /// Cells `draw_line` marks on a 10x10 screen, in row order
+    fn line_cells(x1: i16, y1: i16, x2: i16, y2: i16) -> Vec<(u16, u16)> {
+        let mut terminal = Terminal::new(ratatui::backend::TestBackend::new(10, 10)).unwrap();
+        let bounds = Rect::new(0, 0, 10, 10);
+        terminal
+            .draw(|f| draw_line(f, x1, y1, x2, y2, '#', Color::White, bounds))
+            .unwrap();
+        let buffer = terminal.backend().buffer();
+        let mut cells = Vec::new();
+        for y in 0..10 {
+            for x in 0..10 {
+                if buffer[(x, y)].symbol() == "#" {
+                    cells.push((x, y));
+                }
+            }
+        }
+        cells
+    }
+
+    #[test]
+    fn draw_line_in_every_direction() {
+        let horizontal = vec![(2, 3), (3, 3), (4, 3), (5, 3)];
+        assert_eq!(line_cells(2, 3, 5, 3), horizontal);
+        assert_eq!(line_cells(5, 3, 2, 3), horizontal);
+
+        let vertical = vec![(4, 1), (4, 2), (4, 3), (4, 4)];
+        assert_eq!(line_cells(4, 1, 4, 4), vertical);
+        assert_eq!(line_cells(4, 4, 4, 1), vertical);
+
+        let diagonal = vec![(1, 1), (2, 2), (3, 3), (4, 4)];
+        assert_eq!(line_cells(1, 1, 4, 4), diagonal);
+        assert_eq!(line_cells(4, 4, 1, 1), diagonal);
+
+        let anti_diagonal = vec![(4, 1), (3, 2), (2, 3), (1, 4)];
+        assert_eq!(line_cells(1, 4, 4, 1), anti_diagonal);
+        assert_eq!(line_cells(4, 1, 1, 4), anti_diagonal);
+
+        assert_eq!(line_cells(7, 7, 7, 7), vec![(7, 7)]);
+        // Cells off the screen are skipped, the rest still drawn
+        assert_eq!(line_cells(-2, 0, 1, 0), vec![(0, 0), (1, 0)]);
+    }
+
+    #[test]
+    fn actions_table_lists_the_whole_menu() {
+        let content = r#"
+            [actions.foo]
+            icon = "x"
+            label = "Foo"
+            command = "true"
+            args = []
+            enabled = true
+            confirm = false
+            favorite = false
+            shortcut = "f"
+
+            [actions.lock]
+            command = "swaylock"
+        "#;
+        let config = parse_config(content).unwrap();
+        let mut ids: Vec<&str> = config.actions.keys().map(String::as_str).collect();
+        ids.sort();
+        assert_eq!(ids, ["foo", "lock"]);
+        // Named default actions keep the fields they leave out
+        assert_eq!(config.actions["lock"].command, "swaylock");
+        assert_eq!(config.actions["lock"].label, "Lock");
+
+        let config = parse_config("title = \"bye\"").unwrap();
+        assert_eq!(config.actions.len(), Config::default().actions.len());
+    }
 }